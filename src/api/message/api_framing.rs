@@ -0,0 +1,88 @@
+use sal_core::{dbg::Dbg, error::Error};
+use crate::api::{
+    message::{
+        fields::{FieldData, FieldId, FieldKind, FieldSize, FieldSyn},
+        message::MessageField, message_kind::MessageKind,
+        parse_data::ParseData, parse_id::ParseId, parse_kind::ParseKind,
+        parse_size::ParseSize, parse_syn::ParseSyn,
+    },
+    socket::tcp_socket::TcpMessage,
+};
+///
+/// Framing schema of the API messages: `Syn | Id | Kind | Size | Data`
+/// - `syn` - the start byte of the message, 22 by default
+/// - `kind` - the kind of the `Data`, [MessageKind::Bytes] by default, [MessageKind::String] also supported
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiFraming {
+    pub syn: FieldSyn,
+    pub kind: MessageKind,
+}
+//
+//
+impl ApiFraming {
+    ///
+    /// Returns [ApiFraming] new instance
+    pub fn new(syn: u8, kind: MessageKind) -> Self {
+        Self { syn: FieldSyn(syn), kind }
+    }
+    ///
+    /// Returns Err if the API query can't be transferred in the `Data` of the specified `kind`
+    pub fn validate(&self, parent: impl Into<String>) -> Result<(), Error> {
+        match self.kind {
+            MessageKind::Bytes | MessageKind::String => Ok(()),
+            _ => Err(Error::new(parent, "ApiFraming.validate").err(format!("Kind '{:?}' not supported, expected Bytes or String", self.kind))),
+        }
+    }
+    ///
+    /// Returns [TcpMessage] building & parsing the messages of the schema
+    pub fn message(&self, dbg: &Dbg) -> TcpMessage {
+        TcpMessage::new(
+            dbg,
+            vec![
+                MessageField::Syn(self.syn.clone()),
+                MessageField::Id(FieldId(4)),
+                MessageField::Kind(FieldKind(self.kind.clone())),
+                MessageField::Size(FieldSize(4)),
+                MessageField::Data(FieldData(vec![]))
+            ],
+            ParseData::new(
+                dbg,
+                ParseSize::new(
+                    dbg,
+                    FieldSize(4),
+                    ParseKind::new(
+                        dbg,
+                        FieldKind(self.kind.clone()),
+                        ParseId::new(
+                            dbg,
+                            FieldId(4),
+                            ParseSyn::new(
+                                dbg,
+                                self.syn.clone(),
+                            ),
+                        ),
+                    ),
+                ),
+            ),
+        )
+    }
+    ///
+    /// Returns the message of the schema, containing `data`
+    /// - Used where [TcpMessage] can't be, it's not shared between threads
+    pub fn frame(&self, id: u32, data: &[u8]) -> Vec<u8> {
+        let mut frame = Vec::with_capacity(data.len() + 10);
+        frame.push(self.syn.0);
+        frame.extend(FieldId(id).to_be_bytes());
+        frame.extend(FieldKind(self.kind.clone()).to_bytes());
+        frame.extend(FieldSize(4).to_be_bytes(data.len() as u32));
+        frame.extend_from_slice(data);
+        frame
+    }
+}
+//
+//
+impl Default for ApiFraming {
+    fn default() -> Self {
+        Self { syn: FieldSyn::default(), kind: MessageKind::Bytes }
+    }
+}
@@ -1,3 +1,4 @@
+pub mod api_framing;
 pub mod fields;
 pub mod message_kind;
 pub mod message;
@@ -60,5 +60,6 @@ impl MessageParse<(FieldId, MessageKind, FieldSize, Bytes)> for ParseData {
     fn reset(&mut self) {
         self.field.reset();
         self.buffer.clear();
+        self.remains.clear();
     }
 }
@@ -1,3 +1,4 @@
 pub mod message;
+pub mod query;
 pub mod reply;
-pub mod socket;
+pub mod socket;
\ No newline at end of file
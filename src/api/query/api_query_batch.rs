@@ -0,0 +1,63 @@
+use serde::{Serialize, Deserialize};
+use crate::api::query::api_query_sql_params::ApiQuerySqlParams;
+///
+/// Batch of the sql statements executed by the server atomically in a single transaction
+/// {
+///     "id": "123",
+///     "batch": {
+///         "database": "database name",
+///         "statements": [
+///             {"sql": "insert into header (name) values ($1);", "params": ["order"]},
+///             {"sql": "insert into line (name) values (:name);", "params": {"name": "line"}}
+///         ]
+///     },
+///     "keep-alive": true,
+/// }
+/// - Reply contains one result set per statement: `{"rows": [...], "affected": n}`
+/// - Whole transaction rolled back on the first failed statement
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiQueryBatch {
+    pub database: String,
+    pub statements: Vec<ApiQueryBatchStatement>,
+}
+///
+/// 
+impl ApiQueryBatch {
+    ///
+    /// Creates new instance of ApiQueryBatch
+    pub fn new(
+        database: impl Into<String>,
+        statements: Vec<ApiQueryBatchStatement>,
+    ) -> Self {
+        Self {
+            database: database.into(),
+            statements,
+        }
+    }
+}
+///
+/// Single statement of the [ApiQueryBatch]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiQueryBatchStatement {
+    pub sql: String,
+    #[serde(default, skip_serializing_if = "ApiQuerySqlParams::is_empty", deserialize_with = "ApiQuerySqlParams::deserialize_nullable")]
+    pub params: ApiQuerySqlParams,
+}
+///
+/// 
+impl ApiQueryBatchStatement {
+    ///
+    /// Creates new instance of ApiQueryBatchStatement
+    pub fn new(sql: impl Into<String>) -> Self {
+        Self {
+            sql: sql.into(),
+            params: ApiQuerySqlParams::default(),
+        }
+    }
+    ///
+    /// Returns [ApiQueryBatchStatement] with specified parameters
+    pub fn with_params(mut self, params: ApiQuerySqlParams) -> Self {
+        self.params = params;
+        self
+    }
+}
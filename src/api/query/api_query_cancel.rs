@@ -0,0 +1,23 @@
+use serde::{Serialize, Deserialize};
+///
+/// Control query, cancels the request currently running on the server
+/// {
+///     "id": "123",
+///     "cancel": {
+///         "id": "id of the request to be cancelled"
+///     },
+/// }
+/// - Only requests with the same `authToken` can be cancelled
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiQueryCancel {
+    pub id: String,
+}
+///
+/// 
+impl ApiQueryCancel {
+    ///
+    /// Creates new instance of ApiQueryCancel
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
+}
@@ -0,0 +1,60 @@
+use serde::{Serialize, Deserialize};
+use crate::api::query::api_query_sql::ApiQuerySql;
+///
+/// Server side cursor, returns the rows of the large `select` page by page
+/// over the keep-alive connection
+/// - `open` - opens the cursor and returns the first page, reply contains the cursor `id`
+///     {"cursor": {"open": {"database": "database name", "sql": "select ...", "params": [...], "pageSize": 100}}}
+/// - `next` - returns the next page of the cursor
+///     {"cursor": {"next": "cursor id"}}
+/// - `close` - closes the cursor before the last page returned
+///     {"cursor": {"close": "cursor id"}}
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiQueryCursor {
+    Open(ApiQueryCursorOpen),
+    Next(String),
+    Close(String),
+}
+//
+//
+impl ApiQueryCursor {
+    ///
+    /// Default number of rows in the page
+    pub const PAGE_SIZE: usize = 1000;
+}
+///
+/// The query the cursor opened for
+/// {
+///     "id": "123",
+///     "cursor": {
+///         "open": {
+///             "database": "database name",
+///             "sql": "select * from event where time > $1;",
+///             "params": ["2024-01-01T00:00:00Z"],
+///             "pageSize": 1000
+///         }
+///     },
+///     "keep-alive": true,
+/// }
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiQueryCursorOpen {
+    #[serde(flatten)]
+    pub query: ApiQuerySql,
+    #[serde(rename = "pageSize", default = "ApiQueryCursorOpen::page_size_default")]
+    pub page_size: usize,
+}
+///
+/// 
+impl ApiQueryCursorOpen {
+    ///
+    /// Creates new instance of ApiQueryCursorOpen
+    pub fn new(query: ApiQuerySql, page_size: usize) -> Self {
+        Self { query, page_size }
+    }
+    ///
+    ///
+    fn page_size_default() -> usize {
+        ApiQueryCursor::PAGE_SIZE
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Serialize, Deserialize};
+use crate::api::query::api_query_params::params_object;
+///
+/// Runs the executable on the server
+/// {
+///     "id": "123",
+///     "executable": {
+///         "name": "executable name",
+///         "params": {"a": 4, "b": 7}
+///     },
+///     "keep-alive": true,
+/// }
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiQueryExecutable {
+    pub name: String,
+    pub params: serde_json::Map<String, serde_json::Value>,
+}
+///
+/// 
+impl ApiQueryExecutable {
+    ///
+    /// Creates new instance of ApiQuery
+    pub fn new(
+        name: impl Into<String>,
+        params: serde_json::Map<String, serde_json::Value>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            params,
+        }
+    }
+    ///
+    /// Creates new instance of ApiQueryExecutable with the `params` of any serializable type
+    /// - Returns Err if `params` isn't serialized into the json object
+    pub fn from_params<T: Serialize>(name: impl Into<String>, params: &T) -> Result<Self, String> {
+        Ok(Self::new(name, params_object("ApiQueryExecutable.from_params", params)?))
+    }
+}
@@ -0,0 +1,37 @@
+use serde::{Serialize, Deserialize};
+use crate::api::query::{
+    api_query_batch::ApiQueryBatch, api_query_cancel::ApiQueryCancel, api_query_cursor::ApiQueryCursor,
+    api_query_executable::ApiQueryExecutable, api_query_login::ApiQueryLogin, api_query_python::ApiQueryPython,
+    api_query_sql::ApiQuerySql, api_query_subscribe::ApiQuerySubscribe,
+};
+///
+/// Contains properties specific to quety kind, shared by the client & the server
+///  - ApiQuerySql
+///  - ApiQueryBatch
+///  - ApiQueryCursor
+///  - ApiQueryCancel
+///  - ApiQuerySubscribe
+///  - ApiQueryLogin
+///  - ApiQueryPython
+///  - ApiQueryQxecutable
+///
+/// Serialized as the single field named by the kind: `{"sql": {...}}`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ApiQueryKind {
+    #[serde(rename = "sql")]
+    Sql(ApiQuerySql),
+    #[serde(rename = "batch")]
+    Batch(ApiQueryBatch),
+    #[serde(rename = "cursor")]
+    Cursor(ApiQueryCursor),
+    #[serde(rename = "cancel")]
+    Cancel(ApiQueryCancel),
+    #[serde(rename = "subscribe")]
+    Subscribe(ApiQuerySubscribe),
+    #[serde(rename = "login")]
+    Login(ApiQueryLogin),
+    #[serde(rename = "python")]
+    Python(ApiQueryPython),
+    #[serde(rename = "executable")]
+    Executable(ApiQueryExecutable),
+}
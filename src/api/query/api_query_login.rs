@@ -0,0 +1,35 @@
+use serde::{Serialize, Deserialize};
+///
+/// Auth query, exchanges the service `user` / `pass` for the signed `authToken` with expiry
+/// {
+///     "id": "123",
+///     "login": {
+///         "user": "service user",
+///         "pass": "service password"
+///     },
+/// }
+/// - Reply contains single row: {"token": "signed token", "user": "service user", "expires": unix timestamp, sec}
+#[derive(Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiQueryLogin {
+    pub user: String,
+    pub pass: String,
+}
+///
+/// 
+impl ApiQueryLogin {
+    ///
+    /// Creates new instance of ApiQueryLogin
+    pub fn new(user: impl Into<String>, pass: impl Into<String>) -> Self {
+        Self { user: user.into(), pass: pass.into() }
+    }
+}
+//
+// The password never goes into the log
+impl std::fmt::Debug for ApiQueryLogin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiQueryLogin")
+            .field("user", &self.user)
+            .field("pass", &"***")
+            .finish()
+    }
+}
@@ -0,0 +1,12 @@
+use serde::Serialize;
+///
+/// Returns the `params` of the python / executable query serialized into the json object
+/// - Returns Err if `params` can't be serialized or serialized not into the object,
+///   the server rejects such a query
+pub fn params_object<T: Serialize>(parent: &str, params: &T) -> Result<serde_json::Map<String, serde_json::Value>, String> {
+    match serde_json::to_value(params) {
+        Ok(serde_json::Value::Object(params)) => Ok(params),
+        Ok(params) => Err(format!("{} | Params must be serialized into the object, but found: {}", parent, params)),
+        Err(err) => Err(format!("{} | Params serialize error: {}", parent, err)),
+    }
+}
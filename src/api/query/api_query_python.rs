@@ -0,0 +1,38 @@
+use serde::{Serialize, Deserialize};
+use crate::api::query::api_query_params::params_object;
+///
+/// Runs the python script on the server
+/// {
+///     "id": "123",
+///     "python": {
+///         "script": "script name",
+///         "params": {"a": 4, "b": 7}
+///     },
+///     "keep-alive": true,
+/// }
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiQueryPython {
+    pub script: String,
+    pub params: serde_json::Map<String, serde_json::Value>,
+}
+///
+/// 
+impl ApiQueryPython {
+    ///
+    /// Creates new instance of ApiQuery
+    pub fn new(
+        script: impl Into<String>,
+        params: serde_json::Map<String, serde_json::Value>,
+    ) -> Self {
+        Self {
+            script: script.into(),
+            params,
+        }
+    }
+    ///
+    /// Creates new instance of ApiQueryPython with the `params` of any serializable type
+    /// - Returns Err if `params` isn't serialized into the json object
+    pub fn from_params<T: Serialize>(script: impl Into<String>, params: &T) -> Result<Self, String> {
+        Ok(Self::new(script, params_object("ApiQueryPython.from_params", params)?))
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Serialize, Deserialize};
+use crate::api::query::api_query_sql_params::ApiQuerySqlParams;
+///
+/// Wrap a structure of an API query
+/// {
+///     "id": "123",
+///     "sql": {
+///         "database": "database name",
+///         "sql": "select * from customer where id = $1 or name = $2;",
+///         "params": [1, "Alex"]
+///     },
+///     "keep-alive": true,
+/// }
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiQuerySql {
+    pub database: String,
+    pub sql: String,
+    #[serde(default, skip_serializing_if = "ApiQuerySqlParams::is_empty", deserialize_with = "ApiQuerySqlParams::deserialize_nullable")]
+    pub params: ApiQuerySqlParams,
+}
+///
+/// 
+impl ApiQuerySql {
+    ///
+    /// Creates new instance of ApiQuery
+    pub fn new(
+        database: impl Into<String>,
+        sql: impl Into<String>,
+    ) -> Self {
+        Self {
+            database: database.into(),
+            sql: sql.into(),
+            params: ApiQuerySqlParams::default(),
+        }
+    }
+    ///
+    /// Returns [ApiQuerySql] with specified parameters,
+    /// bound by the server to the prepared statement, never interpolated into the sql
+    pub fn with_params(mut self, params: ApiQuerySqlParams) -> Self {
+        self.params = params;
+        self
+    }
+}
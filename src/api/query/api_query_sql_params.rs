@@ -0,0 +1,38 @@
+use serde::{Deserializer, Serialize, Deserialize};
+///
+/// Parameters of the [ApiQuerySql](crate::api::query::api_query_sql::ApiQuerySql)
+/// - `Positional` - bound to the `$1`, `$2`, ... placeholders in the order of the values
+/// - `Named` - bound to the `:name` placeholders by the name
+///
+/// Values are typed JSON, never interpolated into the sql string,
+/// bound to the prepared statement on the server side
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum ApiQuerySqlParams {
+    Positional(Vec<serde_json::Value>),
+    Named(serde_json::Map<String, serde_json::Value>),
+}
+//
+//
+impl Default for ApiQuerySqlParams {
+    fn default() -> Self {
+        Self::Positional(vec![])
+    }
+}
+//
+//
+impl ApiQuerySqlParams {
+    ///
+    /// Returns true if no parameters contains
+    pub fn is_empty(&self) -> bool {
+        match self {
+            ApiQuerySqlParams::Positional(params) => params.is_empty(),
+            ApiQuerySqlParams::Named(params) => params.is_empty(),
+        }
+    }
+    ///
+    /// Deserializes the `params` field, `null` means no parameters, as the missing field
+    pub fn deserialize_nullable<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Option::<Self>::deserialize(deserializer).map(Option::unwrap_or_default)
+    }
+}
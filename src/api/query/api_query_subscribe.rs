@@ -0,0 +1,31 @@
+use serde::{Serialize, Deserialize};
+///
+/// Subscribes the keep-alive connection to the postgres notifications
+/// {
+///     "id": "123",
+///     "keepAlive": true,
+///     "subscribe": {
+///         "database": "database name",
+///         "channels": ["channel1", "channel2"]
+///     },
+/// }
+/// - The server runs `LISTEN channel` for each of the `channels`
+///   and forwards each `NOTIFY` payload as unsolicited frame, see [ApiNotification](crate::api::reply::api_notification::ApiNotification)
+/// - Subscription lives until the connection closed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiQuerySubscribe {
+    pub database: String,
+    pub channels: Vec<String>,
+}
+///
+/// 
+impl ApiQuerySubscribe {
+    ///
+    /// Creates new instance of ApiQuerySubscribe
+    pub fn new(database: impl Into<String>, channels: &[&str]) -> Self {
+        Self {
+            database: database.into(),
+            channels: channels.iter().map(|channel| channel.to_string()).collect(),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+pub mod api_query_kind;
+pub mod api_query_sql;
+pub mod api_query_sql_params;
+pub mod api_query_batch;
+pub mod api_query_cursor;
+pub mod api_query_cancel;
+pub mod api_query_subscribe;
+pub mod api_query_login;
+pub mod api_query_params;
+pub mod api_query_python;
+pub mod api_query_executable;
\ No newline at end of file
@@ -0,0 +1,48 @@
+use serde::{Serialize, Deserialize};
+///
+/// The postgres `NOTIFY` forwarded by the server to the subscribed connection
+/// - Sent as unsolicited frame, not related to any request:
+/// {
+///     "notification": {
+///         "channel": "channel1",
+///         "payload": "payload of the NOTIFY",
+///         "processId": 123
+///     }
+/// }
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiNotification {
+    pub channel: String,
+    pub payload: String,
+    #[serde(rename = "processId")]
+    pub process_id: u32,
+}
+///
+/// The frame containing [ApiNotification]
+#[derive(Debug, Serialize, Deserialize)]
+struct ApiNotificationFrame {
+    notification: ApiNotification,
+}
+//
+//
+impl ApiNotification {
+    ///
+    /// Returns [ApiNotification] new instance
+    pub fn new(channel: impl Into<String>, payload: impl Into<String>, process_id: u32) -> Self {
+        Self {
+            channel: channel.into(),
+            payload: payload.into(),
+            process_id,
+        }
+    }
+    ///
+    /// Returns bytes of the frame containing Self
+    pub fn as_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(&ApiNotificationFrame { notification: self.clone() }).unwrap_or_default()
+    }
+    ///
+    /// Returns [ApiNotification] if `bytes` contains the notification frame
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        serde_json::from_slice::<ApiNotificationFrame>(bytes).ok()
+            .map(|frame| frame.notification)
+    }
+}
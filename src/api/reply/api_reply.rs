@@ -1,5 +1,5 @@
 use indexmap::IndexMap;
-use serde::{Serialize, Deserialize};
+use serde::{de::DeserializeOwned, Serialize, Deserialize};
 use crate::error::api_error::ApiError;
 
 ///
@@ -13,6 +13,17 @@ pub struct ApiReply {
     #[serde(rename = "keepAlive")]
     pub keep_alive: bool,
     pub error: ApiError,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<ApiReplyCursor>,
+}
+///
+/// State of the server side cursor, the page of rows in the [ApiReply] belongs to
+/// - `id` - to be passed in the `next` / `close` cursor queries
+/// - `done` - true if the last page is returned and cursor is closed on the server side
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiReplyCursor {
+    pub id: String,
+    pub done: bool,
 }
 impl ApiReply {
     ///
@@ -42,9 +53,16 @@ impl ApiReply {
             query: query.into(),
             data,
             error: ApiError::empty(),
+            cursor: None,
         }        
     }
     ///
+    /// Returns ApiReply containing the page of the server side cursor
+    pub fn with_cursor(mut self, cursor: ApiReplyCursor) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+    ///
     /// Creates ApiReply with error information only
     pub fn error(
         auth_token: impl Into<String>,
@@ -60,6 +78,7 @@ impl ApiReply {
             query: query.into(),
             data: vec![],
             error,
+            cursor: None,
         }        
     }
     ///
@@ -67,6 +86,22 @@ impl ApiReply {
     pub fn has_error(&self) -> bool {
         !self.error.is_empty()
     }
+    ///
+    /// Returns `data` rows deserialized into `T`
+    /// - Each row is mapped onto the `T` fields by the column names
+    /// - Error contains the index of the row and the column failed to be deserialized
+    pub fn data_as<T: DeserializeOwned>(&self) -> Result<Vec<T>, String> {
+        self.data.iter().enumerate().map(|(index, row)| {
+            let row = serde_json::Value::Object(row.clone().into_iter().collect());
+            serde_path_to_error::deserialize(row).map_err(|err| {
+                let column = err.path().to_string();
+                match column.as_str() {
+                    "." => format!("ApiReply.data_as | Row {}: {}", index, err.inner()),
+                    _ => format!("ApiReply.data_as | Row {}, column '{}': {}", index, column, err.inner()),
+                }
+            })
+        }).collect()
+    }
 }
 ///
 /// 
@@ -1 +1,2 @@
-pub mod api_reply;
\ No newline at end of file
+pub mod api_reply;
+pub mod api_notification;
\ No newline at end of file
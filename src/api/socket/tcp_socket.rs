@@ -1,4 +1,4 @@
-use std::{io::{BufReader, BufWriter, Read, Write}, net::{Shutdown, SocketAddr, TcpStream, ToSocketAddrs}, sync::Arc, time::{Duration, Instant}};
+use std::{io::{BufWriter, Read, Write}, net::{Shutdown, SocketAddr, TcpStream, ToSocketAddrs}, sync::Arc, time::{Duration, Instant}};
 use sal_core::{dbg::Dbg, error::Error};
 use crate::api::message::{fields::{FieldId, FieldSize}, message::{Bytes, Message, MessageParse}, message_kind::MessageKind, msg_kind::MsgKind};
 ///
@@ -67,6 +67,12 @@ impl TcpSocket {
         }
     }
     ///
+    /// Returns [TcpSocket] with specified connect / read / write timeout (default 10 sec)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+    ///
     /// Opens a connection to the TCP Socket and preparing the `Message`
     pub fn connect(&mut self) -> Result<Arc<TcpStream>, Error> {
         let error = Error::new(&self.dbg, "connect");
@@ -111,7 +117,10 @@ impl TcpSocket {
     }
     ///
     /// Closes a connection
+    /// - The bytes of the closed connection buffered by the message parser are dropped,
+    ///   so the next connection is parsed from the clean state
     pub fn close(&mut self) -> Result<(), Error> {
+        self.message.reset();
         match &self.connection.take() {
             Some(stream) => {
                 stream
@@ -162,7 +171,13 @@ impl TcpSocket {
         match self.connect() {
             Ok(stream) => {
                 let time = Instant::now();
-                let mut stream = BufReader::new(stream.as_ref());
+                // The message received together with the previous one is returned first
+                if let Ok((id, kind, _size, bytes)) = self.message.parse(vec![]) {
+                    if let Some(msg) = self.msg_kind(id, kind, bytes) {
+                        return msg;
+                    }
+                }
+                let mut stream = stream.as_ref();
                 loop {
                     match stream.read(&mut self.buf) {
                         Ok(len) => {
@@ -171,25 +186,8 @@ impl TcpSocket {
                                 Ok((id, kind, size, bytes)) => {
                                     let dbg_bytes = if bytes.len() > 16 {format!("{:?} ...", &bytes[..16])} else {format!("{:?}", bytes)};
                                     log::trace!("{}.read | id: {:?},  kind: {:?},  size: {:?},  bytes: {:?}", self.dbg, id, kind, size, dbg_bytes);
-                                    match kind {
-                                        MessageKind::Any => return Ok((id.clone(), MsgKind::Bytes(bytes.to_owned()))),
-                                        MessageKind::Empty => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbg, kind),
-                                        MessageKind::Bytes => return Ok((id.clone(), MsgKind::Bytes(bytes.to_owned()))),
-                                        MessageKind::Bool => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbg, kind),
-                                        MessageKind::U16 => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbg, kind),
-                                        MessageKind::U32 => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbg, kind),
-                                        MessageKind::U64 => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbg, kind),
-                                        MessageKind::I16 => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbg, kind),
-                                        MessageKind::I32 => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbg, kind),
-                                        MessageKind::I64 => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbg, kind),
-                                        MessageKind::F32 => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbg, kind),
-                                        MessageKind::F64 => log::warn!("{} | Message of kind '{:?}' - is not implemented yet", self.dbg, kind),
-                                        MessageKind::String => match String::from_utf8(bytes) {
-                                            Ok(value) => return Ok((id.clone(), MsgKind::String(value))),
-                                            Err(err) => return Err(format!("{}.read | Message::string parse error: {}", self.dbg, err).into()),
-                                        },
-                                        MessageKind::Timestamp => log::warn!("{}.read | Message of kind '{:?}' - is not implemented yet", self.dbg, kind),
-                                        MessageKind::Duration => log::warn!("{}.read | Message of kind '{:?}' - is not implemented yet", self.dbg, kind),
+                                    if let Some(msg) = self.msg_kind(id, kind, bytes) {
+                                        return msg;
                                     }
                                 }
                                 Err(err) => {
@@ -228,6 +226,27 @@ impl TcpSocket {
         };
     }
     ///
+    /// Returns the payload of the parsed message depending on it's `kind`
+    /// - None if the `kind` is not implemented yet
+    fn msg_kind(&self, id: FieldId, kind: MessageKind, bytes: Bytes) -> Option<Result<(FieldId, MsgKind), Error>> {
+        match kind {
+            MessageKind::Any => Some(Ok((id, MsgKind::Bytes(bytes)))),
+            MessageKind::Bytes => Some(Ok((id, MsgKind::Bytes(bytes)))),
+            MessageKind::String => match String::from_utf8(bytes) {
+                Ok(value) => Some(Ok((id, MsgKind::String(value)))),
+                Err(err) => Some(Err(format!("{}.read | Message::string parse error: {}", self.dbg, err).into())),
+            },
+            MessageKind::Empty | MessageKind::Bool |
+            MessageKind::U16 | MessageKind::U32 | MessageKind::U64 |
+            MessageKind::I16 | MessageKind::I32 | MessageKind::I64 |
+            MessageKind::F32 | MessageKind::F64 |
+            MessageKind::Timestamp | MessageKind::Duration => {
+                log::warn!("{}.read | Message of kind '{:?}' - is not implemented yet", self.dbg, kind);
+                None
+            }
+        }
+    }
+    ///
     /// Returns Connection status dipending on IO Error
     fn parse_err(&self, input: std::io::Error) -> IsConnected<(), Error> {
         log::warn!("{}.parse_err | error reading from socket: {:?}", self.dbg, input);
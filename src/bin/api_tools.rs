@@ -0,0 +1,47 @@
+use std::process::ExitCode;
+use clap::Parser;
+use api_tools::{
+    cli::{cli_args::CliArgs, cli_query::CliQuery, repl::Repl},
+    client::api_request::ApiRequest,
+};
+///
+/// Command-line client for ad-hoc API queries
+fn main() -> ExitCode {
+    let args = CliArgs::parse();
+    let query = match CliQuery::from_command(&args.command) {
+        Ok(query) => query,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let keep_alive = query.is_none();
+    let mut builder = ApiRequest::builder("api-tools")
+        .address(args.address.as_str())
+        .auth_token(args.token.as_str())
+        .keep_alive(keep_alive)
+        .debug(args.debug)
+        .timeout(args.timeout());
+    if let (Some(user), Some(pass)) = (&args.user, &args.pass) {
+        builder = builder.login(user, pass);
+    }
+    let mut request = match builder.build() {
+        Ok(request) => request,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let result = match query {
+        Some(query) => CliQuery::perform(&mut request, query, keep_alive, args.format)
+            .map(|out| println!("{}", out)),
+        None => Repl::new(request, args.format).run(),
+    };
+    match result {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
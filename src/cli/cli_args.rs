@@ -0,0 +1,87 @@
+use std::{path::PathBuf, time::Duration};
+use clap::{Parser, Subcommand};
+use crate::cli::reply_format::ReplyFormat;
+///
+/// Command-line client for ad-hoc API queries
+/// ```
+/// api-tools -a 127.0.0.1:8080 -t token sql -d database "select * from event;"
+/// api-tools --format csv python --params '{"a": 4}' py-test
+/// api-tools file query.json
+/// api-tools repl
+/// ```
+#[derive(Debug, Parser)]
+#[command(name = "api-tools", version, about = "Command-line client for ad-hoc API queries")]
+pub struct CliArgs {
+    /// API server address, ip:port
+    #[arg(short, long, default_value = "127.0.0.1:8080")]
+    pub address: String,
+    /// Auth token sent with every request
+    #[arg(short, long, default_value = "")]
+    pub token: String,
+    /// Service user, the token is requested by `login` if specified
+    #[arg(long, requires = "pass")]
+    pub user: Option<String>,
+    /// Service password
+    #[arg(long, requires = "user")]
+    pub pass: Option<String>,
+    /// Connect / read / write timeout, sec
+    #[arg(long, default_value_t = 10)]
+    pub timeout: u64,
+    /// Output format of the reply rows
+    #[arg(short, long, value_enum, default_value_t = ReplyFormat::Table)]
+    pub format: ReplyFormat,
+    /// Requests the server to include the query & the error details into the reply
+    #[arg(long)]
+    pub debug: bool,
+    #[command(subcommand)]
+    pub command: CliCommand,
+}
+//
+//
+impl CliArgs {
+    ///
+    /// Returns the timeout as [Duration]
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout)
+    }
+}
+///
+/// The query to be performed
+#[derive(Debug, Subcommand)]
+pub enum CliCommand {
+    /// Performs `sql` query
+    Sql {
+        /// Database name
+        #[arg(short, long)]
+        database: String,
+        /// Sql parameters, json array (`$1`, `$2`, ...) or object (`:name`)
+        #[arg(short, long)]
+        params: Option<String>,
+        /// Sql statement
+        sql: String,
+    },
+    /// Performs `python` query
+    Python {
+        /// Script parameters, json object
+        #[arg(short, long, default_value = "{}")]
+        params: String,
+        /// Script service name
+        script: String,
+    },
+    /// Performs `executable` query
+    Executable {
+        /// Executable parameters, json object
+        #[arg(short, long, default_value = "{}")]
+        params: String,
+        /// Executable service name
+        name: String,
+    },
+    /// Performs the query read from the json file, `{"sql": {...}}`,
+    /// the logged request can be passed as is
+    File {
+        /// Path to the json file
+        path: PathBuf,
+    },
+    /// Interactive mode over single keep-alive connection
+    Repl,
+}
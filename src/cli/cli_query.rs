@@ -0,0 +1,99 @@
+use std::path::Path;
+use crate::{
+    api::{reply::api_reply::ApiReply, query::{
+        api_query_executable::ApiQueryExecutable, api_query_kind::ApiQueryKind, api_query_python::ApiQueryPython,
+        api_query_sql::ApiQuerySql, api_query_sql_params::ApiQuerySqlParams,
+    }},
+    cli::{cli_args::CliCommand, reply_format::ReplyFormat},
+    client::{api_query::ApiQuery, api_request::ApiRequest},
+};
+///
+/// Builds [ApiQueryKind] from the command-line arguments or the json and performs it
+pub struct CliQuery;
+//
+//
+impl CliQuery {
+    ///
+    /// Fields of the request, which are not the part of the query
+    const REQUEST_FIELDS: [&str; 4] = ["authToken", "id", "keepAlive", "debug"];
+    ///
+    /// Returns the query of the `command`, None for the [CliCommand::Repl]
+    pub fn from_command(command: &CliCommand) -> Result<Option<ApiQueryKind>, String> {
+        match command {
+            CliCommand::Sql { database, params, sql } => Self::sql(database, params.as_deref(), sql).map(Some),
+            CliCommand::Python { params, script } => Self::python(script, params).map(Some),
+            CliCommand::Executable { params, name } => Self::executable(name, params).map(Some),
+            CliCommand::File { path } => Self::file(path).map(Some),
+            CliCommand::Repl => Ok(None),
+        }
+    }
+    ///
+    /// Performs the `query` and returns the rows of the reply formatted
+    /// - Returns Err if the request failed or the reply contains an error
+    pub fn perform(request: &mut ApiRequest, query: ApiQueryKind, keep_alive: bool, format: ReplyFormat) -> Result<String, String> {
+        let bytes = request.fetch_with(&ApiQuery::new(query, keep_alive), keep_alive)
+            .map_err(|err| err.to_string())?;
+        let reply = ApiReply::try_from(bytes)?;
+        match reply.has_error() {
+            true => Err(reply.error.to_string()),
+            false => Ok(format.format(&reply.data)),
+        }
+    }
+    ///
+    /// Returns `sql` query
+    /// - `params` - json array or object
+    pub fn sql(database: &str, params: Option<&str>, sql: &str) -> Result<ApiQueryKind, String> {
+        let query = ApiQuerySql::new(database, sql);
+        let query = match params {
+            Some(params) => query.with_params(
+                serde_json::from_str::<ApiQuerySqlParams>(params)
+                    .map_err(|err| format!("CliQuery.sql | Invalid params, json array or object expected: {}", err))?,
+            ),
+            None => query,
+        };
+        Ok(ApiQueryKind::Sql(query))
+    }
+    ///
+    /// Returns `python` query
+    /// - `params` - json object
+    pub fn python(script: &str, params: &str) -> Result<ApiQueryKind, String> {
+        Ok(ApiQueryKind::Python(ApiQueryPython::new(script, Self::object(params)?)))
+    }
+    ///
+    /// Returns `executable` query
+    /// - `params` - json object
+    pub fn executable(name: &str, params: &str) -> Result<ApiQueryKind, String> {
+        Ok(ApiQueryKind::Executable(ApiQueryExecutable::new(name, Self::object(params)?)))
+    }
+    ///
+    /// Returns the query read from the json file
+    pub fn file(path: &Path) -> Result<ApiQueryKind, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|err| format!("CliQuery.file | Read '{}' error: {}", path.display(), err))?;
+        Self::json(&json)
+    }
+    ///
+    /// Returns the query parsed from the json `{"sql": {...}}`
+    /// - The fields of the request (`authToken`, `id`, `keepAlive`, `debug`) are ignored,
+    ///   so the logged request can be replayed as is
+    pub fn json(json: &str) -> Result<ApiQueryKind, String> {
+        let mut value: serde_json::Value = serde_json::from_str(json)
+            .map_err(|err| format!("CliQuery.json | Invalid json: {}", err))?;
+        if let Some(fields) = value.as_object_mut() {
+            for field in Self::REQUEST_FIELDS {
+                fields.remove(field);
+            }
+        }
+        serde_json::from_value(value)
+            .map_err(|err| format!("CliQuery.json | Invalid query: {}", err))
+    }
+    ///
+    /// Returns the json object parsed from the string
+    fn object(params: &str) -> Result<serde_json::Map<String, serde_json::Value>, String> {
+        match serde_json::from_str(params) {
+            Ok(serde_json::Value::Object(params)) => Ok(params),
+            Ok(params) => Err(format!("CliQuery.object | Invalid params, json object expected, but found: {}", params)),
+            Err(err) => Err(format!("CliQuery.object | Invalid params, json object expected: {}", err)),
+        }
+    }
+}
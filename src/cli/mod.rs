@@ -0,0 +1,4 @@
+pub mod cli_args;
+pub mod cli_query;
+pub mod reply_format;
+pub mod repl;
\ No newline at end of file
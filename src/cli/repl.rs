@@ -0,0 +1,129 @@
+use std::path::PathBuf;
+use rustyline::{error::ReadlineError, DefaultEditor};
+use crate::{
+    api::query::api_query_kind::ApiQueryKind,
+    cli::{cli_query::CliQuery, reply_format::ReplyFormat},
+    client::api_request::ApiRequest,
+};
+///
+/// Interactive mode, performs the queries over single keep-alive connection
+/// - The line history is kept between the sessions in the `~/.api_tools_history`
+pub struct Repl {
+    request: ApiRequest,
+    format: ReplyFormat,
+}
+//
+//
+impl Repl {
+    ///
+    /// Help on the commands of the [Repl]
+    pub const HELP: &str = "\
+sql <database> <statement>        performs sql query
+python <script> [json params]     performs python query
+executable <name> [json params]   performs executable query
+{\"sql\": {...}}                    performs the query in json
+.format table|json|csv            sets output format
+.help                             prints this help
+.exit                             exits";
+    ///
+    /// Returns [Repl] new instance
+    pub fn new(request: ApiRequest, format: ReplyFormat) -> Self {
+        Self { request, format }
+    }
+    ///
+    /// Reads & performs the commands until `.exit` or EOF
+    pub fn run(&mut self) -> Result<(), String> {
+        let mut editor = DefaultEditor::new().map_err(|err| format!("Repl.run | Editor error: {}", err))?;
+        let history = Self::history_path();
+        if let Some(path) = &history {
+            // The history file doesn't exist on the first run
+            _ = editor.load_history(path);
+        }
+        println!("Type .help for the list of the commands");
+        loop {
+            match editor.readline("api> ") {
+                Ok(line) => {
+                    if !line.trim().is_empty() {
+                        _ = editor.add_history_entry(line.as_str());
+                    }
+                    match ReplCommand::parse(&line) {
+                        Ok(ReplCommand::Query(query)) => match CliQuery::perform(&mut self.request, query, true, self.format) {
+                            Ok(out) => println!("{}", out),
+                            Err(err) => eprintln!("Error: {}", err),
+                        },
+                        Ok(ReplCommand::Format(format)) => self.format = format,
+                        Ok(ReplCommand::Help) => println!("{}", Self::HELP),
+                        Ok(ReplCommand::Empty) => {}
+                        Ok(ReplCommand::Exit) => break,
+                        Err(err) => eprintln!("Error: {}", err),
+                    }
+                }
+                Err(ReadlineError::Interrupted) => continue,
+                Err(ReadlineError::Eof) => break,
+                Err(err) => return Err(format!("Repl.run | Read error: {}", err)),
+            }
+        }
+        if let Some(path) = &history {
+            if let Err(err) = editor.save_history(path) {
+                log::warn!("Repl.run | Save history error: {}", err);
+            }
+        }
+        Ok(())
+    }
+    ///
+    ///
+    fn history_path() -> Option<PathBuf> {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".api_tools_history"))
+    }
+}
+///
+/// The line entered in the [Repl]
+#[derive(Debug, PartialEq)]
+pub enum ReplCommand {
+    Query(ApiQueryKind),
+    Format(ReplyFormat),
+    Help,
+    Exit,
+    Empty,
+}
+//
+//
+impl ReplCommand {
+    ///
+    /// Returns the command parsed from the line
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(Self::Empty);
+        }
+        if line.starts_with('{') {
+            return CliQuery::json(line).map(Self::Query);
+        }
+        let (command, args) = match line.split_once(char::is_whitespace) {
+            Some((command, args)) => (command, args.trim()),
+            None => (line, ""),
+        };
+        let (name, rest) = match args.split_once(char::is_whitespace) {
+            Some((name, rest)) => (name, rest.trim()),
+            None => (args, ""),
+        };
+        let params = match rest.is_empty() {
+            true => "{}",
+            false => rest,
+        };
+        match command {
+            "sql" if !name.is_empty() && !rest.is_empty() => CliQuery::sql(name, None, rest).map(Self::Query),
+            "python" if !name.is_empty() => CliQuery::python(name, params).map(Self::Query),
+            "executable" if !name.is_empty() => CliQuery::executable(name, params).map(Self::Query),
+            ".format" => match args {
+                "table" => Ok(Self::Format(ReplyFormat::Table)),
+                "json" => Ok(Self::Format(ReplyFormat::Json)),
+                "csv" => Ok(Self::Format(ReplyFormat::Csv)),
+                _ => Err(format!("Unknown format '{}', expected: table, json or csv", args)),
+            },
+            ".help" => Ok(Self::Help),
+            ".exit" | ".quit" => Ok(Self::Exit),
+            _ => Err(format!("Invalid command '{}', type .help for the list of the commands", line)),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use indexmap::IndexMap;
+///
+/// Output format of the reply rows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReplyFormat {
+    /// Aligned columns
+    Table,
+    /// Pretty printed json array
+    Json,
+    /// Comma separated values with the header
+    Csv,
+}
+//
+//
+impl ReplyFormat {
+    ///
+    /// Returns the rows formatted
+    /// - Columns are in the order of the first appearance, the missing values are empty
+    pub fn format(&self, rows: &[IndexMap<String, serde_json::Value>]) -> String {
+        match self {
+            ReplyFormat::Table => Self::table(rows),
+            ReplyFormat::Json => serde_json::to_string_pretty(rows).unwrap_or_else(|err| format!("ReplyFormat.json | Error: {}", err)),
+            ReplyFormat::Csv => Self::csv(rows),
+        }
+    }
+    ///
+    /// Returns the rows as the columns aligned by the widest value, with the header & the count of the rows
+    fn table(rows: &[IndexMap<String, serde_json::Value>]) -> String {
+        let columns = Self::columns(rows);
+        let cells: Vec<Vec<String>> = rows.iter()
+            .map(|row| columns.iter().map(|column| Self::cell(row.get(*column))).collect())
+            .collect();
+        let widths: Vec<usize> = columns.iter().enumerate()
+            .map(|(i, column)| cells.iter().map(|row| row[i].chars().count()).fold(column.chars().count(), usize::max))
+            .collect();
+        let line = |values: Vec<&str>| values.iter().zip(&widths)
+            .map(|(value, width)| format!("{:<width$}", value, width = width))
+            .collect::<Vec<_>>()
+            .join(" | ")
+            .trim_end()
+            .to_owned();
+        let mut out = vec![];
+        if !columns.is_empty() {
+            out.push(line(columns.clone()));
+            out.push(widths.iter().map(|width| "-".repeat(*width)).collect::<Vec<_>>().join("-+-"));
+        }
+        for row in &cells {
+            out.push(line(row.iter().map(|cell| cell.as_str()).collect()));
+        }
+        out.push(match rows.len() {
+            1 => "(1 row)".to_owned(),
+            len => format!("({} rows)", len),
+        });
+        out.join("\n")
+    }
+    ///
+    /// Returns the rows as the comma separated values, the first line - the names of the columns
+    fn csv(rows: &[IndexMap<String, serde_json::Value>]) -> String {
+        let columns = Self::columns(rows);
+        let line = |values: Vec<String>| values.iter().map(|value| Self::csv_escape(value)).collect::<Vec<_>>().join(",");
+        let mut out = vec![line(columns.iter().map(|column| column.to_string()).collect())];
+        for row in rows {
+            out.push(line(columns.iter().map(|column| Self::cell(row.get(*column))).collect()));
+        }
+        out.join("\n")
+    }
+    ///
+    /// Returns the names of the columns in the order of the first appearance
+    fn columns(rows: &[IndexMap<String, serde_json::Value>]) -> Vec<&str> {
+        let mut columns: Vec<&str> = vec![];
+        for row in rows {
+            for column in row.keys() {
+                if !columns.contains(&column.as_str()) {
+                    columns.push(column);
+                }
+            }
+        }
+        columns
+    }
+    ///
+    /// Returns the value as the text, strings without quotes, null as empty
+    fn cell(value: Option<&serde_json::Value>) -> String {
+        match value {
+            None | Some(serde_json::Value::Null) => String::new(),
+            Some(serde_json::Value::String(value)) => value.to_owned(),
+            Some(value) => value.to_string(),
+        }
+    }
+    ///
+    /// Quotes the value containing the separator, quote or line break (RFC 4180)
+    fn csv_escape(value: &str) -> String {
+        match value.contains([',', '"', '\n', '\r']) {
+            true => format!("\"{}\"", value.replace('"', "\"\"")),
+            false => value.to_owned(),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::marker::PhantomData;
+use sal_core::error::Error;
+use serde::Serialize;
+use crate::client::api_query::{ApiQuery, ApiQueryExecutable, ApiQueryKind, ApiQueryPython};
+///
+/// Typed call of the python script or the executable
+/// - `params` of any serializable type, checked to be serialized into the json object
+/// - The rows of the script output deserialized into `R` by [ApiRequest::call](crate::client::api_request::ApiRequest::call)
+/// ```
+/// #[derive(Serialize)]
+/// struct Params { a: i64, b: i64 }
+/// #[derive(Deserialize)]
+/// struct Sum { sum: i64 }
+/// let call = ApiCall::<Sum>::python("py-sum", &Params { a: 4, b: 7 })?;
+/// let sum = request.call(&call, false)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ApiCall<R> {
+    query: ApiQuery,
+    reply: PhantomData<fn() -> R>,
+}
+//
+//
+impl<R> ApiCall<R> {
+    ///
+    /// Returns the call of the python `script`
+    pub fn python<T: Serialize>(script: impl Into<String>, params: &T) -> Result<Self, Error> {
+        ApiQueryPython::from_params(script, params)
+            .map(|query| Self::new(ApiQueryKind::Python(query)))
+            .map_err(|err| Error::new("ApiCall", "python").err(err))
+    }
+    ///
+    /// Returns the call of the executable `name`
+    pub fn executable<T: Serialize>(name: impl Into<String>, params: &T) -> Result<Self, Error> {
+        ApiQueryExecutable::from_params(name, params)
+            .map(|query| Self::new(ApiQueryKind::Executable(query)))
+            .map_err(|err| Error::new("ApiCall", "executable").err(err))
+    }
+    ///
+    /// Returns the query performed by the call
+    pub fn query(&self) -> &ApiQuery {
+        &self.query
+    }
+    ///
+    /// Returns [ApiCall] wrapping the `query`
+    fn new(query: ApiQueryKind) -> Self {
+        Self {
+            query: ApiQuery::new(query, false),
+            reply: PhantomData,
+        }
+    }
+}
@@ -1,12 +1,31 @@
-use serde::Serialize;
+use std::time::Duration;
+use serde::{Serialize, Deserialize};
+pub use crate::api::query::{
+    api_query_kind::ApiQueryKind,
+    api_query_sql::ApiQuerySql,
+    api_query_sql_params::ApiQuerySqlParams,
+    api_query_batch::{ApiQueryBatch, ApiQueryBatchStatement},
+    api_query_cursor::{ApiQueryCursor, ApiQueryCursorOpen},
+    api_query_cancel::ApiQueryCancel,
+    api_query_subscribe::ApiQuerySubscribe,
+    api_query_login::ApiQueryLogin,
+    api_query_python::ApiQueryPython,
+    api_query_executable::ApiQueryExecutable,
+};
 
 ///
 /// Client side API query structure
-#[derive(Debug, Clone, Serialize)]    // , Deserialize
+/// - Query kinds are shared with the server, see [crate::api::query]
+/// - Serialized query can be deserialized back, so the requests can be logged & replayed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ApiQuery {
     pub query: ApiQueryKind,
-    #[serde(skip_serializing)]
+    #[serde(skip_serializing, default)]
     pub keep_alive: bool,
+    #[serde(skip, default)]
+    pub idempotent: bool,
+    #[serde(skip, default)]
+    pub cache_ttl: Option<Duration>,
 }
 ///
 /// 
@@ -18,94 +37,24 @@ impl ApiQuery {
         Self {
             query,
             keep_alive,
+            idempotent: false,
+            cache_ttl: None,
         }
     }
-}
-///
-/// Contains properties specific to quety kind 
-///  - ApiQuerySql
-///  - ApiQueryPython
-///  - ApiQueryQxecutable
-#[derive(Debug, Clone, Serialize)]    // , Deserialize
-pub enum ApiQueryKind {
-    #[serde(rename(serialize = "sql"))]
-    Sql(ApiQuerySql),
-    #[serde(rename(serialize = "python"))]
-    Python(ApiQueryPython),
-    #[serde(rename(serialize = "executable"))]
-    Executable(ApiQueryExecutable),
-}
-
-///
-/// Wrap a structure of an API query
-/// {
-///     "id": "123",
-///     "sql": {
-///         "database": "database name",
-///         "sql": "Some valid sql query"
-///     },
-///     "keep-alive": true,
-/// }
-#[derive(Debug, Clone, Serialize)]    // , Deserialize
-pub struct ApiQuerySql {
-    pub database: String,
-    pub sql: String,
-}
-///
-/// 
-impl ApiQuerySql {
-    ///
-    /// Creates new instance of ApiQuery
-    pub fn new(
-        database: impl Into<String>,
-        sql: impl Into<String>,
-    ) -> Self {
-        Self {
-            database: database.into(),
-            sql: sql.into(),
-        }
-    }
-}
-///
-/// 
-#[derive(Debug, Clone, Serialize)]    // , Deserialize
-pub struct ApiQueryPython {
-    pub script: String,
-    pub params: serde_json::Value,
-}
-///
-/// 
-impl ApiQueryPython {
     ///
-    /// Creates new instance of ApiQuery
-    pub fn new(
-        script: impl Into<String>,
-        params: serde_json::Value,
-    ) -> Self {
-        Self {
-            script: script.into(),
-            params: params,
-        }
+    /// Returns [ApiQuery] marked idempotent, so it can be repeated
+    /// by the [RetryPolicy](crate::client::retry_policy::RetryPolicy) of the request
+    /// - Mark only queries safe to be performed more than once, like `select`
+    pub fn with_idempotent(mut self, idempotent: bool) -> Self {
+        self.idempotent = idempotent;
+        self
     }
-}
-
-#[derive(Debug, Clone, Serialize)]    // , Deserialize
-pub struct ApiQueryExecutable {
-    pub name: String,
-    pub params: serde_json::Value,
-}
-///
-/// 
-impl ApiQueryExecutable {
     ///
-    /// Creates new instance of ApiQuery
-    pub fn new(
-        name: impl Into<String>,
-        params: serde_json::Value,
-    ) -> Self {
-        Self {
-            name: name.into(),
-            params: params,
-        }
+    /// Returns [ApiQuery] with the specified time to live of the reply
+    /// in the [ReplyCache](crate::client::reply_cache::ReplyCache) of the request
+    /// - Zero TTL disables caching of the reply
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
     }
 }
@@ -1,17 +1,14 @@
 use sal_core::{dbg::Dbg, error::Error};
-use serde::{ser::SerializeStruct, Serialize, Serializer};
-use std::{net::ToSocketAddrs, time::Duration};
+use serde::{de::DeserializeOwned, ser::SerializeStruct, Deserialize, Serialize, Serializer};
+use std::{hash::{BuildHasher, Hasher}, net::{SocketAddr, TcpStream, ToSocketAddrs}, sync::{atomic::{AtomicUsize, Ordering}, mpsc, Arc}, thread, time::{Duration, SystemTime, UNIX_EPOCH}};
 use crate::{
+    error::{api_error::ApiError, api_error_code::ApiErrorCode},
     api::{
-        message::{
-            fields::{FieldData, FieldId, FieldKind, FieldSize, FieldSyn},
-            message::MessageField, message_kind::MessageKind, msg_kind,
-            parse_data::ParseData, parse_id::ParseId, parse_kind::ParseKind,
-            parse_size::ParseSize, parse_syn::ParseSyn
-        },
-        socket::tcp_socket::{TcpMessage, TcpSocket},
+        message::{api_framing::ApiFraming, msg_kind},
+        reply::{api_notification::ApiNotification, api_reply::ApiReply},
+        socket::tcp_socket::TcpSocket,
     },
-    client::api_query::ApiQuery,
+    client::{api_call::ApiCall, api_query::{ApiQuery, ApiQueryCursorOpen, ApiQueryKind, ApiQueryLogin, ApiQuerySql, ApiQuerySubscribe}, api_request_builder::ApiRequestBuilder, api_stream::ApiStream, api_subscription::ApiSubscription, cancel_handle::CancelHandle, reply_cache::ReplyCache, retry_policy::{RetryClass, RetryPolicy}},
 };
 ///
 /// - Holding single input queue
@@ -33,119 +30,459 @@ use crate::{
 #[derive(Debug)]
 pub struct ApiRequest {
     dbg: Dbg,
+    address: SocketAddr,
     query_id: Id,
     auth_token: String,
+    login: Option<ApiQueryLogin>,
+    expires: Option<i64>,
     query: ApiQuery,
     keep_alive: bool,
     debug: bool,
     timeout: Duration,
+    retry: RetryPolicy,
+    cache: Option<ReplyCache>,
+    framing: ApiFraming,
     socket: TcpSocket,
 }
 //
 //
 impl ApiRequest {
+    ///
+    /// Max number of the stale replies drained while reading the reply to the current request
+    const MAX_STALE_REPLIES: usize = 16;
+    ///
+    /// The token is refreshed this number of seconds before it expires
+    const TOKEN_REFRESH_MARGIN: i64 = 5;
     ///
     /// Creates new instance of [ApiRequest]
     /// - [parent] - the ID if the parent entity
     pub fn new(parent: impl Into<String>, address: impl ToSocketAddrs + std::fmt::Debug, auth_token: impl Into<String>, query: ApiQuery, keep_alive: bool, debug: bool) -> Self {
-        let dbgid = Dbg::new(parent, "ApiRequest");
-        let address = match address.to_socket_addrs() {
-            Ok(mut addr_iter) => match addr_iter.next() {
-                Some(addr) => addr,
-                None => panic!("TcpClientConnect({}).connect | Empty address: {:?}", dbgid, address),
-            },
-            Err(err) => panic!("TcpClientConnect({}).connect | Address error: {:#?}", dbgid, err),
-        };
-        let message = TcpMessage::new(
-            &dbgid,
-            vec![
-                MessageField::Syn(FieldSyn::default()),
-                MessageField::Id(FieldId(4)),
-                MessageField::Kind(FieldKind(MessageKind::Bytes)),
-                MessageField::Size(FieldSize(4)),
-                MessageField::Data(FieldData(vec![]))
-            ],
-            ParseData::new(
-                &dbgid,
-                ParseSize::new(
-                    &dbgid,
-                    FieldSize(4),
-                    ParseKind::new(
-                        &dbgid,
-                        FieldKind(MessageKind::Bytes),
-                        ParseId::new(
-                            &dbgid,
-                            FieldId(4),
-                            ParseSyn::new(
-                                &dbgid,
-                                FieldSyn::default(),
-                            ),
-                        ),
-                    ),
-                ),
-            ),
-        );
+        let builder = Self::builder(parent)
+            .address(address)
+            .auth_token(auth_token)
+            .query(query)
+            .keep_alive(keep_alive)
+            .debug(debug);
+        match builder.build() {
+            Ok(request) => request,
+            Err(err) => panic!("{}", err),
+        }
+    }
+    ///
+    /// Returns [ApiRequestBuilder] to configure the [ApiRequest] step by step
+    /// - [parent] - the ID if the parent entity
+    pub fn builder(parent: impl Into<String>) -> ApiRequestBuilder {
+        ApiRequestBuilder::new(parent)
+    }
+    ///
+    /// Creates new instance of [ApiRequest] configured by the [ApiRequestBuilder]
+    /// - `address` & `socket` - resolved & validated by the [ApiRequestBuilder::build]
+    pub(crate) fn from_builder(builder: ApiRequestBuilder, address: SocketAddr, socket: TcpSocket) -> Self {
+        let query = builder.query.unwrap_or_else(|| ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("", "")), builder.keep_alive));
         Self {
-            socket: TcpSocket::new(&dbgid, address, message, None),
-            dbg: dbgid,
+            dbg: builder.dbg,
+            address,
             query_id: Id::new(),
-            auth_token: auth_token.into(),
+            auth_token: builder.auth_token,
+            login: builder.login,
+            expires: None,
             query,
-            keep_alive,
-            debug,
-            timeout: Duration::from_secs(10),
+            keep_alive: builder.keep_alive,
+            debug: builder.debug,
+            timeout: builder.timeout,
+            retry: builder.retry,
+            cache: builder.cache,
+            framing: builder.framing,
+            socket,
         }
     }
     ///
     /// Returns [ApiRequest] with specified socket read/write timeout (default 10 sec)
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
         self.timeout = timeout;
+        self.socket = self.socket.with_timeout(timeout);
+        self
+    }
+    ///
+    /// Returns [ApiRequest] with specified [RetryPolicy] (single attempt by default)
+    /// - Applied only to the queries marked idempotent, see [ApiQuery::with_idempotent]
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
         self
     }
     ///
+    /// Returns [ApiRequest] with the [ReplyCache] in front of the `fetch`
+    /// - The cached replies are returned without touching the socket
+    pub fn with_cache(mut self, cache: ReplyCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+    ///
+    /// Returns the [ReplyCache] if specified, to invalidate the entries
+    pub fn cache(&self) -> Option<&ReplyCache> {
+        self.cache.as_ref()
+    }
+    ///
+    /// Returns [ApiRequest] refreshing the `authToken` transparently
+    /// - The token is requested by the `login` query with the service `user` / `pass`
+    ///   before the first request if `auth_token` is empty, before the known expiry time
+    ///   and when the server replies the token is expired
+    pub fn with_login(mut self, user: impl Into<String>, pass: impl Into<String>) -> Self {
+        self.login = Some(ApiQueryLogin::new(user, pass));
+        self
+    }
+    ///
+    /// Returns current `authToken`
+    pub fn auth_token(&self) -> &str {
+        &self.auth_token
+    }
+    ///
+    /// Requests the new `authToken` by the `login` query
+    /// - The service `user` / `pass` must be specified by [ApiRequest::with_login]
+    pub fn login(&mut self) -> Result<(), Error> {
+        let error = Error::new(&self.dbg, "login");
+        let login = match &self.login {
+            Some(login) => ApiQuery::new(ApiQueryKind::Login(login.clone()), self.keep_alive).with_idempotent(true),
+            None => return Err(error.err("Service user / pass are not specified")),
+        };
+        let query = self.query.clone();
+        let keep_alive = self.keep_alive;
+        let reply = self.send_with(&login, keep_alive);
+        self.query = query;
+        let reply = match reply.and_then(|bytes| ApiReply::try_from(bytes).map_err(|err| error.pass_with("Reply parse error", err))) {
+            Ok(reply) => reply,
+            Err(err) => return Err(error.pass(err)),
+        };
+        if reply.has_error() {
+            let err = error.err(format!("Reply error: {}", reply.error));
+            log::warn!("{}", err);
+            return Err(err);
+        }
+        match reply.data_as::<LoginReply>().map(|mut rows| rows.pop()) {
+            Ok(Some(token)) => {
+                log::debug!("{}.login | Token refreshed, expires: {}", self.dbg, token.expires);
+                self.auth_token = token.token;
+                self.expires = Some(token.expires);
+                Ok(())
+            }
+            Ok(None) => Err(error.err("Token not found in the reply")),
+            Err(err) => Err(error.pass_with("Token parse error", err)),
+        }
+    }
+    ///
+    /// Returns true if the token must be requested before the next request
+    fn token_expired(&self) -> bool {
+        match (&self.login, self.expires) {
+            (None, _) => false,
+            (Some(_), Some(expires)) => chrono::Utc::now().timestamp() + Self::TOKEN_REFRESH_MARGIN >= expires,
+            (Some(_), None) => self.auth_token.is_empty(),
+        }
+    }
+    ///
+    /// Returns [CancelHandle], which can be moved to another thread
+    /// to cancel the request currently running on the server
+    /// - Holds the current `authToken`, so must be requested again after the token refreshed
+    /// - The cancel query is sent with the framing, timeout & retry policy of the request
+    pub fn cancel_handle(&self) -> CancelHandle {
+        CancelHandle::new(&self.dbg, self.address, &self.auth_token, self.query_id.clone(), self.debug)
+            .with_timeout(self.timeout)
+            .with_framing(self.framing.clone())
+            .with_retry(self.retry.clone())
+    }
+    ///
+    /// Subscribes to the postgres notifications of the `query` channels
+    /// - The subscription holds the own keep-alive connection, this request stays free for the queries
+    pub fn subscribe(&mut self, query: ApiQuerySubscribe) -> Result<ApiSubscription, Error> {
+        let error = Error::new(&self.dbg, "subscribe");
+        if self.token_expired() {
+            self.login().map_err(|err| error.pass(err))?;
+        }
+        let builder = ApiRequest::builder(&self.dbg)
+            .address(self.address)
+            .auth_token(&self.auth_token)
+            .keep_alive(true)
+            .debug(self.debug)
+            .timeout(self.timeout)
+            .framing(self.framing.clone());
+        ApiSubscription::start(&self.dbg, builder, query).map_err(|err| error.pass(err))
+    }
+    ///
+    /// Returns the stream of the connection, connecting if not connected yet
+    pub(crate) fn stream(&mut self) -> Result<Arc<TcpStream>, Error> {
+        self.socket.connect()
+    }
+    ///
+    /// Reads the notifications, forwarding them to the `send`
+    /// - Returns when the connection lost or the receiver dropped
+    pub(crate) fn listen(&mut self, send: &mpsc::Sender<ApiNotification>) {
+        loop {
+            match self.socket.read() {
+                Ok((_id, msg)) => match ApiNotification::from_bytes(&msg.to_be_bytes()) {
+                    Some(notification) => if send.send(notification).is_err() {
+                        self.desync();
+                        return;
+                    }
+                    None => log::warn!("{}.listen | Unexpected message skipped: {:?}", self.dbg, msg),
+                }
+                Err(err) => {
+                    log::debug!("{}.listen | Connection closed: {}", self.dbg, err);
+                    return;
+                }
+            }
+        }
+    }
+    ///
     /// Performs an API request with the parameters specified in the constructor
     pub fn fetch(&mut self, keep_alive: bool) -> Result<Vec<u8>, Error> {
         self.fetch_with(&self.query.clone(), keep_alive)
             .map_err(|err| Error::new(&self.dbg, "fetch").pass(err))
     }
     ///
+    /// Performs an API request with the parameters specified in the constructor
+    /// - Returns parsed [ApiReply]
+    /// - Returns Err if [ApiReply] contains an error
+    pub fn fetch_reply(&mut self, keep_alive: bool) -> Result<ApiReply, Error> {
+        self.fetch_reply_with(&self.query.clone(), keep_alive)
+            .map_err(|err| Error::new(&self.dbg, "fetch_reply").pass(err))
+    }
+    ///
     /// Performs an API request with passed query and parameters specified in the constructor
+    /// - Returns parsed [ApiReply]
+    /// - Returns Err if [ApiReply] contains an error
+    pub fn fetch_reply_with(&mut self, query: &ApiQuery, keep_alive: bool) -> Result<ApiReply, Error> {
+        let error = Error::new(&self.dbg, "fetch_reply_with");
+        match self.fetch_with(query, keep_alive) {
+            Ok(bytes) => match ApiReply::try_from(bytes) {
+                Ok(reply) => match reply.has_error() {
+                    false => Ok(reply),
+                    true => {
+                        let err = error.err(format!("Reply error: {}", reply.error));
+                        log::warn!("{}", err);
+                        Err(err)
+                    }
+                }
+                Err(err) => {
+                    let err = error.pass_with("Reply parse error", err);
+                    log::warn!("{}", err);
+                    Err(err)
+                }
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+    ///
+    /// Performs an API request with the parameters specified in the constructor
+    /// - Returns rows of the [ApiReply] deserialized into `T`
+    /// - Returns Err if [ApiReply] contains an error or some row can't be deserialized
+    pub fn fetch_as<T: DeserializeOwned>(&mut self, keep_alive: bool) -> Result<Vec<T>, Error> {
+        let error = Error::new(&self.dbg, "fetch_as");
+        match self.fetch_reply(keep_alive) {
+            Ok(reply) => reply.data_as().map_err(|err| {
+                let err = error.pass_with("Deserialize error", err);
+                log::warn!("{}", err);
+                err
+            }),
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+    ///
+    /// Performs the typed call of the python script or the executable
+    /// - Returns rows of the [ApiReply] deserialized into `R`
+    /// - Returns Err if [ApiReply] contains an error or some row can't be deserialized
+    pub fn call<R: DeserializeOwned>(&mut self, call: &ApiCall<R>, keep_alive: bool) -> Result<Vec<R>, Error> {
+        let error = Error::new(&self.dbg, "call");
+        match self.fetch_reply_with(call.query(), keep_alive) {
+            Ok(reply) => reply.data_as().map_err(|err| {
+                let err = error.pass_with("Deserialize error", err);
+                log::warn!("{}", err);
+                err
+            }),
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+    ///
+    /// Performs an API request streaming the rows of the `query` lazily
+    /// - Rows are fetched by the server side cursor page by page over the keep-alive connection
+    /// - `page_size` - number of rows in the single page
+    pub fn fetch_stream(&mut self, query: ApiQuerySql, page_size: usize) -> ApiStream<'_> {
+        ApiStream::new(self, ApiQueryCursorOpen::new(query, page_size))
+    }
+    ///
+    /// Performs the requests of the `send`, keeping the query & the `keepAlive` of the request
+    /// - The cursor pages of the [ApiStream] are requested aside, so the next `fetch` sends the configured query
+    pub(crate) fn aside<T>(&mut self, send: impl FnOnce(&mut Self) -> T) -> T {
+        let query = self.query.clone();
+        let keep_alive = self.keep_alive;
+        let result = send(self);
+        self.query = query;
+        self.keep_alive = keep_alive;
+        result
+    }
+    ///
+    /// Performs an API request with passed query and parameters specified in the constructor
+    /// - If [ApiRequest::with_login] specified, the expired token is refreshed
+    ///   and the request is repeated once
+    /// - If [ApiRequest::with_cache] specified, the cached reply returned if not expired,
+    ///   the reply without error is stored into the cache, if the query opted in, see [ReplyCache]
     pub fn fetch_with(&mut self, query: &ApiQuery, keep_alive: bool) -> Result<Vec<u8>, Error>{
         let error = Error::new(&self.dbg, "fetch_with");
+        if let Some(bytes) = self.cache.as_ref().and_then(|cache| cache.get(self.address, &self.auth_token, query, self.debug)) {
+            log::debug!("{}.fetch_with | Reply from the cache", self.dbg);
+            return Ok(bytes);
+        }
+        if self.token_expired() {
+            self.login().map_err(|err| error.pass(err))?;
+        }
+        let bytes = match self.send_with(query, keep_alive) {
+            Ok(bytes) if self.login.is_some() && Self::error_code(&bytes) == Some(ApiErrorCode::TokenExpired) => {
+                log::debug!("{}.fetch_with | Token expired, refreshing...", self.dbg);
+                self.login().map_err(|err| error.pass(err))?;
+                self.send_with(query, keep_alive).map_err(|err| error.pass(err))?
+            }
+            Ok(bytes) => bytes,
+            Err(err) => return Err(error.pass(err)),
+        };
+        if let Some(cache) = &self.cache {
+            if Self::is_success(&bytes) {
+                cache.insert(self.address, &self.auth_token, query, self.debug, bytes.clone());
+            }
+        }
+        Ok(bytes)
+    }
+    ///
+    /// Returns true if the reply contains no error
+    fn is_success(bytes: &[u8]) -> bool {
+        serde_json::from_slice::<ReplyStatus>(bytes)
+            .map(|reply| reply.error.is_none_or(|error| error.is_empty()))
+            .unwrap_or(false)
+    }
+    ///
+    /// Returns the code of the error contained in the reply
+    fn error_code(bytes: &[u8]) -> Option<ApiErrorCode> {
+        serde_json::from_slice::<ReplyStatus>(bytes).ok()
+            .and_then(|reply| reply.error)
+            .and_then(|error| error.code())
+    }
+    ///
+    /// Sends the query and reads the reply
+    /// - Idempotent query repeated according to the [RetryPolicy] if the transport fails
+    /// - Before the next attempt the connection is closed and the new query id is used,
+    ///   so the late reply to the failed attempt can't be taken for the reply to the next one
+    fn send_with(&mut self, query: &ApiQuery, keep_alive: bool) -> Result<Vec<u8>, Error>{
+        let error = Error::new(&self.dbg, "send_with");
+        let mut attempt = 1;
+        loop {
+            match self.send_once(query, keep_alive) {
+                Ok(bytes) => return Ok(bytes),
+                Err((Some(class), err)) if query.idempotent && self.retry.retryable(class, attempt) => {
+                    let delay = self.retry.delay(attempt);
+                    log::warn!("{}.send_with | Attempt {} of {} failed ({:?}), retry in {:?}: {}", self.dbg, attempt, self.retry.max_attempts, class, delay, err);
+                    self.desync();
+                    thread::sleep(delay);
+                    attempt += 1;
+                }
+                Err((_, err)) => return Err(error.pass(err)),
+            }
+        }
+    }
+    ///
+    /// Sends the query and reads the reply, single attempt
+    /// - Returns Err with the [RetryClass] if the transport failed
+    fn send_once(&mut self, query: &ApiQuery, keep_alive: bool) -> Result<Vec<u8>, (Option<RetryClass>, Error)>{
+        let error = Error::new(&self.dbg, "send_once");
         self.query_id.add();
         self.query = query.clone();
         self.keep_alive = keep_alive;
         match serde_json::to_vec(&self) {
             Ok(query) => {
                 log::trace!("{}.fetch | query: {:#?}", self.dbg, query);
+                if let Err(err) = self.socket.connect() {
+                    let err = error.pass_with("Connection error", err);
+                    log::warn!("{}", err);
+                    return Err((Some(RetryClass::Connect), err))
+                }
                 match self.socket.send(&query, None) {
-                    Ok(_id) => {
-                        match self.socket.read() {
-                            Ok((_id, msg)) => match msg {
-                                msg_kind::MsgKind::Bytes(bytes) =>  Ok(bytes),
-                                _ => {
-                                    let err = error.err(format!("Wrong Message kind error, expected Bytes, but found: {:?}", msg));
-                                    log::warn!("{}", err);
-                                    Err(err)
-                                }
-                            }
-                            Err(err) => Err(error.pass(err)),
-                        }
-                    }
+                    Ok(_id) => self.read_reply().map_err(|(class, err)| (class, error.pass(err))),
                     Err(err) => {
                         let err = error.pass_with("Send error", err);
                         log::warn!("{}", err);
-                        Err(err)
+                        Err((Some(RetryClass::Write), err))
                     }
                 }
             }
             Err(err) => {
                 let err = error.pass_with("Serialize  error", err.to_string());
                 log::warn!("{}", err);
-                Err(err)
+                Err((None, err))
             }
         }
     }
+    ///
+    /// Reads the reply to the query just sent
+    /// - Stale replies (to the previous queries) are drained, up to [ApiRequest::MAX_STALE_REPLIES]
+    /// - If `id` or `authToken` of the reply doesn't match to the request,
+    ///   connection is closed to be reopened on the next request and Err returned
+    /// - Returns Err with [RetryClass::ReadTimeout] if the reply isn't received
+    fn read_reply(&mut self) -> Result<Vec<u8>, (Option<RetryClass>, Error)> {
+        let error = Error::new(&self.dbg, "read_reply");
+        let id = self.query_id.get();
+        let mut stale = 0;
+        loop {
+            let bytes = match self.socket.read() {
+                Ok((_id, msg)) => match msg {
+                    msg_kind::MsgKind::Bytes(bytes) => bytes,
+                    msg_kind::MsgKind::String(string) => string.into_bytes(),
+                    _ => {
+                        let err = error.err(format!("Wrong Message kind error, expected Bytes or String, but found: {:?}", msg));
+                        log::warn!("{}", err);
+                        return Err((None, err))
+                    }
+                }
+                Err(err) => return Err((Some(RetryClass::ReadTimeout), error.pass(err))),
+            };
+            let reply: ReplyHeader = match serde_json::from_slice(&bytes) {
+                Ok(reply) => reply,
+                Err(_) if ApiNotification::from_bytes(&bytes).is_some() => {
+                    log::debug!("{}.read_reply | Notification skipped, use `subscribe` to receive notifications", self.dbg);
+                    continue;
+                }
+                Err(err) => {
+                    let err = error.pass_with("Reply parse error", err.to_string());
+                    log::warn!("{}", err);
+                    self.desync();
+                    return Err((None, err))
+                }
+            };
+            match self.query_id.parse(&reply.id) {
+                Some(reply_id) if reply_id == id => {
+                    if reply.auth_token != self.auth_token {
+                        let err = error.err(format!("Reply authToken mismatch in reply id '{}'", reply.id));
+                        log::warn!("{}", err);
+                        self.desync();
+                        return Err((None, err))
+                    }
+                    return Ok(bytes)
+                }
+                Some(reply_id) if reply_id < id && stale < Self::MAX_STALE_REPLIES => {
+                    stale += 1;
+                    log::warn!("{}.read_reply | Stale reply id '{}' drained, expected id '{}'", self.dbg, reply_id, id);
+                }
+                _ => {
+                    let err = error.err(format!("Reply id mismatch, expected '{}', but found '{}'", id, reply.id));
+                    log::warn!("{}", err);
+                    self.desync();
+                    return Err((None, err))
+                }
+            }
+        }
+    }
+    ///
+    /// Closes the desynchronised connection, the new one will be opened on the next request
+    fn desync(&mut self) {
+        if let Err(err) = self.socket.close() {
+            log::warn!("{}.desync | Close tcp stream error: {:?}", self.dbg, err);
+        }
+    }
 }
 //
 //
@@ -162,6 +499,21 @@ impl Serialize for ApiRequest {
             super::api_query::ApiQueryKind::Sql(query) => {
                 state.serialize_field("sql", query)?;
             },
+            super::api_query::ApiQueryKind::Batch(query) => {
+                state.serialize_field("batch", query)?;
+            },
+            super::api_query::ApiQueryKind::Cursor(query) => {
+                state.serialize_field("cursor", query)?;
+            },
+            super::api_query::ApiQueryKind::Cancel(query) => {
+                state.serialize_field("cancel", query)?;
+            },
+            super::api_query::ApiQueryKind::Subscribe(query) => {
+                state.serialize_field("subscribe", query)?;
+            },
+            super::api_query::ApiQueryKind::Login(query) => {
+                state.serialize_field("login", query)?;
+            },
             super::api_query::ApiQueryKind::Python(query) => {
                 state.serialize_field("python", query)?;
             },
@@ -173,21 +525,70 @@ impl Serialize for ApiRequest {
     }
 }
 ///
-/// 
-#[derive(Debug)]
-struct Id {
-    value: usize,
+/// Fields of the [ApiReply] used to verify it matches to the request
+#[derive(Debug, Deserialize)]
+struct ReplyHeader {
+    id: String,
+    #[serde(rename = "authToken")]
+    auth_token: String,
+}
+///
+/// The error of the [ApiReply], used to detect expired token
+#[derive(Debug, Deserialize)]
+struct ReplyStatus {
+    #[serde(default)]
+    error: Option<ApiError>,
+}
+///
+/// The token issued by the `login` query
+#[derive(Debug, Deserialize)]
+struct LoginReply {
+    token: String,
+    expires: i64,
+}
+///
+/// Id of the current query
+/// - Formatted as `<session>-<counter>`, where `session` is random for each [ApiRequest],
+///   so the ids of the different clients (even sharing the same `authToken`) never collide
+/// - The counter is shared with the [CancelHandle] (cloned [Id] refers the same counter)
+#[derive(Debug, Clone)]
+pub(crate) struct Id {
+    session: String,
+    value: Arc<AtomicUsize>,
 }
 impl Id {
-    pub fn new() -> Self { Self { value: 0 } }
+    pub fn new() -> Self {
+        let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+        hasher.write_u128(SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_nanos()).unwrap_or_default());
+        hasher.write_u32(std::process::id());
+        Self { session: format!("{:016x}", hasher.finish()), value: Arc::new(AtomicUsize::new(0)) }
+    }
     pub fn add(&mut self) {
-        self.value = (self.value % usize::MAX) + 1;
+        let value = (self.get() % usize::MAX) + 1;
+        self.value.store(value, Ordering::SeqCst);
+    }
+    ///
+    /// Returns the counter of the current query, 0 if no query sent yet
+    pub fn get(&self) -> usize {
+        self.value.load(Ordering::SeqCst)
+    }
+    ///
+    /// Returns the counter of the `id`, if it is issued by this [Id]
+    pub fn parse(&self, id: &str) -> Option<usize> {
+        id.split_once('-')
+            .filter(|(session, _)| *session == self.session)
+            .and_then(|(_, value)| value.parse().ok())
+    }
+}
+impl std::fmt::Display for Id {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.session, self.get())
     }
 }
 impl Serialize for Id {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where
     S: Serializer, {
-        serializer.serialize_str(&self.value.to_string())
+        serializer.serialize_str(&self.to_string())
     }
 }
 // impl Into<usize> for Id {
@@ -0,0 +1,156 @@
+use sal_core::{dbg::Dbg, error::Error};
+use std::{net::{SocketAddr, ToSocketAddrs}, time::Duration};
+use crate::{
+    api::{message::api_framing::ApiFraming, socket::tcp_socket::TcpSocket},
+    client::{
+        api_query::{ApiQuery, ApiQueryLogin},
+        api_request::ApiRequest,
+        api_transport::ApiTransport,
+        reply_cache::ReplyCache,
+        retry_policy::RetryPolicy,
+    },
+};
+///
+/// Builds the [ApiRequest] step by step
+/// - Configuration is validated by [ApiRequestBuilder::build]
+/// ```
+/// let request = ApiRequest::builder(parent)
+///     .address("127.0.0.1:8080")
+///     .auth_token(token)
+///     .query(ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new(database, sql)), false))
+///     .timeout(Duration::from_secs(3))
+///     .build()?;
+/// ```
+#[derive(Debug)]
+pub struct ApiRequestBuilder {
+    pub(crate) dbg: Dbg,
+    pub(crate) address: Option<Result<SocketAddr, String>>,
+    pub(crate) auth_token: String,
+    pub(crate) login: Option<ApiQueryLogin>,
+    pub(crate) query: Option<ApiQuery>,
+    pub(crate) keep_alive: bool,
+    pub(crate) debug: bool,
+    pub(crate) timeout: Duration,
+    pub(crate) framing: ApiFraming,
+    pub(crate) transport: ApiTransport,
+    pub(crate) retry: RetryPolicy,
+    pub(crate) cache: Option<ReplyCache>,
+}
+//
+//
+impl ApiRequestBuilder {
+    ///
+    /// Default socket connect / read / write timeout
+    pub const TIMEOUT: Duration = Duration::from_secs(10);
+    ///
+    /// Returns [ApiRequestBuilder] new instance
+    /// - [parent] - the ID if the parent entity
+    pub fn new(parent: impl Into<String>) -> Self {
+        Self {
+            dbg: Dbg::new(parent, "ApiRequest"),
+            address: None,
+            auth_token: String::new(),
+            login: None,
+            query: None,
+            keep_alive: false,
+            debug: false,
+            timeout: Self::TIMEOUT,
+            framing: ApiFraming::default(),
+            transport: ApiTransport::default(),
+            retry: RetryPolicy::default(),
+            cache: None,
+        }
+    }
+    ///
+    /// Address of the API server, required
+    /// - The first resolved address is used
+    pub fn address(mut self, address: impl ToSocketAddrs + std::fmt::Debug) -> Self {
+        self.address = Some(match address.to_socket_addrs() {
+            Ok(mut addr_iter) => match addr_iter.next() {
+                Some(addr) => Ok(addr),
+                None => Err(format!("Empty address: {:?}", address)),
+            },
+            Err(err) => Err(format!("Address {:?} error: {}", address, err)),
+        });
+        self
+    }
+    ///
+    /// The `authToken` sent with each request
+    pub fn auth_token(mut self, auth_token: impl Into<String>) -> Self {
+        self.auth_token = auth_token.into();
+        self
+    }
+    ///
+    /// Service `user` / `pass` used to refresh the `authToken`, see [ApiRequest::with_login]
+    pub fn login(mut self, user: impl Into<String>, pass: impl Into<String>) -> Self {
+        self.login = Some(ApiQueryLogin::new(user, pass));
+        self
+    }
+    ///
+    /// The query performed by [ApiRequest::fetch]
+    pub fn query(mut self, query: ApiQuery) -> Self {
+        self.query = Some(query);
+        self
+    }
+    ///
+    /// Keep the connection open after the request, false by default
+    pub fn keep_alive(mut self, keep_alive: bool) -> Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+    ///
+    /// Request the server to include the debug details into the reply, false by default
+    pub fn debug(mut self, debug: bool) -> Self {
+        self.debug = debug;
+        self
+    }
+    ///
+    /// Socket connect / read / write timeout, [ApiRequestBuilder::TIMEOUT] by default
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+    ///
+    /// Framing schema of the messages, [ApiFraming::default] by default
+    pub fn framing(mut self, framing: ApiFraming) -> Self {
+        self.framing = framing;
+        self
+    }
+    ///
+    /// Transport of the messages, [ApiTransport::Tcp] by default
+    pub fn transport(mut self, transport: ApiTransport) -> Self {
+        self.transport = transport;
+        self
+    }
+    ///
+    /// Retry policy of the idempotent queries, single attempt by default, see [ApiRequest::with_retry]
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+    ///
+    /// Reply cache in front of the `fetch`, not used by default, see [ApiRequest::with_cache]
+    pub fn cache(mut self, cache: ReplyCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+    ///
+    /// Returns configured [ApiRequest]
+    /// - Returns Err if the address is missing or can't be resolved,
+    ///   the timeout is zero or the framing is not supported
+    pub fn build(self) -> Result<ApiRequest, Error> {
+        let error = Error::new(&self.dbg, "build");
+        let address = match self.address {
+            Some(Ok(address)) => address,
+            Some(Err(err)) => return Err(error.err(err)),
+            None => return Err(error.err("Address is not specified")),
+        };
+        if self.timeout.is_zero() {
+            return Err(error.err("Timeout must be greater than zero"));
+        }
+        self.framing.validate(&self.dbg).map_err(|err| error.pass(err))?;
+        let socket = TcpSocket::new(&self.dbg, address, self.framing.message(&self.dbg), self.transport.stream())
+            .with_timeout(self.timeout);
+        Ok(ApiRequest::from_builder(self, address, socket))
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::VecDeque;
+use indexmap::IndexMap;
+use sal_core::error::Error;
+use crate::client::{api_query::{ApiQuery, ApiQueryCursor, ApiQueryCursorOpen, ApiQueryKind}, api_request::ApiRequest};
+///
+/// Rows of the large `select` streamed lazily by the server side cursor
+/// - The next page requested over the keep-alive connection only when the rows of the current page are over
+/// - Stops on the first error
+/// - Cursor closed on the server side if the stream dropped before the last page
+/// - The query & the `keepAlive` of the request are kept, the next `fetch` sends the configured query
+/// ```
+/// for row in request.fetch_stream(ApiQuerySql::new(database, "select * from event;"), 1000) {
+///     let row = row?;
+/// }
+/// ```
+pub struct ApiStream<'a> {
+    request: &'a mut ApiRequest,
+    open: Option<ApiQueryCursorOpen>,
+    cursor: Option<String>,
+    rows: VecDeque<IndexMap<String, serde_json::Value>>,
+    done: bool,
+}
+//
+//
+impl<'a> ApiStream<'a> {
+    ///
+    /// Returns [ApiStream] new instance, the cursor is opened on the first `next`
+    pub fn new(request: &'a mut ApiRequest, open: ApiQueryCursorOpen) -> Self {
+        Self {
+            request,
+            open: Some(open),
+            cursor: None,
+            rows: VecDeque::new(),
+            done: false,
+        }
+    }
+    ///
+    /// Fetches the next page of the rows
+    fn fetch_page(&mut self) -> Result<(), Error> {
+        let query = match (self.open.take(), &self.cursor) {
+            (Some(open), _) => ApiQueryCursor::Open(open),
+            (None, Some(id)) => ApiQueryCursor::Next(id.clone()),
+            (None, None) => {
+                self.done = true;
+                return Ok(());
+            }
+        };
+        let query = ApiQuery::new(ApiQueryKind::Cursor(query), false);
+        match self.request.aside(|request| request.fetch_reply_with(&query, true)) {
+            Ok(reply) => {
+                match reply.cursor {
+                    Some(cursor) => {
+                        self.done = cursor.done;
+                        self.cursor = Some(cursor.id);
+                    }
+                    None => self.done = true,
+                }
+                self.rows.extend(reply.data);
+                Ok(())
+            }
+            Err(err) => {
+                self.done = true;
+                Err(err)
+            }
+        }
+    }
+}
+//
+//
+impl Iterator for ApiStream<'_> {
+    type Item = Result<IndexMap<String, serde_json::Value>, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(row) = self.rows.pop_front() {
+                return Some(Ok(row));
+            }
+            if self.done {
+                return None;
+            }
+            if let Err(err) = self.fetch_page() {
+                return Some(Err(err));
+            }
+        }
+    }
+}
+//
+//
+impl Drop for ApiStream<'_> {
+    fn drop(&mut self) {
+        if let (false, Some(id)) = (self.done, self.cursor.take()) {
+            let query = ApiQuery::new(ApiQueryKind::Cursor(ApiQueryCursor::Close(id)), false);
+            if let Err(err) = self.request.aside(|request| request.fetch_with(&query, true)) {
+                log::warn!("ApiStream.drop | Close cursor error: {}", err);
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use std::{net::{Shutdown, TcpStream}, sync::{mpsc, Arc}, thread::{self, JoinHandle}, time::Duration};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    api::reply::api_notification::ApiNotification,
+    client::{api_query::{ApiQuery, ApiQueryKind, ApiQuerySubscribe}, api_request_builder::ApiRequestBuilder},
+};
+///
+/// Receives the postgres notifications forwarded by the server
+/// - Holding the own keep-alive connection subscribed by the `subscribe` query,
+///   frames are read in the dedicated thread
+/// - Notifications are received over [ApiSubscription::receiver] or [ApiSubscription::recv_timeout]
+/// - Receiver is disconnected when the connection lost
+/// ```
+/// let subscription = request.subscribe(ApiQuerySubscribe::new(database, &["event"]))?;
+/// for notification in subscription.receiver() {
+///     ...
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ApiSubscription {
+    dbg: Dbg,
+    stream: Arc<TcpStream>,
+    recv: mpsc::Receiver<ApiNotification>,
+    handle: Option<JoinHandle<()>>,
+}
+//
+//
+impl ApiSubscription {
+    ///
+    /// Subscribes the connection of the request built by the `builder` & starts reading notifications
+    /// - The request is used only by the subscription
+    pub(crate) fn start(parent: impl Into<String>, builder: ApiRequestBuilder, query: ApiQuerySubscribe) -> Result<Self, Error> {
+        let dbg = Dbg::new(parent, "ApiSubscription");
+        let error = Error::new(&dbg, "start");
+        let (started_send, started_recv) = mpsc::channel();
+        let (send, recv) = mpsc::channel();
+        let handle = {
+            let dbg = dbg.clone();
+            thread::spawn(move || {
+                let error = Error::new(&dbg, "start");
+                let subscribed = builder.build().and_then(|mut request| {
+                    request.fetch_reply_with(&ApiQuery::new(ApiQueryKind::Subscribe(query), true), true)
+                        .map_err(|err| error.pass_with("Subscribe error", err))?;
+                    let stream = request.stream().map_err(|err| error.pass(err))?;
+                    // Notifications are awaited as long as needed, the thread stopped by the shutdown of the stream
+                    stream.set_read_timeout(None).map_err(|err| error.pass_with("Set read timeout error", err.to_string()))?;
+                    Ok((request, stream))
+                });
+                match subscribed {
+                    Ok((mut request, stream)) => {
+                        if started_send.send(Ok(stream)).is_ok() {
+                            request.listen(&send);
+                        }
+                    }
+                    Err(err) => _ = started_send.send(Err(err)),
+                }
+                log::debug!("{}.start | Stopped", dbg);
+            })
+        };
+        match started_recv.recv() {
+            Ok(Ok(stream)) => Ok(Self { dbg, stream, recv, handle: Some(handle) }),
+            Ok(Err(err)) => {
+                _ = handle.join();
+                Err(error.pass(err))
+            }
+            Err(err) => {
+                _ = handle.join();
+                Err(error.pass_with("Subscription thread error", err.to_string()))
+            }
+        }
+    }
+    ///
+    /// Returns the receiver of the notifications
+    pub fn receiver(&self) -> &mpsc::Receiver<ApiNotification> {
+        &self.recv
+    }
+    ///
+    /// Returns the next notification received in the `timeout`
+    /// - None if timed out or the connection lost
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<ApiNotification> {
+        self.recv.recv_timeout(timeout).ok()
+    }
+    ///
+    /// Closes the connection, the server stops listening
+    pub fn close(&mut self) {
+        if let Err(err) = self.stream.shutdown(Shutdown::Both) {
+            log::debug!("{}.close | Shutdown error: {}", self.dbg, err);
+        }
+        if let Some(handle) = self.handle.take() {
+            if let Err(err) = handle.join() {
+                log::warn!("{}.close | Thread error: {:?}", self.dbg, err);
+            }
+        }
+    }
+}
+//
+//
+impl Drop for ApiSubscription {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
@@ -0,0 +1,23 @@
+use std::{net::TcpStream, sync::Arc};
+///
+/// Transport used by the [ApiRequest](crate::client::api_request::ApiRequest)
+/// - `Tcp` - connection opened on the first request and reopened if lost
+/// - `Stream` - already connected stream, reopened to the same address if lost
+#[derive(Debug, Clone, Default)]
+pub enum ApiTransport {
+    #[default]
+    Tcp,
+    Stream(Arc<TcpStream>),
+}
+//
+//
+impl ApiTransport {
+    ///
+    /// Returns connected stream if specified
+    pub fn stream(&self) -> Option<Arc<TcpStream>> {
+        match self {
+            ApiTransport::Tcp => None,
+            ApiTransport::Stream(stream) => Some(stream.clone()),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::{net::SocketAddr, time::Duration};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    api::message::api_framing::ApiFraming,
+    client::{api_query::{ApiQuery, ApiQueryCancel, ApiQueryKind}, api_request::{ApiRequest, Id}, retry_policy::RetryPolicy},
+};
+///
+/// Cancels the request currently running by the [ApiRequest] on the server
+/// - Can be moved to another thread, while the [ApiRequest] is blocked in the `fetch`
+/// - Cancel query sent over the separate connection,
+///   with the framing, timeout & retry policy of the [ApiRequest]
+/// ```
+/// let cancel = request.cancel_handle();
+/// thread::spawn(move || cancel.cancel());
+/// let result = request.fetch(true);   // returns the cancel error
+/// ```
+#[derive(Debug, Clone)]
+pub struct CancelHandle {
+    dbg: Dbg,
+    address: SocketAddr,
+    auth_token: String,
+    query_id: Id,
+    debug: bool,
+    timeout: Duration,
+    framing: ApiFraming,
+    retry: RetryPolicy,
+}
+//
+//
+impl CancelHandle {
+    ///
+    /// Returns [CancelHandle] new instance
+    /// - `query_id` - id of the current query of the [ApiRequest]
+    pub(crate) fn new(parent: impl Into<String>, address: SocketAddr, auth_token: impl Into<String>, query_id: Id, debug: bool) -> Self {
+        Self {
+            dbg: Dbg::new(parent, "CancelHandle"),
+            address,
+            auth_token: auth_token.into(),
+            query_id,
+            debug,
+            timeout: Duration::from_secs(10),
+            framing: ApiFraming::default(),
+            retry: RetryPolicy::default(),
+        }
+    }
+    ///
+    /// Returns [CancelHandle] with specified socket read/write timeout (default 10 sec)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+    ///
+    /// Returns [CancelHandle] with specified framing schema of the messages ([ApiFraming::default] by default)
+    pub fn with_framing(mut self, framing: ApiFraming) -> Self {
+        self.framing = framing;
+        self
+    }
+    ///
+    /// Returns [CancelHandle] with specified [RetryPolicy] of the cancel query (single attempt by default)
+    /// - The cancel query is idempotent, cancelling the finished request just returns false
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+    ///
+    /// Cancels the current request
+    /// - Returns false if no request is running on the server
+    pub fn cancel(&self) -> Result<bool, Error> {
+        let error = Error::new(&self.dbg, "cancel");
+        if self.query_id.get() == 0 {
+            return Ok(false);
+        }
+        let id = self.query_id.to_string();
+        let request = ApiRequest::builder(&self.dbg)
+            .address(self.address)
+            .auth_token(&self.auth_token)
+            .query(ApiQuery::new(ApiQueryKind::Cancel(ApiQueryCancel::new(id.clone())), false).with_idempotent(true))
+            .debug(self.debug)
+            .timeout(self.timeout)
+            .framing(self.framing.clone())
+            .retry(self.retry.clone())
+            .build();
+        let mut request = match request {
+            Ok(request) => request,
+            Err(err) => return Err(error.pass(err)),
+        };
+        match request.fetch_reply(false) {
+            Ok(reply) => {
+                let cancelled = reply.data.first()
+                    .and_then(|row| row.get("cancelled"))
+                    .and_then(|cancelled| cancelled.as_bool())
+                    .unwrap_or(false);
+                log::debug!("{}.cancel | Request '{}' cancelled: {}", self.dbg, id, cancelled);
+                Ok(cancelled)
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
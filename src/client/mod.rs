@@ -1,5 +1,21 @@
+pub mod api_call;
+
 pub mod api_query;
 
 pub mod api_request;
 
+pub mod api_request_builder;
+
+pub mod api_stream;
+
+pub mod api_subscription;
+
+pub mod api_transport;
+
+pub mod cancel_handle;
+
+pub mod reply_cache;
+
+pub mod retry_policy;
+
 // pub mod api_reply;
\ No newline at end of file
@@ -0,0 +1,174 @@
+use indexmap::IndexMap;
+use std::{net::SocketAddr, sync::{Arc, Mutex}, time::{Duration, Instant}};
+use crate::client::api_query::{ApiQuery, ApiQueryKind};
+///
+/// Cached reply and the time it expires
+#[derive(Debug)]
+struct CacheEntry {
+    bytes: Vec<u8>,
+    expires: Instant,
+}
+///
+/// Client side TTL cache of the replies
+/// - Only `sql`, `python` & `executable` queries are cached,
+///   only if opted in: marked idempotent, see [ApiQuery::with_idempotent], or with the explicit TTL, see [ApiQuery::with_cache_ttl],
+///   so the replies to the modifying queries are never returned from the cache
+/// - Keyed by the server address, the `authToken` & the normalized query: `database`, `sql` & `params` or `script` / `name` & `params`,
+///   so the differences in the whitespaces of the sql or the order of the named params
+///   don't produce the separate entries
+/// - Each entry lives for the TTL of the query, or the default one
+/// - If `capacity` exceeded the expired entries are removed first, then the least recently used one
+/// - Cloned instances share the same entries, so the single cache can be used by several requests,
+///   the replies to the different servers & users are kept separately
+/// ```
+/// let cache = ReplyCache::new(Duration::from_secs(60), 1000);
+/// let request = ApiRequest::builder(parent).address(address).cache(cache.clone()).build()?;
+/// cache.invalidate(&query);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReplyCache {
+    ttl: Duration,
+    capacity: usize,
+    entries: Arc<Mutex<IndexMap<(String, String), CacheEntry>>>,
+}
+//
+//
+impl ReplyCache {
+    ///
+    /// Returns [ReplyCache] new instance
+    /// - `ttl` - default time to live of the entries
+    /// - `capacity` - max number of the entries
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            entries: Arc::new(Mutex::new(IndexMap::new())),
+        }
+    }
+    ///
+    /// Returns the cached reply to the `query` sent to the `address` with the `auth_token`, if it isn't expired
+    pub fn get(&self, address: SocketAddr, auth_token: &str, query: &ApiQuery, debug: bool) -> Option<Vec<u8>> {
+        let key = (Self::scope(address, auth_token), Self::key(query, debug)?);
+        let mut entries = self.entries.lock().ok()?;
+        match entries.shift_remove(&key) {
+            Some(entry) if entry.expires > Instant::now() => {
+                let bytes = entry.bytes.clone();
+                entries.insert(key, entry);
+                Some(bytes)
+            }
+            _ => None,
+        }
+    }
+    ///
+    /// Stores the reply to the `query` sent to the `address` with the `auth_token`
+    /// - Not cached queries, not opted in queries & the queries with zero TTL are ignored
+    pub fn insert(&self, address: SocketAddr, auth_token: &str, query: &ApiQuery, debug: bool, bytes: Vec<u8>) {
+        if !query.idempotent && query.cache_ttl.is_none() {
+            return;
+        }
+        let ttl = query.cache_ttl.unwrap_or(self.ttl);
+        if ttl.is_zero() || self.capacity == 0 {
+            return;
+        }
+        if let (Some(key), Ok(mut entries)) = (Self::key(query, debug), self.entries.lock()) {
+            let key = (Self::scope(address, auth_token), key);
+            let now = Instant::now();
+            entries.shift_remove(&key);
+            if entries.len() >= self.capacity {
+                entries.retain(|_, entry| entry.expires > now);
+            }
+            while entries.len() >= self.capacity {
+                entries.shift_remove_index(0);
+            }
+            entries.insert(key, CacheEntry { bytes, expires: now + ttl });
+        }
+    }
+    ///
+    /// Removes the cached replies to the `query`, sent to any server with any `authToken`
+    pub fn invalidate(&self, query: &ApiQuery) {
+        if let Ok(mut entries) = self.entries.lock() {
+            let keys: Vec<String> = [false, true].into_iter().filter_map(|debug| Self::key(query, debug)).collect();
+            entries.retain(|(_, key), _| !keys.contains(key));
+        }
+    }
+    ///
+    /// Removes all the cached replies
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+    ///
+    /// Returns the number of the entries, including expired but not removed yet
+    pub fn len(&self) -> usize {
+        self.entries.lock().map(|entries| entries.len()).unwrap_or(0)
+    }
+    ///
+    /// Returns true if there is no entries
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    ///
+    /// Returns the part of the key, separating the replies of the different servers & users
+    fn scope(address: SocketAddr, auth_token: &str) -> String {
+        format!("{}\u{0}{}", address, auth_token)
+    }
+    ///
+    /// Returns the normalized key of the `query`, None if the query isn't cached
+    fn key(query: &ApiQuery, debug: bool) -> Option<String> {
+        let query = match &query.query {
+            ApiQueryKind::Sql(query) => {
+                let mut query = query.clone();
+                query.sql = Self::normalize_sql(&query.sql);
+                ApiQueryKind::Sql(query)
+            }
+            ApiQueryKind::Python(_) | ApiQueryKind::Executable(_) => query.query.clone(),
+            _ => return None,
+        };
+        serde_json::to_value(query).ok()
+            .map(|query| Self::sorted(query).to_string())
+            .map(|query| format!("{}{}", debug as u8, query))
+    }
+    ///
+    /// Returns `sql` with trimmed whitespaces, the whitespaces outside of the quoted literals collapsed into single space
+    fn normalize_sql(sql: &str) -> String {
+        let mut result = String::with_capacity(sql.len());
+        let mut quote = None;
+        let mut space = false;
+        for c in sql.trim().chars() {
+            match quote {
+                Some(q) => {
+                    if c == q {
+                        quote = None;
+                    }
+                    result.push(c);
+                }
+                None if c.is_whitespace() => space = true,
+                None => {
+                    if space {
+                        result.push(' ');
+                        space = false;
+                    }
+                    if c == '\'' || c == '"' {
+                        quote = Some(c);
+                    }
+                    result.push(c);
+                }
+            }
+        }
+        result
+    }
+    ///
+    /// Returns the `value` with the fields of the objects sorted by the name
+    fn sorted(value: serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Object(map) => {
+                let mut fields: Vec<_> = map.into_iter().collect();
+                fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+                serde_json::Value::Object(fields.into_iter().map(|(key, value)| (key, Self::sorted(value))).collect())
+            }
+            serde_json::Value::Array(values) => serde_json::Value::Array(values.into_iter().map(Self::sorted).collect()),
+            value => value,
+        }
+    }
+}
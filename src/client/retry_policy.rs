@@ -0,0 +1,84 @@
+use std::time::Duration;
+///
+/// Class of the transport failure, which can be retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryClass {
+    ///
+    /// Connection to the server can't be opened in the timeout
+    Connect,
+    ///
+    /// The query can't be written into the connection
+    Write,
+    ///
+    /// The reply isn't received: read timed out or the connection lost while waiting
+    ReadTimeout,
+}
+///
+/// Retry policy of the [ApiRequest](crate::client::api_request::ApiRequest)
+/// - Applied only to the queries marked idempotent, see [ApiQuery::with_idempotent](crate::client::api_query::ApiQuery::with_idempotent)
+/// - `max_attempts` - total number of attempts, including the first one
+/// - `backoff` - delay before the second attempt, doubled for each next one, up to `max_backoff`
+/// - `retry_on` - retryable classes of the failures
+/// ```
+/// let policy = RetryPolicy::new(3, Duration::from_millis(100))
+///     .with_retry_on(&[RetryClass::Connect, RetryClass::ReadTimeout]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: usize,
+    pub backoff: Duration,
+    pub max_backoff: Duration,
+    pub retry_on: Vec<RetryClass>,
+}
+//
+//
+impl RetryPolicy {
+    ///
+    /// Default upper limit of the delay between attempts
+    pub const MAX_BACKOFF: Duration = Duration::from_secs(30);
+    ///
+    /// Returns [RetryPolicy] new instance, retrying failures of all classes
+    pub fn new(max_attempts: usize, backoff: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            backoff,
+            max_backoff: Self::MAX_BACKOFF,
+            retry_on: vec![RetryClass::Connect, RetryClass::Write, RetryClass::ReadTimeout],
+        }
+    }
+    ///
+    /// Returns [RetryPolicy] with the single attempt
+    pub fn none() -> Self {
+        Self::new(1, Duration::ZERO)
+    }
+    ///
+    /// Returns [RetryPolicy] with the specified upper limit of the delay between attempts
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+    ///
+    /// Returns [RetryPolicy] retrying only specified classes of the failures
+    pub fn with_retry_on(mut self, retry_on: &[RetryClass]) -> Self {
+        self.retry_on = retry_on.to_vec();
+        self
+    }
+    ///
+    /// Returns true if the failure of the `class` can be retried after the `attempt` (1-based)
+    pub fn retryable(&self, class: RetryClass, attempt: usize) -> bool {
+        attempt < self.max_attempts && self.retry_on.contains(&class)
+    }
+    ///
+    /// Returns delay before the next attempt after the `attempt` (1-based)
+    pub fn delay(&self, attempt: usize) -> Duration {
+        let factor = 1u32.checked_shl(attempt.saturating_sub(1) as u32).unwrap_or(u32::MAX);
+        self.backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+//
+//
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
@@ -5,6 +5,7 @@ use std::collections::HashMap;
 // use log::{debug, warn};
 use serde::{Serialize, Deserialize, Serializer, ser::SerializeStruct};
 use serde_json::json;
+use crate::error::api_error_code::ApiErrorCode;
 
 ///
 #[derive(Debug, Clone, Deserialize, PartialEq)]
@@ -12,6 +13,8 @@ pub struct ApiError {
     message: String,
     #[serde(default)]
     details: String,
+    #[serde(default)]
+    code: Option<ApiErrorCode>,
     #[serde(default="bool::default")]
     debug: bool,
 }
@@ -22,6 +25,7 @@ impl ApiError {
         Self {
             message: message.into(),
             details: details.into(),
+            code: None,
             debug: false,
         }        
     }
@@ -31,10 +35,22 @@ impl ApiError {
         Self {
             message: String::new(),
             details: String::new(),
+            code: None,
             debug: false,
         }
     }
     ///
+    /// Returns [ApiError] with the machine readable `code`
+    pub fn with_code(mut self, code: ApiErrorCode) -> Self {
+        self.code = Some(code);
+        self
+    }
+    ///
+    /// Returns the machine readable code of the error, if specified
+    pub fn code(&self) -> Option<ApiErrorCode> {
+        self.code
+    }
+    ///
     /// 
     pub fn debug(mut self, debug: bool) -> Self {
         self.debug = debug;
@@ -63,11 +79,13 @@ impl ApiError {
     pub fn asJason(&self, debug: bool) -> serde_json::Value {
         match debug {
             false => {
-                let result = serde_json::to_value(
-                    HashMap::from([
-                        ("message", self.message.clone()),
-                    ])
-                );
+                let mut fields = HashMap::from([
+                    ("message", json!(self.message)),
+                ]);
+                if let Some(code) = self.code {
+                    fields.insert("code", json!(code));
+                }
+                let result = serde_json::to_value(fields);
                 match result {
                     Ok(value) => value,
                     Err(err) => {
@@ -80,12 +98,14 @@ impl ApiError {
                 }
             },
             true => {
-                let result = serde_json::to_value(
-                    HashMap::from([
-                        ("message", self.message.clone()),
-                        ("details", self.details.clone()),
-                    ])
-                );
+                let mut fields = HashMap::from([
+                    ("message", json!(self.message)),
+                    ("details", json!(self.details)),
+                ]);
+                if let Some(code) = self.code {
+                    fields.insert("code", json!(code));
+                }
+                let result = serde_json::to_value(fields);
                 match result {
                     Ok(value) => value,
                     Err(err) => {
@@ -103,7 +123,7 @@ impl ApiError {
     ///
     /// Returns true if no error information contains
     pub fn is_empty(&self) -> bool {
-        self.message.is_empty() & self.details.is_empty()
+        self.message.is_empty() & self.details.is_empty() & self.code.is_none()
     }
     ///
     /// 
@@ -116,7 +136,16 @@ impl ApiError {
     //     self.query()
     // }
 }
-
+//
+//
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.details.is_empty() {
+            true => write!(f, "{}", self.message),
+            false => write!(f, "{} ({})", self.message, self.details),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 enum MsgType {
@@ -128,13 +157,19 @@ impl Serialize for ApiError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where
     S: Serializer, {
         if self.debug {
-            let mut state = serializer.serialize_struct("ApiError", 2)?;
+            let mut state = serializer.serialize_struct("ApiError", 3)?;
             state.serialize_field("message", &self.message)?;
             state.serialize_field("details", &self.details)?;
+            if let Some(code) = &self.code {
+                state.serialize_field("code", code)?;
+            }
             state.end()
         } else {
-            let mut state = serializer.serialize_struct("ApiError", 1)?;
+            let mut state = serializer.serialize_struct("ApiError", 2)?;
             state.serialize_field("message", &self.message)?;
+            if let Some(code) = &self.code {
+                state.serialize_field("code", code)?;
+            }
             state.end()
         }
         // 3 is the number of fields in the struct.
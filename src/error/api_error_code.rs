@@ -0,0 +1,21 @@
+use serde::{Serialize, Deserialize};
+///
+/// Machine readable kind of the [ApiError](crate::error::api_error::ApiError),
+/// so the client can react on the error without parsing the message
+/// - `tokenExpired` - the `authToken` was valid, but it's expired, new one can be requested by `login`
+/// - `tokenInvalid` - the `authToken` is malformed or it's signature doesn't match
+/// - `loginFailed` - wrong `user` / `pass` in the `login` query
+/// - `limitExceeded` - the script / executable is killed, it exceeds the resource limit of the service
+/// - `forbidden` - the user of the `authToken` isn't allowed the requested operation on the service
+/// - `unknown` - code sent by the newer server, not known to this client
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ApiErrorCode {
+    TokenExpired,
+    TokenInvalid,
+    LoginFailed,
+    LimitExceeded,
+    Forbidden,
+    #[serde(other)]
+    Unknown,
+}
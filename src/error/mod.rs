@@ -1 +1,3 @@
 pub mod api_error;
+
+pub mod api_error_code;
\ No newline at end of file
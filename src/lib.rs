@@ -7,3 +7,9 @@ pub mod client;
 pub mod tcp;
 
 pub mod api;
+
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+
+#[cfg(feature = "cli")]
+pub mod cli;
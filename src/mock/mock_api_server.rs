@@ -0,0 +1,215 @@
+use std::{
+    collections::VecDeque, io::{ErrorKind, Write}, net::{Shutdown, SocketAddr, TcpListener, TcpStream},
+    sync::{atomic::{AtomicBool, Ordering}, Arc, Mutex}, thread::{self, JoinHandle}, time::Duration,
+};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    api::{message::api_framing::ApiFraming, reply::{api_notification::ApiNotification, api_reply::ApiReply}, socket::tcp_socket::TcpSocket},
+    error::api_error::ApiError,
+    mock::mock_expectation::{MockExpectation, MockReply},
+};
+///
+/// In-process API server for the client tests
+/// - Speaks the framing `Syn | Id | Kind | Size | Data` by the [ApiFraming] parser, the same as the `ApiServer`,
+///   [ApiFraming::default] or specified by [MockApiServer::start_with_framing]
+/// - Replies according to the scripted [MockExpectation]'s, in the order they are added
+/// - The query not matching the next expectation, or received when no expectations left,
+///   is replied with the error
+/// ```
+/// let server = MockApiServer::start("test").unwrap();
+/// server.expect(MockExpectation::json(json!([{"id": 1}])));
+/// let mut request = ApiRequest::new("test", server.address(), "token", query, true, false);
+/// let reply = request.fetch_reply(true)?;
+/// assert!(server.received()[0]["sql"]["database"] == "database");
+/// ```
+pub struct MockApiServer {
+    dbg: Dbg,
+    address: SocketAddr,
+    framing: ApiFraming,
+    expectations: Arc<Mutex<VecDeque<MockExpectation>>>,
+    received: Arc<Mutex<Vec<serde_json::Value>>>,
+    streams: Arc<Mutex<Vec<TcpStream>>>,
+    exit: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+//
+//
+impl MockApiServer {
+    ///
+    /// Returns [MockApiServer] listening on the free local port
+    pub fn start(parent: impl Into<String>) -> Result<Self, Error> {
+        Self::start_with_framing(parent, ApiFraming::default())
+    }
+    ///
+    /// Returns [MockApiServer] listening on the free local port, with the specified framing of the messages
+    pub fn start_with_framing(parent: impl Into<String>, framing: ApiFraming) -> Result<Self, Error> {
+        let dbg = Dbg::new(parent, "MockApiServer");
+        let error = Error::new(&dbg, "start");
+        framing.validate(&dbg).map_err(|err| error.pass(err))?;
+        let listener = TcpListener::bind("127.0.0.1:0").map_err(|err| error.pass_with("Bind error", err.to_string()))?;
+        let address = listener.local_addr().map_err(|err| error.pass_with("Address error", err.to_string()))?;
+        listener.set_nonblocking(true).map_err(|err| error.pass_with("Set nonblocking error", err.to_string()))?;
+        let expectations = Arc::new(Mutex::new(VecDeque::new()));
+        let received = Arc::new(Mutex::new(vec![]));
+        let streams = Arc::new(Mutex::new(vec![]));
+        let exit = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let (dbg, framing, expectations, received, streams, exit) = (dbg.clone(), framing.clone(), expectations.clone(), received.clone(), streams.clone(), exit.clone());
+            thread::spawn(move || {
+                log::debug!("{}.start | Listening on {}", dbg, address);
+                while !exit.load(Ordering::SeqCst) {
+                    match listener.accept() {
+                        Ok((stream, remote)) => {
+                            log::debug!("{}.start | Accepted {}", dbg, remote);
+                            if let Err(err) = stream.set_nonblocking(false) {
+                                log::warn!("{}.start | Set blocking error: {}", dbg, err);
+                            }
+                            if let (Ok(clone), Ok(mut streams)) = (stream.try_clone(), streams.lock()) {
+                                streams.push(clone);
+                            }
+                            let (dbg, framing, expectations, received) = (dbg.clone(), framing.clone(), expectations.clone(), received.clone());
+                            thread::spawn(move || Self::serve(&dbg, stream, remote, &framing, &expectations, &received));
+                        }
+                        Err(err) if err.kind() == ErrorKind::WouldBlock => thread::sleep(Duration::from_millis(10)),
+                        Err(err) => {
+                            log::warn!("{}.start | Accept error: {}", dbg, err);
+                            thread::sleep(Duration::from_millis(10));
+                        }
+                    }
+                }
+                log::debug!("{}.start | Stopped", dbg);
+            })
+        };
+        Ok(Self { dbg, address, framing, expectations, received, streams, exit, handle: Some(handle) })
+    }
+    ///
+    /// Returns the address the server is listening on
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+    ///
+    /// Adds the reaction on the next query
+    pub fn expect(&self, expectation: MockExpectation) -> &Self {
+        match self.expectations.lock() {
+            Ok(mut expectations) => expectations.push_back(expectation),
+            Err(err) => log::warn!("{}.expect | Lock error: {}", self.dbg, err),
+        }
+        self
+    }
+    ///
+    /// Returns the number of the expectations not met yet
+    pub fn pending(&self) -> usize {
+        self.expectations.lock().map(|expectations| expectations.len()).unwrap_or(0)
+    }
+    ///
+    /// Sends the unsolicited notification frame to all the open connections
+    /// - Call it when no query is being replied, frames are not synchronized with the replies
+    pub fn notify(&self, notification: &ApiNotification) {
+        if let Ok(mut streams) = self.streams.lock() {
+            let frame = self.framing.frame(0, &notification.as_bytes());
+            streams.retain_mut(|stream| stream.write_all(&frame).is_ok());
+        }
+    }
+    ///
+    /// Returns all the queries received, in the order of receiving
+    pub fn received(&self) -> Vec<serde_json::Value> {
+        self.received.lock().map(|received| received.clone()).unwrap_or_default()
+    }
+    ///
+    /// Stops listening and closes all the connections
+    pub fn stop(&mut self) {
+        self.exit.store(true, Ordering::SeqCst);
+        if let Ok(mut streams) = self.streams.lock() {
+            for stream in streams.drain(..) {
+                _ = stream.shutdown(Shutdown::Both);
+            }
+        }
+        if let Some(handle) = self.handle.take() {
+            if let Err(err) = handle.join() {
+                log::warn!("{}.stop | Thread error: {:?}", self.dbg, err);
+            }
+        }
+    }
+    ///
+    /// Replies the queries of the single connection
+    fn serve(dbg: &Dbg, stream: TcpStream, remote: SocketAddr, framing: &ApiFraming, expectations: &Mutex<VecDeque<MockExpectation>>, received: &Mutex<Vec<serde_json::Value>>) {
+        let mut writer = match stream.try_clone() {
+            Ok(clone) => clone,
+            Err(err) => {
+                log::warn!("{}.serve | Clone tcp stream error: {}", dbg, err);
+                return;
+            }
+        };
+        // The connection waits for the next query as long as it's open
+        let mut socket = TcpSocket::new(dbg, remote, framing.message(dbg), Some(Arc::new(stream)))
+            .with_timeout(Duration::MAX);
+        while let Ok((frame_id, msg)) = socket.read() {
+            let bytes = msg.to_be_bytes();
+            let query: serde_json::Value = match serde_json::from_slice(&bytes) {
+                Ok(query) => query,
+                Err(err) => {
+                    log::warn!("{}.serve | Invalid query: {}", dbg, err);
+                    serde_json::Value::String(String::from_utf8_lossy(&bytes).into_owned())
+                }
+            };
+            log::debug!("{}.serve | Query: {}", dbg, query);
+            if let Ok(mut received) = received.lock() {
+                received.push(query.clone());
+            }
+            let expectation = match expectations.lock() {
+                Ok(mut expectations) => match expectations.front() {
+                    Some(expectation) if expectation.matcher.as_ref().is_none_or(|matcher| matcher(&query)) => expectations.pop_front(),
+                    _ => None,
+                },
+                Err(_) => None,
+            };
+            let (reply, delay) = match expectation {
+                Some(expectation) => (expectation.reply, expectation.delay),
+                None => (
+                    MockReply::Error(ApiError::new(
+                        "MockApiServer - unexpected query",
+                        format!("{}.serve | No expectation matches the query: {}", dbg, query),
+                    )),
+                    Duration::ZERO,
+                ),
+            };
+            thread::sleep(delay);
+            let str_field = |key: &str| query[key].as_str().unwrap_or_default().to_owned();
+            let keep_alive = query["keepAlive"].as_bool().unwrap_or(false);
+            let debug = query["debug"].as_bool().unwrap_or(false);
+            let src_query = match debug {
+                true => query.to_string(),
+                false => String::new(),
+            };
+            let truncated = matches!(reply, MockReply::Truncated(_));
+            let reply = match reply {
+                MockReply::Rows(rows) | MockReply::Truncated(rows) => ApiReply::new(str_field("authToken"), str_field("id"), keep_alive, src_query, rows),
+                MockReply::Cursor(rows, cursor) => ApiReply::new(str_field("authToken"), str_field("id"), keep_alive, src_query, rows)
+                    .with_cursor(cursor),
+                MockReply::Error(err) => ApiReply::error(str_field("authToken"), str_field("id"), keep_alive, src_query, err.debug(debug)),
+                MockReply::Drop => {
+                    log::debug!("{}.serve | Connection dropped", dbg);
+                    _ = writer.shutdown(Shutdown::Both);
+                    return;
+                }
+                MockReply::Silence => continue,
+            };
+            let frame = framing.frame(frame_id.0, &reply.as_bytes());
+            let frame = match truncated {
+                true => &frame[..frame.len() / 2],
+                false => &frame[..],
+            };
+            if let Err(err) = writer.write_all(frame) {
+                log::warn!("{}.serve | Write error: {}", dbg, err);
+                return;
+            }
+        }
+    }
+}
+//
+//
+impl Drop for MockApiServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
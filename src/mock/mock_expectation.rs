@@ -0,0 +1,108 @@
+use std::time::Duration;
+use indexmap::IndexMap;
+use crate::{api::reply::api_reply::ApiReplyCursor, error::api_error::ApiError};
+///
+/// Matches the incoming query
+pub type MockMatcher = Box<dyn Fn(&serde_json::Value) -> bool + Send>;
+///
+/// What the [MockApiServer](crate::mock::mock_api_server::MockApiServer) does on the query
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockReply {
+    /// Replies with the rows
+    Rows(Vec<IndexMap<String, serde_json::Value>>),
+    /// Replies with the rows of the cursor page
+    Cursor(Vec<IndexMap<String, serde_json::Value>>, ApiReplyCursor),
+    /// Replies with the error
+    Error(ApiError),
+    /// Closes the connection without reply
+    Drop,
+    /// Doesn't reply, the connection stays open
+    Silence,
+    /// Sends the first half of the frame of the reply with the rows, the connection stays open
+    Truncated(Vec<IndexMap<String, serde_json::Value>>),
+}
+///
+/// Scripted reaction of the [MockApiServer](crate::mock::mock_api_server::MockApiServer) on the single query
+/// ```
+/// server.expect(MockExpectation::rows(vec![row]).when(|query| query["sql"].is_object()));
+/// server.expect(MockExpectation::error(ApiError::new("message", "details")).with_delay(Duration::from_millis(100)));
+/// server.expect(MockExpectation::drop());
+/// server.expect(MockExpectation::truncated(vec![row]));
+/// ```
+pub struct MockExpectation {
+    pub(crate) matcher: Option<MockMatcher>,
+    pub(crate) reply: MockReply,
+    pub(crate) delay: Duration,
+}
+//
+//
+impl MockExpectation {
+    ///
+    /// Returns [MockExpectation] replying with the `rows`
+    pub fn rows(rows: Vec<IndexMap<String, serde_json::Value>>) -> Self {
+        Self::new(MockReply::Rows(rows))
+    }
+    ///
+    /// Returns [MockExpectation] replying with the `rows` from the json array of objects
+    /// - Panics if `rows` isn't an array of objects
+    pub fn json(rows: serde_json::Value) -> Self {
+        match serde_json::from_value(rows) {
+            Ok(rows) => Self::rows(rows),
+            Err(err) => panic!("MockExpectation.json | Array of objects expected: {}", err),
+        }
+    }
+    ///
+    /// Returns [MockExpectation] replying with the page of the cursor
+    pub fn cursor(rows: Vec<IndexMap<String, serde_json::Value>>, cursor: ApiReplyCursor) -> Self {
+        Self::new(MockReply::Cursor(rows, cursor))
+    }
+    ///
+    /// Returns [MockExpectation] replying with the `error`
+    pub fn error(error: ApiError) -> Self {
+        Self::new(MockReply::Error(error))
+    }
+    ///
+    /// Returns [MockExpectation] closing the connection without reply
+    pub fn drop() -> Self {
+        Self::new(MockReply::Drop)
+    }
+    ///
+    /// Returns [MockExpectation] never replying
+    pub fn silence() -> Self {
+        Self::new(MockReply::Silence)
+    }
+    ///
+    /// Returns [MockExpectation] sending the first half of the reply with the `rows` and never the rest
+    pub fn truncated(rows: Vec<IndexMap<String, serde_json::Value>>) -> Self {
+        Self::new(MockReply::Truncated(rows))
+    }
+    ///
+    /// Returns [MockExpectation] applied to the query only if `matcher` returns true,
+    /// otherwise the query is replied with the error
+    pub fn when(mut self, matcher: impl Fn(&serde_json::Value) -> bool + Send + 'static) -> Self {
+        self.matcher = Some(Box::new(matcher));
+        self
+    }
+    ///
+    /// Returns [MockExpectation] reacting after the `delay`
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+    ///
+    ///
+    fn new(reply: MockReply) -> Self {
+        Self { matcher: None, reply, delay: Duration::ZERO }
+    }
+}
+//
+//
+impl std::fmt::Debug for MockExpectation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockExpectation")
+            .field("matcher", &self.matcher.is_some())
+            .field("reply", &self.reply)
+            .field("delay", &self.delay)
+            .finish()
+    }
+}
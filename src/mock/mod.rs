@@ -0,0 +1,2 @@
+pub mod mock_api_server;
+pub mod mock_expectation;
\ No newline at end of file
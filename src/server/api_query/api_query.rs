@@ -1,7 +1,7 @@
-use serde::{Serialize, Deserialize};
+use serde::{de::DeserializeOwned, Serialize, Deserialize};
 use crate::{
     error::api_error::ApiError, 
-    server::api_query::{api_query_error::ApiQueryError, api_query_executable::ApiQueryExecutable, api_query_python::ApiQueryPython, api_query_sql::ApiQuerySql, api_query_type::{ApiQueryType, ApiQueryTypeName}}, 
+    server::api_query::{api_query_cursor::ApiQueryCursor, api_query_error::ApiQueryError, api_query_type::{ApiQueryType, ApiQueryTypeName}}, 
 };
 
 ///
@@ -52,85 +52,64 @@ impl ApiQuery {
         }
     }
     ///
-    /// Returns `ApiQuery` parsing query of type `ApiQueryType::Sql`
-    fn parse_api_query_sql(src_query: &str, json: serde_json::Value, auth_token: String, id: String, keep_alive: bool, debug: bool) -> ApiQuery {
-        log::debug!("[ApiQuery.parseApiQuerySql] detected: {}", ApiQueryTypeName::Sql.value());
-        match ApiQuerySql::from_json(json[ApiQueryTypeName::Sql.value()].clone()) {
-            Ok(api_query_sql) => {
-                ApiQuery::new(
-                    auth_token,
-                    id,
-                    ApiQueryType::Sql( api_query_sql ),
-                    src_query,
-                    keep_alive,
-                    debug,
-                )
-            },
-            Err(err) => {
-                ApiQuery::new(
-                    auth_token,
-                    id,
-                    ApiQueryType::Error( ApiQueryError::new(err) ),
-                    src_query,
-                    keep_alive,
-                    debug,
-                )
-            },
+    /// Returns the query of the `kind` deserialized from the field named by the kind, `{"sql": {...}}`
+    /// - The constraints not expressed by the types are checked as well:
+    ///   non empty batch `statements` & subscribe `channels`, positive cursor `pageSize`
+    fn parse_query(kind: ApiQueryTypeName, json: &serde_json::Value) -> Result<ApiQueryType, ApiError> {
+        log::debug!("ApiQuery.fromBytes | detected: {}", kind.value());
+        let query = match kind {
+            ApiQueryTypeName::Sql => ApiQueryType::Sql(Self::deserialize(kind, json)?),
+            ApiQueryTypeName::Batch => ApiQueryType::Batch(Self::deserialize(kind, json)?),
+            ApiQueryTypeName::Cursor => ApiQueryType::Cursor(Self::deserialize(kind, json)?),
+            ApiQueryTypeName::Cancel => ApiQueryType::Cancel(Self::deserialize(kind, json)?),
+            ApiQueryTypeName::Subscribe => ApiQueryType::Subscribe(Self::deserialize(kind, json)?),
+            ApiQueryTypeName::Login => ApiQueryType::Login(Self::deserialize(kind, json)?),
+            ApiQueryTypeName::Python => ApiQueryType::Python(Self::deserialize(kind, json)?),
+            ApiQueryTypeName::Executable => ApiQueryType::Executable(Self::deserialize(kind, json)?),
+            ApiQueryTypeName::Unknown | ApiQueryTypeName::Error => return Ok(ApiQueryType::Unknown),
+        };
+        let invalid = match &query {
+            ApiQueryType::Batch(batch) if batch.statements.is_empty() => Some("statements"),
+            ApiQueryType::Cursor(ApiQueryCursor::Open(open)) if open.page_size == 0 => Some("open.pageSize"),
+            ApiQueryType::Subscribe(subscribe) if subscribe.channels.is_empty() || subscribe.channels.iter().any(|channel| channel.is_empty()) => Some("channels"),
+            _ => None,
+        };
+        match invalid {
+            Some(field) => Err(Self::invalid(kind, field, "empty or out of range")),
+            None => Ok(query),
         }
     }
     ///
-    /// Returns `ApiQuery` parsing query of type `ApiQueryType::Python`
-    fn parse_api_query_python(src_query: &str, json: serde_json::Value, auth_token: String, id: String, keep_alive: bool, debug: bool) -> ApiQuery {
-        log::debug!("ApiQuery.fromBytes | detected: {}", ApiQueryTypeName::Python.value());
-        match ApiQueryPython::from_json(json[ApiQueryTypeName::Python.value()].clone()) {
-            Ok(api_query_python) => {
-                ApiQuery::new(
-                    auth_token,
-                    id,
-                    ApiQueryType::Python( api_query_python ),
-                    src_query,
-                    keep_alive,
-                    debug,
-                )
-            },
-            Err(err) => {
-                ApiQuery::new(
-                    auth_token,
-                    id,
-                    ApiQueryType::Error( ApiQueryError::new(err) ),
-                    src_query,
-                    keep_alive,
-                    debug,
-                )
-            },
-        }
+    /// Returns the query of the `kind` deserialized by serde,
+    /// the error points to the invalid field: `API SQL Service - invalid query (near field "sql")`
+    fn deserialize<T: DeserializeOwned>(kind: ApiQueryTypeName, json: &serde_json::Value) -> Result<T, ApiError> {
+        serde_path_to_error::deserialize(&json[kind.value()]).map_err(|err| {
+            let path = Some(err.path().to_string()).filter(|path| path != ".");
+            let inner = err.inner().to_string();
+            let missing = inner.strip_prefix("missing field `").and_then(|field| field.split('`').next());
+            let field = match (path, missing) {
+                (Some(path), Some(missing)) => format!("{}.{}", path, missing),
+                (Some(path), None) => path,
+                (None, Some(missing)) => missing.to_owned(),
+                (None, None) => kind.value().to_owned(),
+            };
+            Self::invalid(kind, &field, inner)
+        })
     }
     ///
-    /// Returns `ApiQuery` parsing query of type `ApiQueryType::Executable`
-    fn parse_api_query_executable(src_query: &str, json: serde_json::Value, auth_token: String, id: String, keep_alive: bool, debug: bool) -> ApiQuery {
-        log::debug!("ApiQuery.fromBytes | detected: {}", ApiQueryTypeName::Executable.value());
-        match ApiQueryExecutable::from_json(json[ApiQueryTypeName::Executable.value()].clone()) {
-            Ok(api_query_executable) => {
-                ApiQuery::new(
-                    auth_token,
-                    id,
-                    ApiQueryType::Executable( api_query_executable ),
-                    src_query,
-                    keep_alive,
-                    debug,
-                )
-            },
-            Err(err) => {
-                ApiQuery::new(
-                    auth_token,
-                    id,
-                    ApiQueryType::Error( ApiQueryError::new(err) ),
-                    src_query,
-                    keep_alive,
-                    debug,
-                )
-            },
-        }
+    /// Returns the error of the invalid `field` of the query of the `kind`
+    fn invalid(kind: ApiQueryTypeName, field: &str, details: impl std::fmt::Display) -> ApiError {
+        let message = match kind {
+            ApiQueryTypeName::Cancel => "API Service - invalid cancel query",
+            ApiQueryTypeName::Subscribe => "API SQL Service - invalid subscribe query",
+            ApiQueryTypeName::Login => "API Auth Service - invalid login query",
+            ApiQueryTypeName::Python => "API Python Script Service - invalid query",
+            ApiQueryTypeName::Executable => "API Executable service - invalid query",
+            _ => "API SQL Service - invalid query",
+        };
+        let details = format!("ApiQuery.fromBytes | field '{}' of the '{}' query: {}", field, kind.value(), details);
+        log::warn!("{}", details);
+        ApiError::new(format!("{} (near field \"{}\")", message, field), details)
     }
     ///
     /// Returns `ApiQueryTypeName` if parsed
@@ -141,6 +120,26 @@ impl ApiQuery {
             queries += 1;
             query_type = ApiQueryTypeName::Sql
         }
+        if query.contains_key(ApiQueryTypeName::Batch.value()) {
+            queries += 1;
+            query_type = ApiQueryTypeName::Batch
+        }
+        if query.contains_key(ApiQueryTypeName::Cursor.value()) {
+            queries += 1;
+            query_type = ApiQueryTypeName::Cursor
+        }
+        if query.contains_key(ApiQueryTypeName::Cancel.value()) {
+            queries += 1;
+            query_type = ApiQueryTypeName::Cancel
+        }
+        if query.contains_key(ApiQueryTypeName::Subscribe.value()) {
+            queries += 1;
+            query_type = ApiQueryTypeName::Subscribe
+        }
+        if query.contains_key(ApiQueryTypeName::Login.value()) {
+            queries += 1;
+            query_type = ApiQueryTypeName::Login
+        }
         if query.contains_key(ApiQueryTypeName::Python.value()) {
             queries += 1;
             query_type = ApiQueryTypeName::Python;
@@ -217,30 +216,19 @@ impl ApiQuery {
                             },
                             None => {
                                 log::trace!("ApiQuery.fromBytes | obj: {:?}", query_map);
-                                match Self::parse_query_type_name(&query_map) {
-                                    Ok(query_type) => match query_type {
-                                        ApiQueryTypeName::Sql => Self::parse_api_query_sql(&json.to_string(), json, auth_token, id, keep_alive, debug),
-                                        ApiQueryTypeName::Python => Self::parse_api_query_python(&json.to_string(), json, auth_token, id, keep_alive, debug),
-                                        ApiQueryTypeName::Executable => Self::parse_api_query_executable(&json.to_string(), json, auth_token, id, keep_alive, debug),
-                                        ApiQueryTypeName::Unknown => ApiQuery {
-                                            auth_token,
-                                            id,
-                                            query: ApiQueryType::Unknown,
-                                            src_query: json.to_string(), 
-                                            keep_alive,
-                                            debug,
-                                        },
-                                    },
-                                    Err(err) => {
-                                        ApiQuery {
-                                            auth_token,
-                                            id,
-                                            query: ApiQueryType::Error( ApiQueryError::new(err)),
-                                            src_query: json.to_string(),
-                                            keep_alive,
-                                            debug,
-                                        }
-                                    },
+                                let query = Self::parse_query_type_name(query_map).and_then(|kind| Self::parse_query(kind, &json));
+                                // The password is masked in the original query, so it never comes back in the debug reply
+                                let mut src_query = json.clone();
+                                if let Some(pass) = src_query.get_mut(ApiQueryTypeName::Login.value()).and_then(|login| login.get_mut("pass")) {
+                                    *pass = serde_json::Value::String("***".to_owned());
+                                }
+                                ApiQuery {
+                                    auth_token,
+                                    id,
+                                    query: query.unwrap_or_else(|err| ApiQueryType::Error(ApiQueryError::new(err))),
+                                    src_query: src_query.to_string(),
+                                    keep_alive,
+                                    debug,
                                 }
                             },
                         }
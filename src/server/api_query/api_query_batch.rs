@@ -0,0 +1,79 @@
+use postgres::fallible_iterator::FallibleIterator;
+use crate::{
+    error::api_error::ApiError,
+    server::{
+        api_query::{api_query_sql::ApiQuerySql, api_query_sql_params::SqlParam, row_map::RowMap, sql_lexer::SqlDialect},
+        postgres::pg_service::PgService,
+    },
+};
+pub use crate::api::query::api_query_batch::{ApiQueryBatch, ApiQueryBatchStatement};
+//
+// Server side binding of the shared [ApiQueryBatchStatement]
+impl ApiQueryBatchStatement {
+    ///
+    /// Returns the sql prepared to be executed and it's parameters to be bound, the `sql` is read by the postgres rules
+    pub fn bind(&self) -> Result<(String, Vec<SqlParam>), ApiError> {
+        self.params.bind(&self.sql, SqlDialect::Postgres)
+    }
+}
+//
+//
+impl ApiQueryBatch {
+    ///
+    /// Returns true if all the statements only read the data, see [ApiQuerySql::read_only]
+    pub fn is_read_only(&self) -> bool {
+        self.statements.iter().all(|statement| ApiQuerySql::read_only(&statement.sql))
+    }
+    ///
+    /// Executes all statements in the single transaction
+    /// - `read_only` - the transaction started `READ ONLY`
+    /// - `row_map` - converts the postgres row into the [RowMap]
+    /// - Returns one result set per statement, in the order of the statements:
+    ///   `{"rows": [...], "affected": n}`
+    /// - On the first failed statement transaction is rolled back and Err returned,
+    ///   the errors of binding the params & reading the rows include the index of the statement as well
+    pub fn execute(&self, client: &mut postgres::Client, read_only: bool, row_map: impl Fn(&postgres::Row) -> Result<RowMap, ApiError>) -> Result<Vec<RowMap>, ApiError> {
+        let error = |index: Option<usize>, err: String| {
+            let message = match index {
+                Some(index) => format!("API SQL Service - batch statement {} failed, transaction rolled back", index),
+                None => "API SQL Service - batch transaction failed".to_owned(),
+            };
+            let details = format!("[ApiQueryBatch.execute] {}: {}", message, err);
+            log::warn!("{}", details);
+            ApiError::new(message, details)
+        };
+        // The message of the database error, `db error` only otherwise
+        let db_error = |err: postgres::Error| match err.as_db_error() {
+            Some(db) => format!("{} {}: {}", db.severity(), db.code().code(), db.message()),
+            None => err.to_string(),
+        };
+        let mut transaction = PgService::transaction(client, read_only).map_err(|err| error(None, err.to_string()))?;
+        let mut results = vec![];
+        for (index, statement) in self.statements.iter().enumerate() {
+            let (sql, params) = statement.bind().map_err(|err| error(Some(index), err.to_string()))?;
+            let mut rows = vec![];
+            let affected = match transaction.query_raw(sql.as_str(), params.iter()) {
+                Ok(mut iter) => {
+                    loop {
+                        match iter.next() {
+                            Ok(Some(row)) => match row_map(&row) {
+                                Ok(row) => rows.push(serde_json::Value::Object(row.into_iter().collect())),
+                                Err(err) => return Err(error(Some(index), err.to_string())),
+                            },
+                            Ok(None) => break,
+                            Err(err) => return Err(error(Some(index), db_error(err))),
+                        }
+                    }
+                    iter.rows_affected().unwrap_or(0)
+                }
+                Err(err) => return Err(error(Some(index), db_error(err))),
+            };
+            results.push(RowMap::from([
+                ("rows".to_owned(), serde_json::Value::Array(rows)),
+                ("affected".to_owned(), serde_json::json!(affected)),
+            ]));
+        }
+        transaction.commit().map_err(|err| error(None, err.to_string()))?;
+        Ok(results)
+    }
+}
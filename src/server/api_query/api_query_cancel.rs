@@ -0,0 +1 @@
+pub use crate::api::query::api_query_cancel::ApiQueryCancel;
\ No newline at end of file
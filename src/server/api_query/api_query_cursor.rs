@@ -0,0 +1 @@
+pub use crate::api::query::api_query_cursor::{ApiQueryCursor, ApiQueryCursorOpen};
\ No newline at end of file
@@ -0,0 +1 @@
+pub use crate::api::query::api_query_login::ApiQueryLogin;
\ No newline at end of file
@@ -1,42 +1,63 @@
-use serde::{Serialize, Deserialize};
-use crate::error::api_error::ApiError;
-
-///
-///
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct ApiQuerySql {
-    pub database: String,
-    pub sql: String,
-}
+use crate::{
+    error::api_error::ApiError,
+    server::api_query::{api_query_sql_params::SqlParam, sql_lexer::{SqlDialect, SqlLexer, SqlToken}},
+};
+pub use crate::api::query::api_query_sql::ApiQuerySql;
+//
+// Server side binding of the shared [ApiQuerySql]
 impl ApiQuerySql {
     ///
-    pub fn from_json(json_map: serde_json::Value) -> Result<Self, ApiError> {
-        log::trace!("[ApiQuerySql.fromJson] json: {:?}", json_map);
-        let key = "database";
-        if let serde_json::Value::String(database) = &json_map[key] {
-            log::trace!("[ApiQuerySql.fromJson] field '{}': {:?}", &key, &database);
-            let key = "sql";
-            if let serde_json::Value::String(sql) = &json_map[key] {
-                log::trace!("[ApiQuerySql.fromJson] field '{}': {:?}", &key, &sql);
-                return Ok(ApiQuerySql {
-                    database: database.to_owned(),
-                    sql: sql.to_owned(),
-                });
-            } else {
-                let details = format!("[ApiQuerySql.fromJson] field '{}' of type String not found or invalid content", key);
-                log::warn!("{}", details);
-                return Err(ApiError::new(
-                    format!("API SQL Service - invalid query (near field \"{}\")", key), 
-                    details,
-                ));
+    /// Returns the sql prepared to be executed and it's parameters to be bound
+    /// - Named placeholders `:name` replaced with positional `$n`, the sql is read by the rules of the `dialect`
+    pub fn bind(&self, dialect: SqlDialect) -> Result<(String, Vec<SqlParam>), ApiError> {
+        self.params.bind(&self.sql, dialect)
+    }
+    ///
+    /// Returns the sql prepared to be executed and it's parameters to be bound
+    /// - Named placeholders `:name` replaced with `?`, for the databases without numbered placeholders (MySQL)
+    pub fn bind_anonymous(&self) -> Result<(String, Vec<SqlParam>), ApiError> {
+        self.params.bind_anonymous(&self.sql)
+    }
+    ///
+    /// Returns true if the sql only reads the data
+    pub fn is_read_only(&self) -> bool {
+        Self::read_only(&self.sql)
+    }
+    ///
+    /// Returns true if all the statements of the `sql` start with the reading keyword
+    /// and there are no modifying keywords outside of the quotes & the comments
+    /// - The `sql` is read by the lexical rules of each supported database and must be reading by all of them,
+    ///   so the quotes & the comments can't hide the statement from any database
+    /// - Unterminated quotes & comments, the backslash outside of the quotes, mysql `/*! */` comments
+    ///   and anything else not recognized are treated as modifying
+    /// - `select ... into` & `select ... for update` are treated as modifying, as they create the table & lock the rows
+    /// - Functions with the side effects can't be detected,
+    ///   so the query of the user granted reading only is also executed by the database in the read only mode
+    pub fn read_only(sql: &str) -> bool {
+        const READING: [&str; 6] = ["select", "with", "values", "table", "show", "explain"];
+        // Can be the part of the statement starting with the reading keyword
+        const MODIFYING: [&str; 9] = ["insert", "update", "delete", "merge", "into", "create", "lock", "nextval", "setval"];
+        SqlDialect::ALL.iter().all(|dialect| match Self::statements(sql, *dialect) {
+            Some(statements) => !statements.is_empty() && statements.iter().all(|words| {
+                READING.contains(&words[0].as_str()) && !words.iter().any(|word| MODIFYING.contains(&word.as_str()))
+            }),
+            None => false,
+        })
+    }
+    ///
+    /// Returns the lowercase words of the not empty statements of the `sql`, read by the rules of the `dialect`
+    /// - Quoted strings, identifiers & comments are skipped
+    /// - None if the `sql` isn't recognized
+    fn statements(sql: &str, dialect: SqlDialect) -> Option<Vec<Vec<String>>> {
+        let mut statements = vec![vec![]];
+        for (token, text) in SqlLexer::new(sql, dialect) {
+            match token {
+                SqlToken::Word => statements.last_mut()?.push(text.to_ascii_lowercase()),
+                SqlToken::Char(';') => statements.push(vec![]),
+                SqlToken::Char('\\' | '`') | SqlToken::Unrecognized => return None,
+                _ => {}
             }
-        } else {
-            let details = format!("[ApiQuerySql.fromJson] field '{}' of type String not found or invalid content", key);
-            log::warn!("{}", details);
-            return Err(ApiError::new(
-                format!("API SQL Service - invalid query (near field \"{}\")", key), 
-                details,
-            ));
         }
+        Some(statements.into_iter().filter(|words| !words.is_empty()).collect())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,145 @@
+use bytes::BytesMut;
+use postgres::types::{to_sql_checked, IsNull, ToSql, Type};
+use crate::{error::api_error::ApiError, server::api_query::sql_lexer::{SqlDialect, SqlLexer, SqlToken}};
+pub use crate::api::query::api_query_sql_params::ApiQuerySqlParams;
+//
+// Server side binding of the shared [ApiQuerySqlParams]
+impl ApiQuerySqlParams {
+    ///
+    /// Returns the sql with `$n` placeholders and the parameters in the order of the placeholders
+    /// - `Positional` parameters are returned as is
+    /// - `Named` placeholders `:name` are replaced with `$n`, n - is the order of the first occurrence of the name,
+    ///   casts `::type`, array slices `a[1:n]`, the quoted strings / identifiers & the comments are skipped,
+    ///   the `sql` is read by the rules of the `dialect`
+    pub fn bind(&self, sql: &str, dialect: SqlDialect) -> Result<(String, Vec<SqlParam>), ApiError> {
+        self.replace(sql, dialect, true)
+    }
+    ///
+    /// Returns the sql with `?` placeholders and the parameters in the order of the placeholders,
+    /// for the databases without numbered placeholders (MySQL)
+    /// - `Positional` parameters are returned as is
+    /// - `Named` placeholders `:name` are replaced with `?`, the value is repeated for every occurrence of the name
+    ///   the `sql` is read by the mysql rules, the backslash escapes the next char of the quoted strings / identifiers
+    pub fn bind_anonymous(&self, sql: &str) -> Result<(String, Vec<SqlParam>), ApiError> {
+        self.replace(sql, SqlDialect::Mysql, false)
+    }
+    ///
+    /// Returns the sql with the named placeholders replaced by `$n` if `numbered`, by `?` otherwise
+    fn replace(&self, sql: &str, dialect: SqlDialect, numbered: bool) -> Result<(String, Vec<SqlParam>), ApiError> {
+        match self {
+            ApiQuerySqlParams::Positional(params) => Ok((
+                sql.to_owned(),
+                params.iter().map(|value| SqlParam(value.to_owned())).collect(),
+            )),
+            ApiQuerySqlParams::Named(params) => {
+                let mut names: Vec<String> = vec![];
+                let mut occurrences: Vec<usize> = vec![];
+                let mut result = String::with_capacity(sql.len());
+                let mut tokens = SqlLexer::new(sql, dialect).peekable();
+                // Depth of the postgres array subscripts, the bound of the slice `a[1:n]` isn't the placeholder
+                let mut subscripts = 0usize;
+                while let Some((token, text)) = tokens.next() {
+                    match token {
+                        SqlToken::Char('[') => subscripts += 1,
+                        SqlToken::Char(']') => subscripts = subscripts.saturating_sub(1),
+                        SqlToken::Char(':') if tokens.next_if_eq(&(SqlToken::Char(':'), ":")).is_some() => {
+                            result.push_str("::");
+                            continue;
+                        }
+                        SqlToken::Char(':') if subscripts == 0 => {
+                            let name = tokens.next_if(|(token, text)| {
+                                *token == SqlToken::Word && text.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                            });
+                            if let Some((_, name)) = name {
+                                let index = match names.iter().position(|n| n == name) {
+                                    Some(index) => index,
+                                    None => {
+                                        names.push(name.to_owned());
+                                        names.len() - 1
+                                    }
+                                };
+                                match numbered {
+                                    true => result.push_str(&format!("${}", index + 1)),
+                                    false => result.push('?'),
+                                }
+                                occurrences.push(index);
+                                continue;
+                            }
+                        }
+                        _ => {}
+                    }
+                    result.push_str(text);
+                }
+                if !numbered {
+                    names = occurrences.into_iter().map(|index| names[index].clone()).collect();
+                }
+                let mut values = vec![];
+                for name in names {
+                    match params.get(&name) {
+                        Some(value) => values.push(SqlParam(value.to_owned())),
+                        None => {
+                            let details = format!("[ApiQuerySqlParams.bind] parameter ':{}' not found in params: {:?}", name, params);
+                            log::warn!("{}", details);
+                            return Err(ApiError::new(
+                                format!("API SQL Service - missing parameter \"{}\"", name),
+                                details,
+                            ));
+                        }
+                    }
+                }
+                Ok((result, values))
+            }
+        }
+    }
+}
+///
+/// Single parameter of the prepared statement
+/// - Converts typed JSON into the postgres type of the placeholder
+///     - null - NULL of any type
+///     - bool - bool
+///     - number - int2 / int4 / int8 / float4 / float8 / text
+///     - string - text / varchar / char / name, timestamp / timestamptz / date parsed from the string (RFC 3339 / ISO 8601)
+///     - any value - json / jsonb
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlParam(pub serde_json::Value);
+//
+//
+impl ToSql for SqlParam {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        let value = &self.0;
+        let err = || format!("SqlParam.to_sql | Unable to bind {} to the parameter of type '{}'", value, ty);
+        if value.is_null() {
+            return Ok(IsNull::Yes);
+        }
+        match *ty {
+            Type::JSON | Type::JSONB => value.to_sql(ty, out),
+            Type::BOOL => value.as_bool().ok_or_else(err)?.to_sql(ty, out),
+            Type::INT2 => i16::try_from(value.as_i64().ok_or_else(err)?).map_err(|_| err())?.to_sql(ty, out),
+            Type::INT4 => i32::try_from(value.as_i64().ok_or_else(err)?).map_err(|_| err())?.to_sql(ty, out),
+            Type::INT8 => value.as_i64().ok_or_else(err)?.to_sql(ty, out),
+            Type::FLOAT4 => (value.as_f64().ok_or_else(err)? as f32).to_sql(ty, out),
+            Type::FLOAT8 => value.as_f64().ok_or_else(err)?.to_sql(ty, out),
+            Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::UNKNOWN => match value {
+                serde_json::Value::String(value) => value.to_sql(ty, out),
+                serde_json::Value::Number(_) | serde_json::Value::Bool(_) => value.to_string().to_sql(ty, out),
+                _ => Err(err().into()),
+            },
+            Type::TIMESTAMPTZ => value.as_str().ok_or_else(err)?
+                .parse::<chrono::DateTime<chrono::Utc>>().map_err(|_| err())?
+                .to_sql(ty, out),
+            Type::TIMESTAMP => value.as_str().ok_or_else(err)?
+                .parse::<chrono::NaiveDateTime>().map_err(|_| err())?
+                .to_sql(ty, out),
+            Type::DATE => value.as_str().ok_or_else(err)?
+                .parse::<chrono::NaiveDate>().map_err(|_| err())?
+                .to_sql(ty, out),
+            _ => Err(err().into()),
+        }
+    }
+    //
+    //
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+    to_sql_checked!();
+}
@@ -5,6 +5,11 @@ use serde::{
 
 use crate::{
     server::api_query::api_query_sql::ApiQuerySql,
+    server::api_query::api_query_batch::ApiQueryBatch,
+    server::api_query::api_query_cursor::ApiQueryCursor,
+    server::api_query::api_query_cancel::ApiQueryCancel,
+    server::api_query::api_query_subscribe::ApiQuerySubscribe,
+    server::api_query::api_query_login::ApiQueryLogin,
     server::api_query::api_query_python::ApiQueryPython, 
     server::api_query::api_query_executable::ApiQueryExecutable, 
     server::api_query::api_query_error::ApiQueryError, 
@@ -15,25 +20,61 @@ use crate::{
 // #[serde(rename_all = "lowercase")]
 pub enum ApiQueryType {
     Sql(ApiQuerySql),
+    Batch(ApiQueryBatch),
+    Cursor(ApiQueryCursor),
+    Cancel(ApiQueryCancel),
+    Subscribe(ApiQuerySubscribe),
+    Login(ApiQueryLogin),
     Python(ApiQueryPython),
     Executable(ApiQueryExecutable),
     Unknown,
     Error(ApiQueryError),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ApiQueryTypeName {
     Sql,
+    Batch,
+    Cursor,
+    Cancel,
+    Subscribe,
+    Login,
     Python,
     Executable,
     Unknown,
+    Error,
+}
+impl ApiQueryType {
+    ///
+    /// Returns the name of the query type
+    pub fn name(&self) -> ApiQueryTypeName {
+        match self {
+            ApiQueryType::Sql(_) => ApiQueryTypeName::Sql,
+            ApiQueryType::Batch(_) => ApiQueryTypeName::Batch,
+            ApiQueryType::Cursor(_) => ApiQueryTypeName::Cursor,
+            ApiQueryType::Cancel(_) => ApiQueryTypeName::Cancel,
+            ApiQueryType::Subscribe(_) => ApiQueryTypeName::Subscribe,
+            ApiQueryType::Login(_) => ApiQueryTypeName::Login,
+            ApiQueryType::Python(_) => ApiQueryTypeName::Python,
+            ApiQueryType::Executable(_) => ApiQueryTypeName::Executable,
+            ApiQueryType::Unknown => ApiQueryTypeName::Unknown,
+            ApiQueryType::Error(_) => ApiQueryTypeName::Error,
+        }
+    }
 }
 impl ApiQueryTypeName {
     pub fn value(&self) -> &str {
         match *self {
             ApiQueryTypeName::Sql => "sql",
+            ApiQueryTypeName::Batch => "batch",
+            ApiQueryTypeName::Cursor => "cursor",
+            ApiQueryTypeName::Cancel => "cancel",
+            ApiQueryTypeName::Subscribe => "subscribe",
+            ApiQueryTypeName::Login => "login",
             ApiQueryTypeName::Python => "python",
             ApiQueryTypeName::Executable => "executable",
             ApiQueryTypeName::Unknown => "unknown",
+            ApiQueryTypeName::Error => "error",
         }
     }
 }
@@ -0,0 +1,20 @@
+///
+/// The kind of the column by it's SQL type, the null value of the column is replaced with by [NullDefaults]
+///
+/// [NullDefaults]: crate::server::config::null_defaults::NullDefaults
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnKind {
+    /// Integer & floating point types
+    Number,
+    /// Exact numeric / decimal types, returned as the strings
+    Decimal,
+    /// Character & enum types
+    Text,
+    Bool,
+    /// Timestamp / datetime types without the time zone
+    Timestamp,
+    /// Timestamp with the time zone, returned in UTC with the `Z` suffix
+    TimestampTz,
+    /// Date, time, json, binary, arrays & other types
+    Other,
+}
@@ -1,8 +1,16 @@
 pub mod api_query;
 pub mod api_query_type;
 pub mod api_query_sql;
+pub mod api_query_sql_params;
+pub mod sql_lexer;
+pub mod api_query_batch;
+pub mod api_query_cursor;
+pub mod api_query_cancel;
+pub mod api_query_subscribe;
+pub mod api_query_login;
 pub mod api_query_python;
 pub mod api_query_executable;
 pub mod api_query_unknown;
 pub mod api_query_error;
 pub mod row_map;
+pub mod column_kind;
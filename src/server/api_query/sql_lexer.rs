@@ -0,0 +1,170 @@
+///
+/// The lexical rules of the sql of the supported databases
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SqlDialect {
+    Sqlite,
+    Postgres,
+    /// Postgres with `standard_conforming_strings` off, backslash escapes in all the strings
+    PostgresEscapes,
+    Mysql,
+    /// Mysql with `NO_BACKSLASH_ESCAPES` or `ANSI_QUOTES`
+    MysqlNoEscapes,
+}
+//
+//
+impl SqlDialect {
+    pub const ALL: [Self; 5] = [Self::Sqlite, Self::Postgres, Self::PostgresEscapes, Self::Mysql, Self::MysqlNoEscapes];
+    ///
+    /// Returns true if the postgres `$tag$` quotes, `E''` strings & nested comments are recognized
+    pub fn is_postgres(&self) -> bool {
+        matches!(self, Self::Postgres | Self::PostgresEscapes)
+    }
+    ///
+    /// Returns true if the mysql `#` & `/*! */` comments are recognized
+    pub fn is_mysql(&self) -> bool {
+        matches!(self, Self::Mysql | Self::MysqlNoEscapes)
+    }
+    ///
+    /// Returns true if the backslash escapes the next char in the string quoted by the `quote`
+    /// - `prefix` - the word right before the opening `quote`, `e` of the postgres escape string `E'...'`
+    fn escapes(&self, quote: char, prefix: &str) -> bool {
+        match (self, quote) {
+            (Self::Postgres, '\'') => prefix.eq_ignore_ascii_case("e"),
+            (Self::PostgresEscapes, '\'') => true,
+            (Self::Mysql, _) => true,
+            _ => false,
+        }
+    }
+}
+///
+/// The token of the sql returned by [SqlLexer]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SqlToken {
+    /// Keyword, identifier or number
+    Word,
+    /// Quoted string or identifier, postgres dollar quoted string
+    Quoted,
+    Comment,
+    /// Any other single char, including the whitespace
+    Char(char),
+    /// The rest of the sql not recognized: unterminated quote or comment, mysql executable comment `/*! */`
+    Unrecognized,
+}
+///
+/// Splits the sql into the tokens by the lexical rules of the [SqlDialect]
+/// - Returns the tokens with their text, the texts of all the tokens make up the whole sql
+pub struct SqlLexer<'a> {
+    sql: &'a str,
+    pos: usize,
+    dialect: SqlDialect,
+    /// The word right before the current token
+    prefix: &'a str,
+}
+//
+//
+impl<'a> SqlLexer<'a> {
+    ///
+    /// Returns [SqlLexer] of the `sql` read by the rules of the `dialect`
+    pub fn new(sql: &'a str, dialect: SqlDialect) -> Self {
+        Self { sql, pos: 0, dialect, prefix: "" }
+    }
+    ///
+    /// Returns the token at the start of the `rest` & it's length in bytes
+    /// - None if the token isn't recognized
+    fn read(&self, rest: &str, c: char) -> Option<(SqlToken, usize)> {
+        let after = &rest[c.len_utf8()..];
+        let line = || rest.find('\n').unwrap_or(rest.len());
+        match c {
+            // `$` inside of the identifier, or the sqlite parameter / mysql identifier starting with `$`
+            c if c.is_alphanumeric() || c == '_' || (c == '$' && !self.dialect.is_postgres()) => {
+                let len = rest.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$')).unwrap_or(rest.len());
+                Some((SqlToken::Word, len))
+            }
+            '\'' | '"' => Self::quoted(after, c, self.dialect.escapes(c, self.prefix)).map(|len| (SqlToken::Quoted, 1 + len)),
+            '`' if !self.dialect.is_postgres() => Self::quoted(after, c, false).map(|len| (SqlToken::Quoted, 1 + len)),
+            '[' if self.dialect == SqlDialect::Sqlite => after.find(']').map(|len| (SqlToken::Quoted, len + 2)),
+            // Dollar quoted string `$tag$ ... $tag$`, not the positional parameter `$1`
+            '$' if !after.starts_with(|c: char| c.is_ascii_digit()) => {
+                let len = after.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(after.len());
+                if !after[len..].starts_with('$') {
+                    return None;
+                }
+                let tag = &rest[..len + 2];
+                rest[tag.len()..].find(tag).map(|len| (SqlToken::Quoted, 2 * tag.len() + len))
+            }
+            // Mysql comment requires the whitespace after `--`, `1--1` is the expression
+            '-' if after.starts_with('-')
+                && (!self.dialect.is_mysql() || after[1..].chars().next().is_none_or(|c| c.is_whitespace() || c.is_control())) => {
+                Some((SqlToken::Comment, line()))
+            }
+            '#' if self.dialect.is_mysql() => Some((SqlToken::Comment, line())),
+            '/' if after.starts_with('*') => {
+                // Mysql executes the content of the `/*! */` comments, mariadb also of the `/*M! */`
+                if self.dialect.is_mysql() && after[1..].starts_with(['!', 'M']) {
+                    return None;
+                }
+                // Postgres comments can be nested
+                let mut depth = 0;
+                let mut last = ' ';
+                for (index, c) in rest.char_indices() {
+                    match (last, c) {
+                        ('*', '/') => {
+                            depth -= 1;
+                            if depth == 0 {
+                                return Some((SqlToken::Comment, index + 1));
+                            }
+                            last = ' ';
+                        }
+                        ('/', '*') if depth == 0 || self.dialect.is_postgres() => {
+                            depth += 1;
+                            last = ' ';
+                        }
+                        _ => last = c,
+                    }
+                }
+                None
+            }
+            _ => Some((SqlToken::Char(c), c.len_utf8())),
+        }
+    }
+    ///
+    /// Returns the length of the quoted string or identifier up to & including the closing `quote`
+    /// - The doubled `quote` is the part of the string
+    /// - `escapes` - the backslash escapes the next char
+    /// - None if the closing `quote` not found
+    fn quoted(rest: &str, quote: char, escapes: bool) -> Option<usize> {
+        let mut chars = rest.char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '\\' if escapes => {
+                    chars.next()?;
+                }
+                c if c == quote => match rest[index + 1..].starts_with(quote) {
+                    true => {
+                        chars.next();
+                    }
+                    false => return Some(index + 1),
+                },
+                _ => {}
+            }
+        }
+        None
+    }
+}
+//
+//
+impl<'a> Iterator for SqlLexer<'a> {
+    type Item = (SqlToken, &'a str);
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.sql[self.pos..];
+        let c = rest.chars().next()?;
+        let (token, len) = self.read(rest, c).unwrap_or((SqlToken::Unrecognized, rest.len()));
+        let text = &rest[..len];
+        self.pos += len;
+        self.prefix = match token {
+            SqlToken::Word => text,
+            _ => "",
+        };
+        Some((token, text))
+    }
+}
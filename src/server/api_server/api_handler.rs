@@ -0,0 +1,59 @@
+use crate::{
+    api::reply::api_reply::ApiReplyCursor,
+    error::api_error::ApiError,
+    server::{api_query::{api_query::ApiQuery, row_map::RowMap}, api_server::api_session::ApiSession},
+};
+///
+/// The result of the query, handled by the [ApiHandler]
+/// - `rows` - the `data` of the reply
+/// - `cursor` - the state of the server side cursor, if `rows` is the page of the cursor
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ApiHandlerReply {
+    pub rows: Vec<RowMap>,
+    pub cursor: Option<ApiReplyCursor>,
+}
+//
+//
+impl ApiHandlerReply {
+    ///
+    /// Returns [ApiHandlerReply] containing the `rows`
+    pub fn new(rows: Vec<RowMap>) -> Self {
+        Self { rows, cursor: None }
+    }
+    ///
+    /// Returns [ApiHandlerReply] containing the page of the server side cursor
+    pub fn with_cursor(mut self, cursor: ApiReplyCursor) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+}
+//
+//
+impl From<Vec<RowMap>> for ApiHandlerReply {
+    fn from(rows: Vec<RowMap>) -> Self {
+        Self::new(rows)
+    }
+}
+///
+/// Executes the queries of the type it's registered for in the [ApiRouter](crate::server::api_server::api_router::ApiRouter)
+/// - Called from the thread of the connection, the query is received by
+/// - `session` - the state of the connection, kept between the queries
+/// - Returned error is replied to the client, it's `details` only if the query is `debug`
+/// ```
+/// let router = ApiRouter::new()
+///     .with_handler(ApiQueryTypeName::Sql, |query: &ApiQuery, _: &mut ApiSession| {
+///         Ok(vec![RowMap::from([("id".to_owned(), json!(1))])].into())
+///     });
+/// ```
+pub trait ApiHandler: Send + Sync {
+    fn handle(&self, query: &ApiQuery, session: &mut ApiSession) -> Result<ApiHandlerReply, ApiError>;
+}
+//
+//
+impl<F> ApiHandler for F
+where
+    F: Fn(&ApiQuery, &mut ApiSession) -> Result<ApiHandlerReply, ApiError> + Send + Sync {
+    fn handle(&self, query: &ApiQuery, session: &mut ApiSession) -> Result<ApiHandlerReply, ApiError> {
+        self(query, session)
+    }
+}
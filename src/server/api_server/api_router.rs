@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use indexmap::IndexMap;
+use crate::{
+    api::reply::api_reply::ApiReply,
+    error::api_error::ApiError,
+    server::{
+        api_query::{api_query::ApiQuery, api_query_type::{ApiQueryType, ApiQueryTypeName}, row_map::RowMap},
+        api_server::{api_handler::{ApiHandler, ApiHandlerReply}, api_session::ApiSession},
+        auth::auth_service::AuthService,
+    },
+};
+///
+/// Replies the parsed [ApiQuery] by the [ApiHandler] registered for it's type
+/// - `login` is replied by the [AuthService] if specified, all other queries then require the valid `authToken`
+///   and are authorized by the grants of the [AuthService], the forbidden query never reaches the handler,
+///   the query of the user granted reading only is handled with the read only [ApiSession]
+/// - `cancel`, `unknown` & `error` queries without the handler are replied by default:
+///   `cancel` by the [CancelRegistry](crate::server::cancel::cancel_registry::CancelRegistry) of the session,
+///   `unknown` & `error` with the error the query parsed with
+/// - Query of any other type without the handler is replied with the "not supported" error
+/// - Reply echoes the `id`, `authToken` & `keepAlive` of the query, the source `query` only if it's `debug`
+/// ```
+/// let router = ApiRouter::new()
+///     .with_auth(AuthService::new("secret", Duration::from_secs(3600)).with_user("user", "pass"))
+///     .with_handler(ApiQueryTypeName::Sql, SqlHandler::new(...))
+///     .with_handler(ApiQueryTypeName::Python, PythonHandler::new(...));
+/// ```
+#[derive(Default)]
+pub struct ApiRouter {
+    handlers: HashMap<ApiQueryTypeName, Box<dyn ApiHandler>>,
+    auth: Option<AuthService>,
+}
+//
+//
+impl ApiRouter {
+    ///
+    /// Returns [ApiRouter] without handlers
+    pub fn new() -> Self {
+        Self::default()
+    }
+    ///
+    /// Returns [ApiRouter] replying the queries of the type `name` by the `handler`
+    /// - The handler registered before for the same type is replaced
+    pub fn with_handler(mut self, name: ApiQueryTypeName, handler: impl ApiHandler + 'static) -> Self {
+        self.handlers.insert(name, Box::new(handler));
+        self
+    }
+    ///
+    /// Returns [ApiRouter] replying the `login` & verifying the `authToken` of the queries by the `auth`
+    pub fn with_auth(mut self, auth: AuthService) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+    ///
+    /// Returns the reply on the `query`
+    pub fn reply(&self, query: &ApiQuery, session: &mut ApiSession) -> ApiReply {
+        let debug = query.debug;
+        match self.handle(query, session) {
+            Ok(reply) => {
+                let result = ApiReply::new(query.auth_token(), query.id(), query.keep_alive, query.src_query(debug), reply.rows);
+                match reply.cursor {
+                    Some(cursor) => result.with_cursor(cursor),
+                    None => result,
+                }
+            }
+            Err(err) => {
+                log::debug!("ApiRouter.reply | Query '{}' error: {}", query.id(), err);
+                ApiReply::error(query.auth_token(), query.id(), query.keep_alive, query.src_query(debug), err.debug(debug))
+            }
+        }
+    }
+    ///
+    /// Returns the result of the `query` handled by the built-in or registered handler
+    fn handle(&self, query: &ApiQuery, session: &mut ApiSession) -> Result<ApiHandlerReply, ApiError> {
+        let kind = query.query();
+        let name = kind.name();
+        if let (ApiQueryType::Login(login), Some(auth)) = (&kind, &self.auth) {
+            let token = auth.login(login)?;
+            return Self::row(&token).map(|row| vec![row].into());
+        }
+        let read_only = match &self.auth {
+            Some(auth) => {
+                let claims = auth.verify(&query.auth_token())?;
+                auth.authorize(&claims.user, &kind)?
+            }
+            None => false,
+        };
+        session.set_read_only(read_only);
+        if let Some(handler) = self.handlers.get(&name) {
+            return handler.handle(query, session);
+        }
+        match kind {
+            ApiQueryType::Cancel(cancel) => {
+                let cancelled = session.cancel_registry().cancel(&query.auth_token(), &cancel.id)?;
+                Ok(vec![RowMap::from([("cancelled".to_owned(), serde_json::Value::Bool(cancelled))])].into())
+            }
+            ApiQueryType::Unknown => {
+                let details = format!("ApiRouter.handle | Unknown query: {}", query.src_query(true));
+                log::warn!("{}", details);
+                Err(ApiError::new("API Service - unknown query", details))
+            }
+            ApiQueryType::Error(err) => Err(err.err()),
+            _ => {
+                let details = format!("ApiRouter.handle | Handler of the '{}' query is not registered", name.value());
+                log::warn!("{}", details);
+                Err(ApiError::new(format!("API Service - query of type '{}' not supported", name.value()), details))
+            }
+        }
+    }
+    ///
+    /// Returns the row containing the fields of the `value`
+    fn row(value: &impl serde::Serialize) -> Result<RowMap, ApiError> {
+        match serde_json::to_value(value) {
+            Ok(serde_json::Value::Object(fields)) => Ok(fields.into_iter().collect::<IndexMap<_, _>>()),
+            Ok(value) => Err(ApiError::new("API Service - reply error", format!("ApiRouter.row | Object expected, found: {}", value))),
+            Err(err) => Err(ApiError::new("API Service - reply error", format!("ApiRouter.row | Error: {}", err))),
+        }
+    }
+}
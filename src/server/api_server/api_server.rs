@@ -0,0 +1,188 @@
+use std::{
+    collections::HashMap, io::ErrorKind, net::{Shutdown, SocketAddr, TcpListener, TcpStream},
+    sync::{atomic::{AtomicBool, Ordering}, Arc, Mutex}, thread::{self, JoinHandle}, time::Duration,
+};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    api::{message::api_framing::ApiFraming, socket::tcp_socket::TcpSocket},
+    server::{
+        api_query::api_query::ApiQuery,
+        api_server::{api_router::ApiRouter, api_session::ApiSession, api_writer::ApiWriter},
+        cancel::cancel_registry::CancelRegistry,
+    },
+};
+///
+/// Embedded API server
+/// - Listens the framed messages `Syn | Id | Kind | Size | Data`, the [ApiFraming] the clients are built with
+/// - Each connection is served in the own thread, the queries of the connection are replied in the order received
+/// - The message is parsed into [ApiQuery] and replied by the [ApiRouter], the reply has the `Id` of the query message
+/// - Connection is closed after the reply on the query without `keepAlive`
+/// ```
+/// let router = ApiRouter::new()
+///     .with_handler(ApiQueryTypeName::Sql, handler);
+/// let mut server = ApiServer::new("parent", "0.0.0.0:8080", router);
+/// server.start()?;
+/// ...
+/// server.stop();
+/// ```
+pub struct ApiServer {
+    dbg: Dbg,
+    address: String,
+    local: Option<SocketAddr>,
+    framing: ApiFraming,
+    router: Arc<ApiRouter>,
+    cancel: CancelRegistry,
+    streams: Arc<Mutex<HashMap<usize, TcpStream>>>,
+    exit: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+//
+//
+impl ApiServer {
+    ///
+    /// Interval of checking the exit flag while waiting for connections
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+    ///
+    /// Returns [ApiServer] new instance
+    /// - `address` - to be listened, port 0 - any free port, see [ApiServer::address]
+    /// - `router` - replies the queries
+    pub fn new(parent: impl Into<String>, address: impl Into<String>, router: ApiRouter) -> Self {
+        Self {
+            dbg: Dbg::new(parent, "ApiServer"),
+            address: address.into(),
+            local: None,
+            framing: ApiFraming::default(),
+            router: Arc::new(router),
+            cancel: CancelRegistry::default(),
+            streams: Arc::new(Mutex::new(HashMap::new())),
+            exit: Arc::new(AtomicBool::new(false)),
+            handle: None,
+        }
+    }
+    ///
+    /// Returns [ApiServer] with the specified framing of the messages
+    pub fn with_framing(mut self, framing: ApiFraming) -> Self {
+        self.framing = framing;
+        self
+    }
+    ///
+    /// Returns the address the server is listening on, None if it's not started
+    pub fn address(&self) -> Option<SocketAddr> {
+        self.local
+    }
+    ///
+    /// Starts listening in the own thread
+    /// - Returns the address the server is listening on
+    pub fn start(&mut self) -> Result<SocketAddr, Error> {
+        let error = Error::new(&self.dbg, "start");
+        if let Some(local) = self.local {
+            return Err(error.err(format!("Already listening on {}", local)));
+        }
+        self.framing.validate(&self.dbg)?;
+        let listener = TcpListener::bind(&self.address).map_err(|err| error.pass_with(format!("Bind '{}' error", self.address), err.to_string()))?;
+        let local = listener.local_addr().map_err(|err| error.pass_with("Address error", err.to_string()))?;
+        listener.set_nonblocking(true).map_err(|err| error.pass_with("Set nonblocking error", err.to_string()))?;
+        self.exit.store(false, Ordering::SeqCst);
+        let handle = {
+            let (dbg, framing, router, cancel, streams, exit) = (
+                self.dbg.clone(), self.framing.clone(), self.router.clone(), self.cancel.clone(), self.streams.clone(), self.exit.clone(),
+            );
+            thread::spawn(move || {
+                log::info!("{}.start | Listening on {}", dbg, local);
+                let mut connections = 0;
+                while !exit.load(Ordering::SeqCst) {
+                    match listener.accept() {
+                        Ok((stream, remote)) => {
+                            log::debug!("{}.start | Accepted {}", dbg, remote);
+                            if let Err(err) = stream.set_nonblocking(false) {
+                                log::warn!("{}.start | Set blocking error: {}", dbg, err);
+                            }
+                            let n = connections;
+                            connections += 1;
+                            if let (Ok(clone), Ok(mut streams)) = (stream.try_clone(), streams.lock()) {
+                                streams.insert(n, clone);
+                            }
+                            let (dbg, framing, router, cancel, streams) = (dbg.clone(), framing.clone(), router.clone(), cancel.clone(), streams.clone());
+                            thread::spawn(move || {
+                                Self::serve(&dbg, stream, remote, framing, &router, cancel);
+                                if let Ok(mut streams) = streams.lock() {
+                                    streams.remove(&n);
+                                }
+                            });
+                        }
+                        Err(err) if err.kind() == ErrorKind::WouldBlock => thread::sleep(Self::POLL_INTERVAL),
+                        Err(err) => {
+                            log::warn!("{}.start | Accept error: {}", dbg, err);
+                            thread::sleep(Self::POLL_INTERVAL);
+                        }
+                    }
+                }
+                log::info!("{}.start | Stopped", dbg);
+            })
+        };
+        self.local = Some(local);
+        self.handle = Some(handle);
+        Ok(local)
+    }
+    ///
+    /// Stops listening and closes all the connections
+    pub fn stop(&mut self) {
+        self.exit.store(true, Ordering::SeqCst);
+        if let Ok(mut streams) = self.streams.lock() {
+            for (_, stream) in streams.drain() {
+                _ = stream.shutdown(Shutdown::Both);
+            }
+        }
+        if let Some(handle) = self.handle.take() {
+            if let Err(err) = handle.join() {
+                log::warn!("{}.stop | Thread error: {:?}", self.dbg, err);
+            }
+        }
+        self.local = None;
+    }
+    ///
+    /// Replies the queries of the single connection, until it closed
+    fn serve(dbg: &Dbg, stream: TcpStream, remote: SocketAddr, framing: ApiFraming, router: &ApiRouter, cancel: CancelRegistry) {
+        let writer = match stream.try_clone() {
+            Ok(clone) => ApiWriter::new(dbg, framing.clone(), clone),
+            Err(err) => {
+                log::warn!("{}.serve | Clone tcp stream error: {}", dbg, err);
+                return;
+            }
+        };
+        // The connection waits for the next query as long as it's open
+        let mut socket = TcpSocket::new(dbg, remote, framing.message(dbg), Some(Arc::new(stream)))
+            .with_timeout(Duration::MAX);
+        let mut session = ApiSession::new(remote, writer.clone(), cancel);
+        loop {
+            match socket.read() {
+                Ok((id, msg)) => {
+                    let query = ApiQuery::from_bytes(&msg.to_be_bytes());
+                    log::debug!("{}.serve | {} query '{}': {:?}", dbg, remote, query.id(), query.query().name());
+                    let reply = router.reply(&query, &mut session);
+                    if let Err(err) = writer.write(id.0, &reply.as_bytes()) {
+                        log::warn!("{}.serve | {} reply error: {}", dbg, remote, err);
+                        break;
+                    }
+                    if !query.keep_alive {
+                        break;
+                    }
+                }
+                Err(err) => {
+                    log::debug!("{}.serve | {} closed: {}", dbg, remote, err);
+                    break;
+                }
+            }
+        }
+        session.close();
+        writer.close();
+        log::debug!("{}.serve | {} disconnected", dbg, remote);
+    }
+}
+//
+//
+impl Drop for ApiServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -0,0 +1,79 @@
+use std::net::SocketAddr;
+use crate::server::{
+    api_server::api_writer::ApiWriter,
+    cancel::cancel_registry::CancelRegistry,
+    postgres::{pg_cursor::PgCursors, pg_listener::PgListener},
+};
+///
+/// The state of the single client connection, kept between the queries
+/// - Passed to the [ApiHandler](crate::server::api_server::api_handler::ApiHandler)'s
+/// - Cursors & listeners opened by the connection are closed with the session
+/// - `read_only` - the current query must be executed by the database in the read only mode
+pub struct ApiSession {
+    remote: SocketAddr,
+    writer: ApiWriter,
+    cancel: CancelRegistry,
+    read_only: bool,
+    pub cursors: PgCursors,
+    listeners: Vec<PgListener>,
+}
+//
+//
+impl ApiSession {
+    ///
+    /// Returns [ApiSession] new instance
+    /// - `remote` - the address of the client
+    /// - `writer` - the connection the replies & notifications are written to
+    /// - `cancel` - the running jobs, shared between all connections
+    pub fn new(remote: SocketAddr, writer: ApiWriter, cancel: CancelRegistry) -> Self {
+        Self {
+            remote,
+            writer,
+            cancel,
+            read_only: false,
+            cursors: PgCursors::default(),
+            listeners: vec![],
+        }
+    }
+    ///
+    /// Returns the address of the client
+    pub fn remote(&self) -> SocketAddr {
+        self.remote
+    }
+    ///
+    /// Returns the writer of the connection, to forward the notifications
+    pub fn writer(&self) -> ApiWriter {
+        self.writer.clone()
+    }
+    ///
+    /// Returns the running jobs, the job registered to be cancelled by the `cancel` query
+    pub fn cancel_registry(&self) -> &CancelRegistry {
+        &self.cancel
+    }
+    ///
+    /// Returns true if the current query must be executed by the database in the read only mode,
+    /// the user of the query is granted reading only
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+    ///
+    /// Sets the mode the current query executed in, by the grants of the query's user
+    pub(crate) fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+    ///
+    /// Keeps the `listener` running until the session closed
+    pub fn listen(&mut self, listener: PgListener) {
+        self.listeners.retain(|listener| listener.is_active());
+        self.listeners.push(listener);
+    }
+    ///
+    /// Stops the listeners & closes the cursors of the connection
+    pub fn close(&mut self) {
+        for listener in &mut self.listeners {
+            listener.stop();
+        }
+        self.listeners.clear();
+        self.cursors = PgCursors::default();
+    }
+}
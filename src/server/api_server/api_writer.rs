@@ -0,0 +1,55 @@
+use std::{io::Write, net::{Shutdown, TcpStream}, sync::{Arc, Mutex}};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::api::{message::api_framing::ApiFraming, reply::api_notification::ApiNotification};
+///
+/// Writes the framed messages into the client connection
+/// - Shared by the replies & the notifications of the connection,
+///   the frame is written under the lock, so the frames are never interleaved
+#[derive(Clone)]
+pub struct ApiWriter {
+    dbg: Dbg,
+    framing: ApiFraming,
+    stream: Arc<Mutex<TcpStream>>,
+}
+//
+//
+impl ApiWriter {
+    ///
+    /// Returns [ApiWriter] new instance
+    pub fn new(parent: impl Into<String>, framing: ApiFraming, stream: TcpStream) -> Self {
+        Self {
+            dbg: Dbg::new(parent, "ApiWriter"),
+            framing,
+            stream: Arc::new(Mutex::new(stream)),
+        }
+    }
+    ///
+    /// Writes the message of `id` containing `bytes`
+    pub fn write(&self, id: u32, bytes: &[u8]) -> Result<(), Error> {
+        let error = Error::new(&self.dbg, "write");
+        let frame = self.framing.frame(id, bytes);
+        match self.stream.lock() {
+            Ok(mut stream) => stream.write_all(&frame).map_err(|err| error.pass_with("Write to tcp stream error", err.to_string())),
+            Err(err) => Err(error.pass_with("Lock error", err.to_string())),
+        }
+    }
+    ///
+    /// Writes the unsolicited notification, the message `id` is 0
+    /// - Returns false if the connection is closed
+    pub fn notify(&self, notification: &ApiNotification) -> bool {
+        match self.write(0, &notification.as_bytes()) {
+            Ok(_) => true,
+            Err(err) => {
+                log::debug!("{}.notify | {}", self.dbg, err);
+                false
+            }
+        }
+    }
+    ///
+    /// Closes the connection
+    pub fn close(&self) {
+        if let Ok(stream) = self.stream.lock() {
+            _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+}
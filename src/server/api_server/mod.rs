@@ -0,0 +1,5 @@
+pub mod api_handler;
+pub mod api_router;
+pub mod api_server;
+pub mod api_session;
+pub mod api_writer;
\ No newline at end of file
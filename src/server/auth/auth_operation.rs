@@ -0,0 +1,26 @@
+use serde::{Serialize, Deserialize};
+///
+/// The operation on the service the user can be allowed by the [AuthService](crate::server::auth::auth_service::AuthService)
+/// - `read` - `sql` / `batch` / `cursor` queries only reading the data & `subscribe` on the database
+/// - `write` - `sql` / `batch` / `cursor` queries modifying the data
+/// - `execute` - running the python script / executable
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthOperation {
+    Read,
+    Write,
+    Execute,
+}
+//
+//
+impl AuthOperation {
+    ///
+    /// Returns the name of the operation
+    pub fn value(&self) -> &str {
+        match self {
+            AuthOperation::Read => "read",
+            AuthOperation::Write => "write",
+            AuthOperation::Execute => "execute",
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use std::{collections::{HashMap, HashSet}, time::Duration};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use crate::{
+    error::{api_error::ApiError, api_error_code::ApiErrorCode},
+    server::{
+        api_query::{api_query_cursor::ApiQueryCursor, api_query_login::ApiQueryLogin, api_query_type::ApiQueryType},
+        auth::{auth_operation::AuthOperation, auth_token::{AuthClaims, AuthToken}},
+        service::service_registry::ServiceRegistry,
+    },
+};
+///
+/// Issues & verifies the signed `authToken`
+/// - `login` query exchanges the service `user` / `pass` for the token valid for `ttl`
+/// - Token is signed by HMAC-SHA256 with the server `secret`, so it verified without any storage
+/// - Expired token rejected with [ApiErrorCode::TokenExpired], so the client can refresh it by `login`
+/// - If any grant is specified, the user of the token is allowed only the granted operations on the services,
+///   the rest is rejected with [ApiErrorCode::Forbidden]; no grants - everything is allowed to any valid token
+/// - The query of the user granted `read` only on the service is executed by the database in the read only mode
+/// ```
+/// let auth = AuthService::new("secret", Duration::from_secs(3600))
+///     .with_user("user", "pass")
+///     .with_grant("user", "database", &[AuthOperation::Read])
+///     .with_grant("user", "py-test", &[AuthOperation::Execute]);
+/// let token = auth.login(&query)?;
+/// let claims = auth.verify(&token.token)?;
+/// let read_only = auth.authorize(&claims.user, &query.query())?;
+/// ```
+pub struct AuthService {
+    secret: Vec<u8>,
+    ttl: Duration,
+    users: HashMap<String, String>,
+    grants: HashMap<String, HashMap<String, HashSet<AuthOperation>>>,
+}
+//
+//
+impl AuthService {
+    ///
+    /// Returns [AuthService] new instance
+    /// - `secret` - the key the tokens are signed with
+    /// - `ttl` - the time the issued token is valid for
+    pub fn new(secret: impl Into<Vec<u8>>, ttl: Duration) -> Self {
+        Self {
+            secret: secret.into(),
+            ttl,
+            users: HashMap::new(),
+            grants: HashMap::new(),
+        }
+    }
+    ///
+    /// Returns [AuthService] with the service user allowed to `login`
+    pub fn with_user(mut self, user: impl Into<String>, pass: impl Into<String>) -> Self {
+        self.users.insert(user.into(), pass.into());
+        self
+    }
+    ///
+    /// Returns [AuthService] allowing the `user` the `operations` on the `service`
+    /// - `service` - the name the query requests the service by, `*` - any service
+    pub fn with_grant(mut self, user: impl Into<String>, service: impl Into<String>, operations: &[AuthOperation]) -> Self {
+        self.grants.entry(user.into()).or_default().entry(service.into()).or_default().extend(operations);
+        self
+    }
+    ///
+    /// Returns [AuthService] with the `user` / `pass` of the configured services allowed to `login`,
+    /// each user is granted it's services only
+    /// - database - `read`, and `write` if it isn't `readOnly`
+    /// - python script / executable - `execute`
+    /// - The services without `user` / `pass` are accessible by the explicit grants only
+    pub fn with_services(mut self, registry: &ServiceRegistry) -> Self {
+        for service in registry.services() {
+            if let (Some(user), Some(pass)) = (&service.user, &service.pass) {
+                match self.users.get(user) {
+                    Some(known) if known != pass => log::warn!("AuthService.with_services | User '{}' of the service '{}' has different password, the first one is kept", user, service.key),
+                    Some(_) => {}
+                    None => self = self.with_user(user, pass),
+                }
+                let operations: &[AuthOperation] = match (service.kind.is_database(), service.read_only) {
+                    (true, true) => &[AuthOperation::Read],
+                    (true, false) => &[AuthOperation::Read, AuthOperation::Write],
+                    (false, _) => &[AuthOperation::Execute],
+                };
+                self = self.with_grant(user, &service.name, operations);
+            }
+        }
+        self
+    }
+    ///
+    /// Returns the new token if `user` / `pass` of the `query` are valid
+    pub fn login(&self, query: &ApiQueryLogin) -> Result<AuthToken, ApiError> {
+        match self.users.get(&query.user) {
+            Some(pass) if Self::equals(pass.as_bytes(), query.pass.as_bytes()) => {
+                log::debug!("AuthService.login | User '{}' logged in", query.user);
+                Ok(self.issue(&query.user))
+            }
+            _ => {
+                let details = format!("AuthService.login | Wrong user '{}' or password", query.user);
+                log::warn!("{}", details);
+                Err(ApiError::new("API Auth Service - login failed", details).with_code(ApiErrorCode::LoginFailed))
+            }
+        }
+    }
+    ///
+    /// Returns the new token issued for the `user`
+    pub fn issue(&self, user: &str) -> AuthToken {
+        let claims = AuthClaims {
+            user: user.to_owned(),
+            expires: chrono::Utc::now().timestamp() + self.ttl.as_secs() as i64,
+        };
+        // Serialization of the struct of String & i64 can't fail
+        let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&claims).unwrap_or_default());
+        let signature = URL_SAFE_NO_PAD.encode(self.mac(&payload).finalize().into_bytes());
+        AuthToken {
+            token: format!("{}.{}", payload, signature),
+            user: claims.user,
+            expires: claims.expires,
+        }
+    }
+    ///
+    /// Returns the claims of the `token` if it's signature valid and it isn't expired
+    pub fn verify(&self, token: &str) -> Result<AuthClaims, ApiError> {
+        let (payload, signature) = match token.split_once('.') {
+            Some(parts) => parts,
+            None => return Err(Self::invalid("wrong format")),
+        };
+        let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|err| Self::invalid(err))?;
+        self.mac(payload).verify_slice(&signature).map_err(|_| Self::invalid("wrong signature"))?;
+        let claims: AuthClaims = URL_SAFE_NO_PAD.decode(payload)
+            .map_err(|err| Self::invalid(err))
+            .and_then(|payload| serde_json::from_slice(&payload).map_err(|err| Self::invalid(err)))?;
+        match chrono::Utc::now().timestamp() < claims.expires {
+            true => Ok(claims),
+            false => {
+                let details = format!("AuthService.verify | Token of the user '{}' expired at {}", claims.user, claims.expires);
+                log::debug!("{}", details);
+                Err(ApiError::new("API Auth Service - authToken expired", details).with_code(ApiErrorCode::TokenExpired))
+            }
+        }
+    }
+    ///
+    /// Returns Ok if the `user` is allowed the operation the `query` requests on the service,
+    /// Ok(true) if the `query` must be executed read only, as the `user` isn't granted `write` on the service
+    /// - `cancel`, `login` & cursor `next` / `close` aren't checked, the cursor is checked when it's opened
+    pub fn authorize(&self, user: &str, query: &ApiQueryType) -> Result<bool, ApiError> {
+        if self.grants.is_empty() {
+            return Ok(false);
+        }
+        let (service, operation) = match Self::operation(query) {
+            Some(requested) => requested,
+            None => return Ok(false),
+        };
+        let granted = |operation: AuthOperation| self.grants.get(user).is_some_and(|services| {
+            [service, "*"].iter().any(|service| services.get(*service).is_some_and(|operations| operations.contains(&operation)))
+        });
+        match granted(operation) {
+            true => Ok(operation == AuthOperation::Read && !granted(AuthOperation::Write)),
+            false => {
+                let details = format!("AuthService.authorize | User '{}' isn't allowed to {} '{}'", user, operation.value(), service);
+                log::warn!("{}", details);
+                Err(ApiError::new(format!("API Auth Service - {} '{}' forbidden", operation.value(), service), details).with_code(ApiErrorCode::Forbidden))
+            }
+        }
+    }
+    ///
+    /// Returns the service & the operation requested by the `query`
+    fn operation(query: &ApiQueryType) -> Option<(&str, AuthOperation)> {
+        let access = |read_only: bool| match read_only {
+            true => AuthOperation::Read,
+            false => AuthOperation::Write,
+        };
+        match query {
+            ApiQueryType::Sql(sql) => Some((&sql.database, access(sql.is_read_only()))),
+            ApiQueryType::Batch(batch) => Some((&batch.database, access(batch.is_read_only()))),
+            ApiQueryType::Cursor(ApiQueryCursor::Open(open)) => Some((&open.query.database, access(open.query.is_read_only()))),
+            ApiQueryType::Subscribe(subscribe) => Some((&subscribe.database, AuthOperation::Read)),
+            ApiQueryType::Python(python) => Some((&python.script, AuthOperation::Execute)),
+            ApiQueryType::Executable(executable) => Some((&executable.name, AuthOperation::Execute)),
+            _ => None,
+        }
+    }
+    ///
+    /// Returns HMAC-SHA256 over the `payload`
+    fn mac(&self, payload: &str) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.secret).expect("AuthService.mac | HMAC accepts the key of any size");
+        mac.update(payload.as_bytes());
+        mac
+    }
+    ///
+    /// Compares in constant time, so the password can't be guessed by the response time
+    fn equals(a: &[u8], b: &[u8]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+    }
+    ///
+    ///
+    fn invalid(err: impl std::fmt::Display) -> ApiError {
+        let details = format!("AuthService.verify | Invalid token: {}", err);
+        log::warn!("{}", details);
+        ApiError::new("API Auth Service - invalid authToken", details).with_code(ApiErrorCode::TokenInvalid)
+    }
+}
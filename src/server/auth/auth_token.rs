@@ -0,0 +1,22 @@
+use serde::{Serialize, Deserialize};
+///
+/// The token issued by the `login` query, sent to the client as the single row of the reply
+/// {"token": "signed token", "user": "service user", "expires": 1760000000}
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuthToken {
+    pub token: String,
+    pub user: String,
+    /// Unix timestamp, seconds
+    pub expires: i64,
+}
+///
+/// The payload of the signed token
+/// - Token format: `base64url(json claims).base64url(HMAC-SHA256 of the first part)`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuthClaims {
+    #[serde(rename = "sub")]
+    pub user: String,
+    /// Unix timestamp, seconds
+    #[serde(rename = "exp")]
+    pub expires: i64,
+}
@@ -0,0 +1,3 @@
+pub mod auth_operation;
+pub mod auth_service;
+pub mod auth_token;
\ No newline at end of file
@@ -0,0 +1,121 @@
+use std::{collections::HashMap, sync::{atomic::{AtomicUsize, Ordering}, Arc, Mutex}};
+use crate::error::api_error::ApiError;
+///
+/// Action cancelling the running job
+pub type Cancel = Box<dyn FnOnce() -> Result<(), String> + Send>;
+///
+/// The jobs currently running on the server, which can be cancelled by the request id
+/// - Shared between all connections, because the cancel request comes over the separate connection,
+///   while the connection of the cancelled request is busy
+/// - The job is identified by the `authToken` & `id` of the request,
+///   the `ApiRequest` issues the ids unique for each client (`<random session>-<counter>`),
+///   so the client can cancel only its own requests, even if the `authToken` is shared or empty
+#[derive(Clone, Default)]
+pub struct CancelRegistry {
+    jobs: Arc<Mutex<HashMap<String, (usize, Cancel)>>>,
+    last: Arc<AtomicUsize>,
+}
+//
+//
+impl CancelRegistry {
+    ///
+    /// Registers the `cancel` action of the job running for the request
+    /// - Job is unregistered when returned [CancelGuard] dropped
+    pub fn register(&self, auth_token: &str, id: &str, cancel: Cancel) -> CancelGuard {
+        let key = Self::key(auth_token, id);
+        let n = self.last.fetch_add(1, Ordering::SeqCst) + 1;
+        match self.jobs.lock() {
+            Ok(mut jobs) => {
+                jobs.insert(key.clone(), (n, cancel));
+            }
+            Err(err) => log::warn!("CancelRegistry.register | Lock error: {:?}", err),
+        }
+        CancelGuard { jobs: self.jobs.clone(), key, n }
+    }
+    ///
+    /// Cancels the job running for the request
+    /// - Returns false if the job isn't running (not started or already finished)
+    pub fn cancel(&self, auth_token: &str, id: &str) -> Result<bool, ApiError> {
+        let job = match self.jobs.lock() {
+            Ok(mut jobs) => jobs.remove(&Self::key(auth_token, id)),
+            Err(err) => {
+                let details = format!("CancelRegistry.cancel | Lock error: {:?}", err);
+                log::warn!("{}", details);
+                return Err(ApiError::new("API Service - cancel error", details));
+            }
+        };
+        match job {
+            Some((_, cancel)) => match cancel() {
+                Ok(_) => {
+                    log::debug!("CancelRegistry.cancel | Request '{}' cancelled", id);
+                    Ok(true)
+                }
+                Err(err) => {
+                    let details = format!("CancelRegistry.cancel | Request '{}' cancel error: {}", id, err);
+                    log::warn!("{}", details);
+                    Err(ApiError::new(format!("API Service - unable to cancel request '{}'", id), details))
+                }
+            },
+            None => Ok(false),
+        }
+    }
+    ///
+    /// Returns [Cancel] action, cancelling the statement running by the postgres connection
+    pub fn postgres(token: ::postgres::CancelToken) -> Cancel {
+        Box::new(move || {
+            token.cancel_query(::postgres::NoTls).map_err(|err| err.to_string())
+        })
+    }
+    ///
+    /// Returns [Cancel] action, killing the process and all its children
+    /// - Process must be spawned as the leader of the new process group (`CommandExt::process_group(0)`)
+    #[cfg(unix)]
+    pub fn process(pid: u32) -> Cancel {
+        Box::new(move || {
+            let pid = pid as libc::pid_t;
+            // Negative pid - the whole process group
+            match unsafe { libc::kill(-pid, libc::SIGKILL) } {
+                0 => Ok(()),
+                _ => match unsafe { libc::kill(pid, libc::SIGKILL) } {
+                    0 => Ok(()),
+                    _ => Err(format!("Kill process {} error: {}", pid, std::io::Error::last_os_error())),
+                },
+            }
+        })
+    }
+    ///
+    /// Returns [Cancel] action, killing the process and all its children
+    #[cfg(windows)]
+    pub fn process(pid: u32) -> Cancel {
+        Box::new(move || {
+            match std::process::Command::new("taskkill").args(["/T", "/F", "/PID", &pid.to_string()]).output() {
+                Ok(output) if output.status.success() => Ok(()),
+                Ok(output) => Err(format!("Kill process {} error: {}", pid, String::from_utf8_lossy(&output.stderr))),
+                Err(err) => Err(format!("Kill process {} error: {}", pid, err)),
+            }
+        })
+    }
+    ///
+    ///
+    fn key(auth_token: &str, id: &str) -> String {
+        format!("{}\u{0}{}", auth_token, id)
+    }
+}
+///
+/// Keeps the job registered in the [CancelRegistry] while running
+pub struct CancelGuard {
+    jobs: Arc<Mutex<HashMap<String, (usize, Cancel)>>>,
+    key: String,
+    n: usize,
+}
+//
+//
+impl Drop for CancelGuard {
+    fn drop(&mut self) {
+        if let Ok(mut jobs) = self.jobs.lock() {
+            if jobs.get(&self.key).is_some_and(|(n, _)| *n == self.n) {
+                jobs.remove(&self.key);
+            }
+        }
+    }
+}
@@ -0,0 +1 @@
+pub mod cancel_registry;
@@ -0,0 +1,216 @@
+use std::{collections::HashMap, fs::File, io::Read, path::Path};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use crate::server::config::{config_error::ConfigError, service_config::ServiceConfig};
+///
+/// The API server configuration, the `config.yaml`
+/// ```yaml
+/// address: '127.0.0.1:8899'
+/// services:
+///     - database-postgres:
+///         name: 'db_postgres_test'
+///         type: postgres
+///         path: '127.0.0.1:5432'
+///         user: db_postgres_test
+///         pass: db_postgres_test
+/// ```
+/// - All the problems found are returned at once, each with the line & the field
+/// - Detected: invalid yaml, unknown service type or field, duplicate keys or names,
+///   missing name or path, unreadable sqlite database, script or executable file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiConfig {
+    pub address: String,
+    pub services: Vec<ServiceConfig>,
+}
+//
+//
+impl ApiConfig {
+    ///
+    /// Returns [ApiConfig] loaded from the yaml file
+    /// - Relative file paths of the services resolved against the folder of the file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Vec<ConfigError>> {
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(yaml) => Self::from_yaml(&yaml, path.parent().unwrap_or(Path::new(""))),
+            Err(err) => Err(vec![ConfigError::new(None, "", format!("Can't read '{}': {}", path.display(), err))]),
+        }
+    }
+    ///
+    /// Returns [ApiConfig] parsed from the `yaml`
+    /// - `root` - the folder, relative file paths of the services resolved against
+    pub fn from_yaml(yaml: &str, root: impl AsRef<Path>) -> Result<Self, Vec<ConfigError>> {
+        let raw: ApiConfigRaw = serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(yaml)).map_err(|err| {
+            let field = err.path().to_string();
+            let line = err.inner().location().map(|location| location.line());
+            vec![ConfigError::new(line, field, err.into_inner().to_string())]
+        })?;
+        let lines = YamlLines::new(yaml);
+        let mut errors = vec![];
+        if raw.address.trim().is_empty() {
+            let line = lines.find(0, None, "address").map(|(line, _)| line + 1);
+            errors.push(ConfigError::new(line, "address", "Address is not specified"));
+        }
+        let mut services = vec![];
+        let mut from = 0;
+        for (index, entry) in raw.services.into_iter().enumerate() {
+            if entry.len() != 1 {
+                errors.push(ConfigError::new(None, format!("services[{}]", index), format!("Expected single service per entry, found {}", entry.len())));
+                continue;
+            }
+            for (key, mut service) in entry {
+                // Services are declared in the order of the list
+                let anchor = lines.find(from, None, &key);
+                if let Some((line, _)) = anchor {
+                    from = line + 1;
+                }
+                service.key = key;
+                let field = ServiceField { lines: &lines, anchor, prefix: format!("services[{}].{}", index, service.key) };
+                errors.extend(Self::validate(&field, &mut service, root.as_ref()));
+                services.push((field, service));
+            }
+        }
+        errors.extend(Self::duplicates(&services));
+        match errors.is_empty() {
+            true => Ok(Self { address: raw.address, services: services.into_iter().map(|(_, service)| service).collect() }),
+            false => {
+                errors.sort_by_key(|err| err.line);
+                Err(errors)
+            }
+        }
+    }
+    ///
+    /// Returns the problems of the single service, resolves it's file path
+    fn validate(field: &ServiceField, service: &mut ServiceConfig, root: &Path) -> Vec<ConfigError> {
+        let mut errors = vec![];
+        if service.name.trim().is_empty() {
+            errors.push(field.error("name", format!("Name of the service '{}' is not specified", service.key)));
+        }
+        if service.timeout == Some(0) {
+            errors.push(field.error("timeout", format!("Timeout of the service '{}' must be greater than zero", service.key)));
+        }
+        if service.output_limit == Some(0) {
+            errors.push(field.error("outputLimit", format!("Output limit of the service '{}' must be greater than zero", service.key)));
+        }
+        let limits = [("cpu", service.limits.cpu), ("memory", service.limits.memory), ("files", service.limits.files), ("processes", service.limits.processes)];
+        for (name, _) in limits.iter().filter(|(_, limit)| *limit == Some(0)) {
+            errors.push(field.error(&format!("limits.{}", name), format!("Limit '{}' of the service '{}' must be greater than zero", name, service.key)));
+        }
+        if service.kind.is_database() && !service.limits.is_empty() {
+            errors.push(field.error("limits", format!("Limits of the service '{}' are applied to the scripts & executables only", service.key)));
+        }
+        if !service.kind.is_database() && service.replace_null_with_default.is_some() {
+            errors.push(field.error("replaceNullWithDefault", format!("Null defaults of the service '{}' are applied to the databases only", service.key)));
+        }
+        if service.path.trim().is_empty() {
+            errors.push(field.error("path", format!("Path of the service '{}' is not specified", service.key)));
+            return errors;
+        }
+        if let Some(work_dir) = &service.work_dir {
+            let path = root.join(work_dir);
+            if !path.is_dir() {
+                errors.push(field.error("workDir", format!("Working directory '{}' of the service '{}' not found", path.display(), service.key)));
+            }
+            service.work_dir = Some(path.to_string_lossy().into_owned());
+        }
+        if service.kind.is_file() {
+            let path = root.join(&service.path);
+            service.path = path.to_string_lossy().into_owned();
+            // Directory is opened, but can't be read
+            if let Err(err) = File::open(&path).and_then(|mut file| file.read(&mut [0; 1])) {
+                errors.push(field.error("path", format!("Can't read '{}' of the service '{}': {}", path.display(), service.key, err)));
+            }
+        }
+        errors
+    }
+    ///
+    /// Returns the duplicate keys of the services & names of the services of the same kind
+    /// - Names of databases, scripts & executables are separate, they are requested by the different queries
+    fn duplicates(services: &[(ServiceField, ServiceConfig)]) -> Vec<ConfigError> {
+        let mut errors = vec![];
+        let mut keys = HashMap::new();
+        let mut names = HashMap::new();
+        for (index, (field, service)) in services.iter().enumerate() {
+            if let Some(first) = keys.insert(service.key.as_str(), index) {
+                errors.push(field.error("", format!("Duplicate service '{}', first declared in services[{}]", service.key, first)));
+            }
+            let group = (!service.kind.is_database()).then_some(service.kind);
+            if !service.name.is_empty() {
+                if let Some(first) = names.insert((group, service.name.as_str()), index) {
+                    errors.push(field.error("name", format!("Duplicate name '{}', already used by services[{}]", service.name, first)));
+                }
+            }
+        }
+        errors
+    }
+}
+///
+/// Locates the fields of the single service in the source yaml
+struct ServiceField<'a> {
+    lines: &'a YamlLines<'a>,
+    /// Index & indent of the line declaring the service
+    anchor: Option<(usize, usize)>,
+    prefix: String,
+}
+//
+//
+impl ServiceField<'_> {
+    ///
+    /// Returns [ConfigError] of the `name` field of the service, empty `name` - of the service itself
+    /// - Missing field is reported at the line of the service
+    /// - Nested `name` is located by it's last key, `limits.cpu` - by `cpu`
+    fn error(&self, name: &str, message: String) -> ConfigError {
+        let line = self.anchor.map(|(line, indent)| {
+            match name.rsplit('.').next().filter(|key| !key.is_empty()) {
+                None => line,
+                Some(key) => self.lines.find(line + 1, Some(indent), key).map_or(line, |(line, _)| line),
+            }
+        });
+        let field = match name.is_empty() {
+            true => self.prefix.clone(),
+            false => format!("{}.{}", self.prefix, name),
+        };
+        ConfigError::new(line.map(|line| line + 1), field, message)
+    }
+}
+///
+/// Lines of the yaml, to locate the keys declared
+struct YamlLines<'a> {
+    lines: Vec<&'a str>,
+}
+//
+//
+impl<'a> YamlLines<'a> {
+    fn new(yaml: &'a str) -> Self {
+        Self { lines: yaml.lines().collect() }
+    }
+    ///
+    /// Returns the index & the indent of the line declaring the `key`, starting from `from`
+    /// - The search stops at the end of the block, indented more than `indent`
+    fn find(&self, from: usize, indent: Option<usize>, key: &str) -> Option<(usize, usize)> {
+        for (index, line) in self.lines.iter().enumerate().skip(from) {
+            let content = line.trim_start();
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+            let line_indent = line.len() - content.len();
+            if indent.is_some_and(|indent| line_indent <= indent) {
+                return None;
+            }
+            let content = content.trim_start_matches("- ").trim_start_matches(['\'', '"']);
+            if content.strip_prefix(key).is_some_and(|rest| rest.trim_start_matches(['\'', '"']).starts_with(':')) {
+                return Some((index, line_indent));
+            }
+        }
+        None
+    }
+}
+///
+/// The `config.yaml` as is, services are the list of single key maps
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ApiConfigRaw {
+    #[serde(default)]
+    address: String,
+    #[serde(default)]
+    services: Vec<IndexMap<String, ServiceConfig>>,
+}
@@ -0,0 +1,29 @@
+///
+/// The problem found in the `config.yaml`
+/// - `line` - 1-based line of the `field`, None if it can't be located
+/// - `field` - the path of the field, like `services[1].py-test-script.path`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigError {
+    pub line: Option<usize>,
+    pub field: String,
+    pub message: String,
+}
+//
+//
+impl ConfigError {
+    ///
+    /// Returns [ConfigError] new instance
+    pub fn new(line: Option<usize>, field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { line, field: field.into(), message: message.into() }
+    }
+}
+//
+//
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}, field '{}': {}", line, self.field, self.message),
+            None => write!(f, "field '{}': {}", self.field, self.message),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod api_config;
+pub mod config_error;
+pub mod null_defaults;
+pub mod params_mode;
+pub mod service_config;
+pub mod service_limits;
+pub mod service_type;
\ No newline at end of file
@@ -0,0 +1,78 @@
+use std::collections::{BTreeMap, HashMap};
+use serde::Deserialize;
+use crate::server::api_query::{column_kind::ColumnKind, row_map::RowMap};
+///
+/// The values the nulls of the SQL results are replaced with, `replaceNullWithDefault` of the service
+/// - numbers - `0`, decimals - `"0"`, text - `""`, booleans - `false`,
+///   timestamps - `timestamp`, timestamps with the time zone - `timestamptz`
+/// - the values of the other types are kept null
+/// - the value of the column in `columns` has priority over the default of it's type
+/// ```yaml
+/// replaceNullWithDefault:                 # enabled with the defaults, same as `true`
+/// replaceNullWithDefault:
+///   timestamp: '1970-01-01T00:00:00'      # the default of the timestamp columns
+///   timestamptz: '1970-01-01T00:00:00Z'   # the default of the timestamp with the time zone columns
+///   columns:                              # the defaults of the columns by the name
+///     price: -1
+///     comment: 'n/a'
+/// ```
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct NullDefaults {
+    #[serde(default = "NullDefaults::timestamp")]
+    pub timestamp: serde_json::Value,
+    #[serde(default = "NullDefaults::timestamptz")]
+    pub timestamptz: serde_json::Value,
+    #[serde(default)]
+    pub columns: BTreeMap<String, serde_json::Value>,
+}
+//
+//
+impl NullDefaults {
+    ///
+    /// The default of the timestamp columns
+    fn timestamp() -> serde_json::Value {
+        serde_json::Value::from("1970-01-01T00:00:00")
+    }
+    ///
+    /// The default of the timestamp with the time zone columns
+    fn timestamptz() -> serde_json::Value {
+        serde_json::Value::from("1970-01-01T00:00:00Z")
+    }
+    ///
+    /// Returns the value the null of the column `name` of the `kind` is replaced with
+    pub fn value(&self, name: &str, kind: ColumnKind) -> Option<serde_json::Value> {
+        match self.columns.get(name) {
+            Some(value) => Some(value.clone()),
+            None => match kind {
+                ColumnKind::Number => Some(serde_json::Value::from(0)),
+                ColumnKind::Decimal => Some(serde_json::Value::from("0")),
+                ColumnKind::Text => Some(serde_json::Value::from("")),
+                ColumnKind::Bool => Some(serde_json::Value::Bool(false)),
+                ColumnKind::Timestamp => Some(self.timestamp.clone()),
+                ColumnKind::TimestampTz => Some(self.timestamptz.clone()),
+                ColumnKind::Other => None,
+            },
+        }
+    }
+    ///
+    /// Replaces the nulls of the `row`
+    /// - `columns` - the names & the kinds of the columns of the `row`,
+    ///   the last one is taken of the columns with the same name, as the [RowMap] keeps the last value
+    pub fn apply<'a>(&self, row: &mut RowMap, columns: impl IntoIterator<Item = (&'a str, ColumnKind)>) {
+        let kinds: HashMap<&str, ColumnKind> = columns.into_iter().collect();
+        for (name, value) in row.iter_mut().filter(|(_, value)| value.is_null()) {
+            let kind = kinds.get(name.as_str()).copied().unwrap_or(ColumnKind::Other);
+            if let Some(default) = self.value(name, kind) {
+                *value = default;
+            }
+        }
+    }
+}
+//
+//
+impl Default for NullDefaults {
+    fn default() -> Self {
+        Self { timestamp: Self::timestamp(), timestamptz: Self::timestamptz(), columns: BTreeMap::new() }
+    }
+}
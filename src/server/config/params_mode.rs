@@ -0,0 +1,13 @@
+use serde::{Serialize, Deserialize};
+///
+/// How the `params` of the query are passed to the executable
+/// - `stdin` - written to the stdin as json object
+/// - `args` - passed as the command line arguments `--name=value`,
+///   strings as is, other values as json
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamsMode {
+    #[default]
+    Stdin,
+    Args,
+}
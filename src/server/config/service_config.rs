@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Deserializer};
+use crate::server::config::{null_defaults::NullDefaults, params_mode::ParamsMode, service_limits::ServiceLimits, service_type::ServiceType};
+///
+/// The single entry of the `services` list of the `config.yaml`
+/// ```yaml
+/// - py-test-script:           # key - internal unique API service name
+///     name: 'py-test'         # the name, the service is requested by
+///     type: python            # sqlite / postgres / mysql / python / bin
+///     path: 'extensions/scripts/script_tamplate.py'
+///     user: root
+///     pass: root
+///     readOnly: true          # the database is opened for reading only
+///     interpreter: python3    # the interpreter of the python script
+///     timeout: 30             # seconds, the script / executable is killed when exceeded
+///     outputLimit: 1048576    # bytes, max output of the script / executable
+///     params: args            # stdin / args, how the params are passed to the executable
+///     env:                    # the environment of the executable
+///       LANG: C.UTF-8
+///     inheritEnv: false       # the environment of the server is cleared by default
+///     workDir: 'extensions'   # the working directory of the executable
+///     limits:                 # the resource limits of the script / executable, see [ServiceLimits]
+///       cpu: 10
+///     replaceNullWithDefault: # replace null values with the defaults of the column type, see [NullDefaults]
+/// ```
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ServiceConfig {
+    #[serde(skip)]
+    pub key: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: ServiceType,
+    /// Database file / host:port, script or executable file,
+    /// relative file path is resolved against the folder of the `config.yaml`
+    #[serde(default)]
+    pub path: String,
+    #[serde(default)]
+    pub user: Option<String>,
+    #[serde(default)]
+    pub pass: Option<String>,
+    /// The database is opened for reading only
+    #[serde(rename = "readOnly", default)]
+    pub read_only: bool,
+    /// The interpreter of the python script, `python3` by default
+    #[serde(default)]
+    pub interpreter: Option<String>,
+    /// Max run time of the script / executable, seconds
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    /// Max size of the stdout / stderr of the script / executable, bytes
+    #[serde(rename = "outputLimit", default)]
+    pub output_limit: Option<usize>,
+    /// How the `params` are passed to the executable
+    #[serde(default)]
+    pub params: ParamsMode,
+    /// The environment variables of the executable
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// The executable inherits the environment of the server, cleared otherwise
+    #[serde(rename = "inheritEnv", default)]
+    pub inherit_env: bool,
+    /// The working directory of the executable, the folder of the executable by default,
+    /// relative path is resolved against the folder of the `config.yaml`
+    #[serde(rename = "workDir", default)]
+    pub work_dir: Option<String>,
+    /// The resource limits & the sandbox of the script / executable
+    #[serde(default)]
+    pub limits: ServiceLimits,
+    /// The nulls of the SQL results are replaced, if specified
+    #[serde(rename = "replaceNullWithDefault", default, deserialize_with = "ServiceConfig::null_defaults")]
+    pub replace_null_with_default: Option<NullDefaults>,
+}
+//
+//
+impl ServiceConfig {
+    ///
+    /// The option specified without value or `true` is enabled with the defaults, `false` - disabled
+    fn null_defaults<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<NullDefaults>, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Replace {
+            Flag(Option<bool>),
+            Defaults(NullDefaults),
+        }
+        Ok(match Replace::deserialize(deserializer)? {
+            Replace::Flag(Some(false)) => None,
+            Replace::Flag(_) => Some(NullDefaults::default()),
+            Replace::Defaults(defaults) => Some(defaults),
+        })
+    }
+}
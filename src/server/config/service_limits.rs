@@ -0,0 +1,43 @@
+use serde::Deserialize;
+///
+/// The limits & the sandbox of the python script / executable process, Linux only
+/// ```yaml
+/// limits:
+///   cpu: 10                   # CPU time, seconds
+///   memory: 536870912         # address space, bytes
+///   files: 64                 # open files
+///   processes: 32             # processes of the user, not applied to root
+///   noNewPrivileges: true     # the process can't gain the privileges by setuid / file capabilities
+///   uid: 65534                # the user & the group the process run as
+///   gid: 65534
+///   readOnlyWorkDir: true     # the working directory is mounted read only, the server must run as root
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ServiceLimits {
+    #[serde(default)]
+    pub cpu: Option<u64>,
+    #[serde(default)]
+    pub memory: Option<u64>,
+    #[serde(default)]
+    pub files: Option<u64>,
+    #[serde(default)]
+    pub processes: Option<u64>,
+    #[serde(rename = "noNewPrivileges", default)]
+    pub no_new_privileges: bool,
+    #[serde(default)]
+    pub uid: Option<u32>,
+    #[serde(default)]
+    pub gid: Option<u32>,
+    #[serde(rename = "readOnlyWorkDir", default)]
+    pub read_only_work_dir: bool,
+}
+//
+//
+impl ServiceLimits {
+    ///
+    /// Returns true if nothing is limited
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
@@ -0,0 +1,30 @@
+use serde::{Serialize, Deserialize};
+///
+/// The type of the API service in the `config.yaml`
+/// - `sqlite` / `postgres` / `mysql` - database, replies the `sql` queries
+/// - `python` - script, replies the `python` queries
+/// - `bin` - executable, replies the `executable` queries
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceType {
+    Sqlite,
+    Postgres,
+    Mysql,
+    Python,
+    #[serde(rename = "bin")]
+    Executable,
+}
+//
+//
+impl ServiceType {
+    ///
+    /// Returns true if the service is the database
+    pub fn is_database(&self) -> bool {
+        matches!(self, ServiceType::Sqlite | ServiceType::Postgres | ServiceType::Mysql)
+    }
+    ///
+    /// Returns true if the `path` of the service is the local file
+    pub fn is_file(&self) -> bool {
+        matches!(self, ServiceType::Sqlite | ServiceType::Python | ServiceType::Executable)
+    }
+}
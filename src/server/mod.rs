@@ -1 +1,11 @@
-pub mod api_query;
\ No newline at end of file
+pub mod api_query;
+pub mod api_server;
+pub mod auth;
+pub mod cancel;
+pub mod config;
+#[cfg(feature = "mysql")]
+pub mod mysql;
+pub mod postgres;
+pub mod service;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
\ No newline at end of file
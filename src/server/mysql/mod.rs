@@ -0,0 +1,2 @@
+pub mod mysql_service;
+pub mod mysql_value;
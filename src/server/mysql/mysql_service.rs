@@ -0,0 +1,164 @@
+use std::{sync::Mutex, time::Duration};
+use mysql::{prelude::Queryable, AccessMode, Conn, Opts, OptsBuilder, Params, Row, TxOpts, Value};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    error::api_error::ApiError,
+    server::{
+        api_query::{api_query::ApiQuery, api_query_sql::ApiQuerySql, row_map::RowMap},
+        api_server::api_session::ApiSession,
+        config::{null_defaults::NullDefaults, service_config::ServiceConfig},
+        mysql::mysql_value::MySqlValue,
+        service::sql_service::SqlService,
+    },
+};
+///
+/// The mysql / mariadb database service
+/// - Connects with the credentials of the [ServiceConfig]:
+///   `path` - `host:port` or `mysql://` url, `name` - the database, `user` / `pass`
+/// - The connections of the completed queries are kept for the next ones
+/// - Placeholders: `?` with the positional params, `:name` with the named ones
+/// - The running statement is killed by the `cancel` query, over the separate connection
+/// - `sql` is executed in the read only transaction, if the service is `readOnly`
+///   or the user of the query is granted reading only
+/// - Column values converted into json by [MySqlValue], the order of the columns is kept,
+///   the nulls are replaced by [NullDefaults] if `replaceNullWithDefault` specified
+pub struct MySqlService {
+    dbg: Dbg,
+    opts: Opts,
+    conns: Mutex<Vec<Conn>>,
+    nulls: Option<NullDefaults>,
+    read_only: bool,
+}
+//
+//
+impl MySqlService {
+    ///
+    /// Connection timeout
+    const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+    ///
+    /// Max number of the idle connections kept
+    const IDLE_CONNS: usize = 8;
+    ///
+    /// The server error of the killed query, `ER_QUERY_INTERRUPTED`
+    const QUERY_INTERRUPTED: u16 = 1317;
+    ///
+    /// Returns [MySqlService] of the configured database
+    pub fn new(conf: &ServiceConfig) -> Result<Self, Error> {
+        let dbg = Dbg::own(format!("MySqlService({})", conf.name));
+        let error = Error::new(&dbg, "new");
+        let opts = match conf.path.contains("://") {
+            true => OptsBuilder::from_opts(Opts::from_url(&conf.path).map_err(|err| error.pass_with(format!("Invalid url '{}'", conf.path), err.to_string()))?),
+            false => {
+                let (host, port) = match conf.path.rsplit_once(':') {
+                    Some((host, port)) => (host, port.parse::<u16>().map_err(|err| error.pass_with(format!("Invalid port in '{}'", conf.path), err.to_string()))?),
+                    None => (conf.path.as_str(), 3306),
+                };
+                OptsBuilder::new().ip_or_hostname(Some(host)).tcp_port(port)
+            }
+        };
+        let mut opts = opts.db_name(Some(&conf.name)).tcp_connect_timeout(Some(Self::CONNECT_TIMEOUT));
+        if let Some(user) = &conf.user {
+            opts = opts.user(Some(user));
+        }
+        if let Some(pass) = &conf.pass {
+            opts = opts.pass(Some(pass));
+        }
+        Ok(Self { dbg, opts: opts.into(), conns: Mutex::new(vec![]), nulls: conf.replace_null_with_default.clone(), read_only: conf.read_only })
+    }
+    ///
+    /// Returns the new connection
+    fn connect(&self) -> Result<Conn, ApiError> {
+        Conn::new(self.opts.clone()).map_err(|err| {
+            let details = format!("{}.connect | Connection error: {}", self.dbg, err);
+            log::warn!("{}", details);
+            ApiError::new("API SQL Service - database connection error", details)
+        })
+    }
+    ///
+    /// Returns the idle connection, or the new one
+    fn take(&self) -> Result<Conn, ApiError> {
+        let conn = self.conns.lock().ok().and_then(|mut conns| conns.pop());
+        match conn {
+            Some(mut conn) => match conn.ping() {
+                Ok(_) => Ok(conn),
+                Err(_) => self.connect(),
+            },
+            None => self.connect(),
+        }
+    }
+    ///
+    /// Returns true if the query of the `session` must be executed read only
+    fn read_only(&self, session: &ApiSession) -> bool {
+        self.read_only || session.is_read_only()
+    }
+    ///
+    /// Returns the rows of the `statement`, executed in the read only transaction if `read_only`
+    fn query(conn: &mut Conn, read_only: bool, statement: &str, params: Params) -> Result<Vec<Row>, mysql::Error> {
+        match read_only {
+            true => {
+                let mut transaction = conn.start_transaction(TxOpts::default().set_access_mode(Some(AccessMode::ReadOnly)))?;
+                let rows = transaction.exec_iter(statement, params)?.collect::<Result<Vec<Row>, _>>()?;
+                transaction.commit()?;
+                Ok(rows)
+            }
+            false => conn.exec_iter(statement, params).and_then(|result| result.collect()),
+        }
+    }
+    ///
+    /// Keeps the `conn` for the next query
+    /// - The session state left by the query, the open transaction, the variables, `sql_mode`, ...,
+    ///   is reset by `COM_RESET_CONNECTION`, the connection failed to be reset is dropped
+    fn give(&self, mut conn: Conn) {
+        if let Ok(mut conns) = self.conns.lock() {
+            if conns.len() < Self::IDLE_CONNS {
+                match conn.reset() {
+                    Ok(_) => conns.push(conn),
+                    Err(err) => log::warn!("{}.give | Unable to reset the connection, dropped: {}", self.dbg, err),
+                }
+            }
+        }
+    }
+    ///
+    /// Returns [ApiError] of the failed query, with the details of the database error
+    fn error(&self, method: &str, err: mysql::Error) -> ApiError {
+        let message = match &err {
+            mysql::Error::MySqlError(db) if db.code == Self::QUERY_INTERRUPTED => "API SQL Service - query cancelled",
+            _ => "API SQL Service - query error",
+        };
+        let details = format!("{}.{} | {}", self.dbg, method, err);
+        log::warn!("{}", details);
+        ApiError::new(message, details)
+    }
+}
+//
+//
+impl SqlService for MySqlService {
+    fn sql(&self, query: &ApiQuery, sql: &ApiQuerySql, session: &mut ApiSession) -> Result<Vec<RowMap>, ApiError> {
+        let (statement, params) = sql.bind_anonymous()?;
+        let params = match params.is_empty() {
+            true => Params::Empty,
+            false => Params::Positional(params.iter().map(Value::from).collect()),
+        };
+        let read_only = self.read_only(session);
+        let mut conn = self.take()?;
+        let rows = {
+            let opts = self.opts.clone();
+            let id = conn.connection_id();
+            let _job = session.cancel_registry().register(&query.auth_token(), &query.id(), Box::new(move || {
+                Conn::new(opts)
+                    .and_then(|mut conn| conn.query_drop(format!("KILL QUERY {}", id)))
+                    .map_err(|err| err.to_string())
+            }));
+            Self::query(&mut conn, read_only, &statement, params).map_err(|err| self.error("sql", err))
+        };
+        self.give(conn);
+        rows?.iter().map(|row| {
+            let mut row_map = MySqlValue::row(row)?;
+            if let Some(nulls) = &self.nulls {
+                let columns: Vec<_> = row.columns_ref().iter().map(|column| (column.name_str(), MySqlValue::kind(column))).collect();
+                nulls.apply(&mut row_map, columns.iter().map(|(name, kind)| (name.as_ref(), *kind)));
+            }
+            Ok(row_map)
+        }).collect()
+    }
+}
@@ -0,0 +1,128 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{NaiveDate, NaiveTime};
+use mysql::{consts::ColumnType, Column, Row, Value};
+use crate::{error::api_error::ApiError, server::api_query::{api_query_sql_params::SqlParam, column_kind::ColumnKind, row_map::RowMap}};
+///
+/// The value of the mysql / mariadb column converted into json, the same way as the postgres one
+/// - integer types / `year` - number, `tinyint(1)` (`boolean`) - boolean
+/// - `float` / `double` - number, `NaN` - null
+/// - `decimal` - string with the exact decimal value, `123.450` for `decimal(6, 3)`, as the postgres `numeric`
+/// - `char` / `varchar` / `text` / `enum` / `set` - string
+/// - `json` - json as is, mariadb stores json as `longtext`, so it's returned as string
+/// - `datetime` / `timestamp` - `2024-01-31T12:00:00.123456`, `date` - `2024-01-31`, `time` - `12:00:00.123456`,
+///   the zero dates `0000-00-00` - null
+/// - `bit` - number
+/// - `binary` / `varbinary` / `blob` - base64 string
+#[derive(Debug, Clone, PartialEq)]
+pub struct MySqlValue(pub serde_json::Value);
+//
+//
+impl MySqlValue {
+    ///
+    /// The character set of the binary strings
+    const BINARY_CHARSET: u16 = 63;
+    ///
+    /// Returns the `value` of the `column`
+    pub fn new(value: &Value, column: &Column) -> Result<Self, String> {
+        let value = match value {
+            Value::NULL => serde_json::Value::Null,
+            Value::Int(value) => match (column.column_type(), column.column_length()) {
+                (ColumnType::MYSQL_TYPE_TINY, 1) => serde_json::Value::Bool(*value != 0),
+                _ => serde_json::Value::from(*value),
+            },
+            Value::UInt(value) => serde_json::Value::from(*value),
+            Value::Float(value) => Self::number(*value as f64),
+            Value::Double(value) => Self::number(*value),
+            Value::Bytes(value) => match column.column_type() {
+                ColumnType::MYSQL_TYPE_JSON => serde_json::from_slice(value).map_err(|err| format!("invalid json: {}", err))?,
+                ColumnType::MYSQL_TYPE_DECIMAL | ColumnType::MYSQL_TYPE_NEWDECIMAL => {
+                    let value = std::str::from_utf8(value).map_err(|err| format!("invalid decimal: {}", err))?;
+                    let digits = value.strip_prefix('-').unwrap_or(value);
+                    let (int, fraction) = digits.split_once('.').unwrap_or((digits, "0"));
+                    match [int, fraction].iter().all(|part| !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit())) {
+                        true => serde_json::Value::String(value.to_owned()),
+                        false => return Err(format!("invalid decimal '{}'", value)),
+                    }
+                }
+                ColumnType::MYSQL_TYPE_BIT => serde_json::Value::from(value.iter().fold(0u64, |bits, byte| (bits << 8) | *byte as u64)),
+                _ if column.character_set() == Self::BINARY_CHARSET => serde_json::Value::String(STANDARD.encode(value)),
+                _ => serde_json::Value::String(std::str::from_utf8(value).map_err(|err| format!("invalid utf8 text: {}", err))?.to_owned()),
+            },
+            Value::Date(year, month, day, hour, minute, second, micros) => {
+                let date = NaiveDate::from_ymd_opt(*year as i32, *month as u32, *day as u32);
+                let time = NaiveTime::from_hms_micro_opt(*hour as u32, *minute as u32, *second as u32, *micros);
+                match (date, time, column.column_type()) {
+                    (Some(date), _, ColumnType::MYSQL_TYPE_DATE) => serde_json::Value::String(date.format("%Y-%m-%d").to_string()),
+                    (Some(date), Some(time), _) => serde_json::Value::String(date.and_time(time).format("%Y-%m-%dT%H:%M:%S%.f").to_string()),
+                    // Zero date
+                    _ => serde_json::Value::Null,
+                }
+            }
+            Value::Time(negative, days, hours, minutes, seconds, micros) => {
+                let sign = if *negative { "-" } else { "" };
+                let hours = *days * 24 + *hours as u32;
+                let value = match micros {
+                    0 => format!("{}{:02}:{:02}:{:02}", sign, hours, minutes, seconds),
+                    _ => format!("{}{:02}:{:02}:{:02}.{:06}", sign, hours, minutes, seconds, micros),
+                };
+                serde_json::Value::String(value)
+            }
+        };
+        Ok(Self(value))
+    }
+    ///
+    /// Returns the `row` converted into [RowMap], in the order of the columns
+    pub fn row(row: &Row) -> Result<RowMap, ApiError> {
+        row.columns_ref().iter().enumerate().map(|(index, column)| {
+            let name = column.name_str();
+            row.as_ref(index)
+                .ok_or_else(|| "value is taken".to_owned())
+                .and_then(|value| Self::new(value, column))
+                .map(|value| (name.to_string(), value.0))
+                .map_err(|err| {
+                    let details = format!("MySqlValue.row | Column '{}' of type '{:?}' error: {}", name, column.column_type(), err);
+                    log::warn!("{}", details);
+                    ApiError::new(format!("API SQL Service - unable to read column '{}'", name), details)
+                })
+        }).collect()
+    }
+    ///
+    /// Returns the [ColumnKind] of the `column`
+    pub fn kind(column: &Column) -> ColumnKind {
+        match column.column_type() {
+            ColumnType::MYSQL_TYPE_TINY if column.column_length() == 1 => ColumnKind::Bool,
+            ColumnType::MYSQL_TYPE_TINY | ColumnType::MYSQL_TYPE_SHORT | ColumnType::MYSQL_TYPE_INT24 | ColumnType::MYSQL_TYPE_LONG
+            | ColumnType::MYSQL_TYPE_LONGLONG | ColumnType::MYSQL_TYPE_YEAR | ColumnType::MYSQL_TYPE_FLOAT | ColumnType::MYSQL_TYPE_DOUBLE
+            | ColumnType::MYSQL_TYPE_BIT => ColumnKind::Number,
+            ColumnType::MYSQL_TYPE_DECIMAL | ColumnType::MYSQL_TYPE_NEWDECIMAL => ColumnKind::Decimal,
+            ColumnType::MYSQL_TYPE_DATETIME | ColumnType::MYSQL_TYPE_DATETIME2 | ColumnType::MYSQL_TYPE_TIMESTAMP | ColumnType::MYSQL_TYPE_TIMESTAMP2 => ColumnKind::Timestamp,
+            ColumnType::MYSQL_TYPE_STRING | ColumnType::MYSQL_TYPE_VAR_STRING | ColumnType::MYSQL_TYPE_VARCHAR | ColumnType::MYSQL_TYPE_ENUM
+            | ColumnType::MYSQL_TYPE_SET | ColumnType::MYSQL_TYPE_TINY_BLOB | ColumnType::MYSQL_TYPE_MEDIUM_BLOB | ColumnType::MYSQL_TYPE_LONG_BLOB
+            | ColumnType::MYSQL_TYPE_BLOB if column.character_set() != Self::BINARY_CHARSET => ColumnKind::Text,
+            _ => ColumnKind::Other,
+        }
+    }
+    ///
+    /// Returns the number, null if it can't be represented in json
+    fn number(value: f64) -> serde_json::Value {
+        serde_json::Number::from_f64(value).map_or(serde_json::Value::Null, serde_json::Value::Number)
+    }
+}
+//
+// Binding the json parameter to the mysql statement
+// - null - NULL, bool - 0 / 1, number - integer / double, string - string, array & object - json string
+impl From<&SqlParam> for Value {
+    fn from(param: &SqlParam) -> Self {
+        match &param.0 {
+            serde_json::Value::Null => Value::NULL,
+            serde_json::Value::Bool(value) => Value::Int(*value as i64),
+            serde_json::Value::Number(value) => match (value.as_i64(), value.as_u64()) {
+                (Some(value), _) => Value::Int(value),
+                (None, Some(value)) => Value::UInt(value),
+                _ => Value::Double(value.as_f64().unwrap_or(f64::NAN)),
+            },
+            serde_json::Value::String(value) => Value::Bytes(value.as_bytes().to_vec()),
+            value => Value::Bytes(value.to_string().into_bytes()),
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod pg_cursor;
+pub mod pg_listener;
+pub mod pg_service;
+pub mod pg_value;
@@ -0,0 +1,205 @@
+use std::{collections::HashMap, sync::mpsc, thread::{self, JoinHandle}};
+use ::postgres::{types::ToSql, Client, Row};
+use crate::{
+    api::reply::api_reply::ApiReplyCursor,
+    error::api_error::ApiError,
+    server::{api_query::{api_query_cursor::ApiQueryCursorOpen, row_map::RowMap, sql_lexer::SqlDialect}, postgres::pg_service::PgService},
+};
+///
+/// Command to the cursor thread
+enum PgCursorCmd {
+    Next,
+}
+///
+/// Server side cursor over the postgres portal
+/// - Holding own connection & transaction in the dedicated thread,
+///   so the rows are fetched from the database page by page, never all at once
+/// - Closed when the last page fetched, on error or on drop
+pub struct PgCursor {
+    id: String,
+    page_size: usize,
+    done: bool,
+    send: Option<mpsc::Sender<PgCursorCmd>>,
+    recv: mpsc::Receiver<Result<Vec<RowMap>, ApiError>>,
+    handle: Option<JoinHandle<()>>,
+}
+//
+//
+impl PgCursor {
+    ///
+    /// Opens the cursor for the `query`
+    /// - `client` - connection owned by the cursor until it closed
+    /// - `read_only` - the transaction of the cursor started `READ ONLY`
+    /// - `row_map` - converts the postgres row into the [RowMap]
+    pub fn open<F>(id: impl Into<String>, mut client: Client, query: &ApiQueryCursorOpen, read_only: bool, row_map: F) -> Result<Self, ApiError>
+    where
+        F: Fn(&Row) -> Result<RowMap, ApiError> + Send + 'static {
+        let id = id.into();
+        let (sql, params) = query.query.bind(SqlDialect::Postgres)?;
+        let page_size = query.page_size;
+        let (cmd_send, cmd_recv) = mpsc::channel();
+        let (page_send, page_recv) = mpsc::channel();
+        let dbg = format!("PgCursor({})", id);
+        let handle = thread::spawn(move || {
+            let error = |err: ::postgres::Error| {
+                let details = format!("{}.open | Error: {}", dbg, err);
+                log::warn!("{}", details);
+                ApiError::new("API SQL Service - cursor error", details)
+            };
+            let mut transaction = match PgService::transaction(&mut client, read_only) {
+                Ok(transaction) => transaction,
+                Err(err) => {
+                    _ = page_send.send(Err(error(err)));
+                    return;
+                }
+            };
+            let params: Vec<&(dyn ToSql + Sync)> = params.iter().map(|param| param as &(dyn ToSql + Sync)).collect();
+            let portal = match transaction.bind(sql.as_str(), &params) {
+                Ok(portal) => portal,
+                Err(err) => {
+                    _ = page_send.send(Err(error(err)));
+                    return;
+                }
+            };
+            if page_send.send(Ok(vec![])).is_err() {
+                return;
+            }
+            while let Ok(PgCursorCmd::Next) = cmd_recv.recv() {
+                let page = transaction
+                    .query_portal(&portal, page_size as i32)
+                    .map_err(error)
+                    .and_then(|rows| rows.iter().map(&row_map).collect::<Result<Vec<_>, _>>());
+                let failed = page.is_err();
+                if page_send.send(page).is_err() || failed {
+                    break;
+                }
+            }
+            log::debug!("{}.open | Closed", dbg);
+        });
+        let mut cursor = Self {
+            id,
+            page_size,
+            done: false,
+            send: Some(cmd_send),
+            recv: page_recv,
+            handle: Some(handle),
+        };
+        match cursor.recv.recv() {
+            Ok(Ok(_)) => Ok(cursor),
+            Ok(Err(err)) => {
+                cursor.close();
+                Err(err)
+            }
+            Err(err) => {
+                cursor.close();
+                Err(ApiError::new("API SQL Service - cursor error", format!("PgCursor.open | Error: {}", err)))
+            }
+        }
+    }
+    ///
+    /// Returns the next page of the rows
+    /// - cursor is closed if the page is the last one
+    pub fn next(&mut self) -> Result<Vec<RowMap>, ApiError> {
+        if self.done {
+            return Ok(vec![]);
+        }
+        let page = match &self.send {
+            Some(send) => match send.send(PgCursorCmd::Next) {
+                Ok(_) => match self.recv.recv() {
+                    Ok(page) => page,
+                    Err(err) => Err(ApiError::new("API SQL Service - cursor error", format!("PgCursor({}).next | Error: {}", self.id, err))),
+                },
+                Err(err) => Err(ApiError::new("API SQL Service - cursor error", format!("PgCursor({}).next | Error: {}", self.id, err))),
+            },
+            None => Ok(vec![]),
+        };
+        match &page {
+            Ok(rows) if rows.len() >= self.page_size => {}
+            _ => self.close(),
+        }
+        page
+    }
+    ///
+    /// Returns the state of the cursor
+    pub fn state(&self) -> ApiReplyCursor {
+        ApiReplyCursor { id: self.id.clone(), done: self.done }
+    }
+    ///
+    /// Closes the cursor, rolls back the transaction and releases the connection
+    pub fn close(&mut self) {
+        self.done = true;
+        self.send.take();
+        if let Some(handle) = self.handle.take() {
+            if let Err(err) = handle.join() {
+                log::warn!("PgCursor({}).close | Thread error: {:?}", self.id, err);
+            }
+        }
+    }
+}
+//
+//
+impl Drop for PgCursor {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+///
+/// The cursors opened over the single keep-alive connection of the client
+#[derive(Default)]
+pub struct PgCursors {
+    cursors: HashMap<String, PgCursor>,
+    last_id: usize,
+}
+//
+//
+impl PgCursors {
+    ///
+    /// Opens new cursor and returns it's first page
+    /// - `read_only` - the transaction of the cursor started `READ ONLY`
+    pub fn open<F>(&mut self, client: Client, query: &ApiQueryCursorOpen, read_only: bool, row_map: F) -> Result<(Vec<RowMap>, ApiReplyCursor), ApiError>
+    where
+        F: Fn(&Row) -> Result<RowMap, ApiError> + Send + 'static {
+        self.last_id += 1;
+        let id = self.last_id.to_string();
+        let mut cursor = PgCursor::open(&id, client, query, read_only, row_map)?;
+        let rows = cursor.next()?;
+        let state = cursor.state();
+        if !state.done {
+            self.cursors.insert(id, cursor);
+        }
+        Ok((rows, state))
+    }
+    ///
+    /// Returns the next page of the cursor
+    pub fn next(&mut self, id: &str) -> Result<(Vec<RowMap>, ApiReplyCursor), ApiError> {
+        match self.cursors.get_mut(id) {
+            Some(cursor) => {
+                let rows = cursor.next();
+                let state = cursor.state();
+                if state.done {
+                    self.cursors.remove(id);
+                }
+                rows.map(|rows| (rows, state))
+            }
+            None => Err(Self::not_found(id)),
+        }
+    }
+    ///
+    /// Closes the cursor
+    pub fn close(&mut self, id: &str) -> Result<ApiReplyCursor, ApiError> {
+        match self.cursors.remove(id) {
+            Some(mut cursor) => {
+                cursor.close();
+                Ok(cursor.state())
+            }
+            None => Err(Self::not_found(id)),
+        }
+    }
+    ///
+    ///
+    fn not_found(id: &str) -> ApiError {
+        let details = format!("PgCursors | Cursor '{}' not found, may be already closed", id);
+        log::warn!("{}", details);
+        ApiError::new(format!("API SQL Service - cursor '{}' not found", id), details)
+    }
+}
@@ -0,0 +1,91 @@
+use std::{sync::{atomic::{AtomicBool, Ordering}, Arc}, thread::{self, JoinHandle}, time::Duration};
+use ::postgres::{fallible_iterator::FallibleIterator, Client};
+use crate::{
+    api::reply::api_notification::ApiNotification,
+    error::api_error::ApiError,
+    server::api_query::api_query_subscribe::ApiQuerySubscribe,
+};
+///
+/// Listens the postgres notifications for the subscribed connection
+/// - Runs `LISTEN channel` for each of the subscribed channels,
+///   the channel names are quoted, so they are case sensitive
+/// - Holding own connection in the dedicated thread,
+///   each `NOTIFY` is passed to the `notify` callback
+/// - Stopped when the `notify` returns false (the subscribed connection closed), on error or on drop
+pub struct PgListener {
+    id: String,
+    exit: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+//
+//
+impl PgListener {
+    ///
+    /// Interval of checking the exit flag while waiting for notifications
+    const POLL_INTERVAL: Duration = Duration::from_millis(100);
+    ///
+    /// Starts listening the channels of the `query`
+    /// - `client` - connection owned by the listener until it stopped
+    /// - `notify` - forwards the notification, returns false if it can't be forwarded any more
+    pub fn listen<F>(id: impl Into<String>, mut client: Client, query: &ApiQuerySubscribe, notify: F) -> Result<Self, ApiError>
+    where
+        F: Fn(ApiNotification) -> bool + Send + 'static {
+        let id = id.into();
+        let dbg = format!("PgListener({})", id);
+        let sql: String = query.channels.iter()
+            .map(|channel| format!("LISTEN \"{}\";", channel.replace('"', "\"\"")))
+            .collect();
+        if let Err(err) = client.batch_execute(&sql) {
+            let details = format!("{}.listen | Error: {}", dbg, err);
+            log::warn!("{}", details);
+            return Err(ApiError::new("API SQL Service - subscribe error", details));
+        }
+        log::debug!("{}.listen | Listening: {:?}", dbg, query.channels);
+        let exit = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let exit = exit.clone();
+            thread::spawn(move || {
+                while !exit.load(Ordering::SeqCst) {
+                    match client.notifications().timeout_iter(Self::POLL_INTERVAL).next() {
+                        Ok(Some(notification)) => {
+                            let notification = ApiNotification::new(notification.channel(), notification.payload(), notification.process_id() as u32);
+                            log::trace!("{}.listen | Notification: {:?}", dbg, notification);
+                            if !notify(notification) {
+                                break;
+                            }
+                        }
+                        Ok(None) => {}
+                        Err(err) => {
+                            log::warn!("{}.listen | Error: {}", dbg, err);
+                            break;
+                        }
+                    }
+                }
+                log::debug!("{}.listen | Stopped", dbg);
+            })
+        };
+        Ok(Self { id, exit, handle: Some(handle) })
+    }
+    ///
+    /// Stops listening and releases the connection
+    pub fn stop(&mut self) {
+        self.exit.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            if let Err(err) = handle.join() {
+                log::warn!("PgListener({}).stop | Thread error: {:?}", self.id, err);
+            }
+        }
+    }
+    ///
+    /// Returns true if still listening
+    pub fn is_active(&self) -> bool {
+        self.handle.as_ref().is_some_and(|handle| !handle.is_finished())
+    }
+}
+//
+//
+impl Drop for PgListener {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -0,0 +1,203 @@
+use std::{sync::Mutex, time::Duration};
+use postgres::{error::SqlState, fallible_iterator::FallibleIterator, Client, Config, NoTls, Row, Transaction};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    error::api_error::ApiError,
+    server::{
+        api_query::{
+            api_query::ApiQuery, api_query_batch::ApiQueryBatch, api_query_cursor::ApiQueryCursorOpen,
+            api_query_sql::ApiQuerySql, api_query_sql_params::SqlParam, api_query_subscribe::ApiQuerySubscribe, row_map::RowMap,
+            sql_lexer::SqlDialect,
+        },
+        api_server::{api_handler::ApiHandlerReply, api_session::ApiSession},
+        cancel::cancel_registry::CancelRegistry,
+        config::{null_defaults::NullDefaults, service_config::ServiceConfig},
+        postgres::{pg_listener::PgListener, pg_value::PgValue},
+        service::sql_service::SqlService,
+    },
+};
+///
+/// The postgres database service
+/// - Connects with the credentials of the [ServiceConfig]:
+///   `path` - `host:port` or `postgresql://` url, `name` - the database, `user` / `pass`
+/// - The connections of the completed queries are kept for the next ones,
+///   the cursor & the subscription hold the own connection until closed
+/// - The running statement is cancelled by the `cancel` query
+/// - `sql`, `batch` & `cursor` are executed in the read only transaction, if the service is `readOnly`
+///   or the user of the query is granted reading only
+/// - Column values converted into json by [PgValue], the order of the columns is kept,
+///   the nulls are replaced by [NullDefaults] if `replaceNullWithDefault` specified
+pub struct PgService {
+    dbg: Dbg,
+    config: Config,
+    clients: Mutex<Vec<Client>>,
+    nulls: Option<NullDefaults>,
+    read_only: bool,
+}
+//
+//
+impl PgService {
+    ///
+    /// Connection timeout
+    const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+    ///
+    /// Max number of the idle connections kept
+    const IDLE_CLIENTS: usize = 8;
+    ///
+    /// Returns [PgService] of the configured database
+    pub fn new(conf: &ServiceConfig) -> Result<Self, Error> {
+        let dbg = Dbg::own(format!("PgService({})", conf.name));
+        let error = Error::new(&dbg, "new");
+        let mut config = match conf.path.contains("://") {
+            true => conf.path.parse::<Config>().map_err(|err| error.pass_with(format!("Invalid url '{}'", conf.path), err.to_string()))?,
+            false => {
+                let (host, port) = match conf.path.rsplit_once(':') {
+                    Some((host, port)) => (host, port.parse::<u16>().map_err(|err| error.pass_with(format!("Invalid port in '{}'", conf.path), err.to_string()))?),
+                    None => (conf.path.as_str(), 5432),
+                };
+                let mut config = Config::new();
+                config.host(host).port(port);
+                config
+            }
+        };
+        config.dbname(&conf.name).connect_timeout(Self::CONNECT_TIMEOUT);
+        if let Some(user) = &conf.user {
+            config.user(user);
+        }
+        if let Some(pass) = &conf.pass {
+            config.password(pass);
+        }
+        Ok(Self { dbg, config, clients: Mutex::new(vec![]), nulls: conf.replace_null_with_default.clone(), read_only: conf.read_only })
+    }
+    ///
+    /// Returns the new connection
+    fn connect(&self) -> Result<Client, ApiError> {
+        self.config.connect(NoTls).map_err(|err| {
+            let details = format!("{}.connect | Connection error: {}", self.dbg, err);
+            log::warn!("{}", details);
+            ApiError::new("API SQL Service - database connection error", details)
+        })
+    }
+    ///
+    /// Returns the idle connection, or the new one
+    fn take(&self) -> Result<Client, ApiError> {
+        let client = self.clients.lock().ok().and_then(|mut clients| clients.pop());
+        match client {
+            Some(client) if !client.is_closed() => Ok(client),
+            _ => self.connect(),
+        }
+    }
+    ///
+    /// Keeps the `client` for the next query
+    /// - The session state left by the query, the open transaction, `set role`, `search_path`, ...,
+    ///   is reset by `ROLLBACK` & `DISCARD ALL`, the client failed to be reset is dropped
+    fn give(&self, mut client: Client) {
+        if let Ok(mut clients) = self.clients.lock() {
+            if !client.is_closed() && clients.len() < Self::IDLE_CLIENTS {
+                match client.batch_execute("ROLLBACK").and_then(|_| client.batch_execute("DISCARD ALL")) {
+                    Ok(_) => clients.push(client),
+                    Err(err) => log::warn!("{}.give | Unable to reset the client, dropped: {}", self.dbg, err),
+                }
+            }
+        }
+    }
+    ///
+    /// Returns true if the query of the `session` must be executed read only
+    fn read_only(&self, session: &ApiSession) -> bool {
+        self.read_only || session.is_read_only()
+    }
+    ///
+    /// Returns the transaction started on the `client`, `BEGIN READ ONLY` if `read_only`
+    pub(crate) fn transaction(client: &mut Client, read_only: bool) -> Result<Transaction<'_>, postgres::Error> {
+        match read_only {
+            true => client.build_transaction().read_only(true).start(),
+            false => client.transaction(),
+        }
+    }
+    ///
+    /// Returns the rows of the `statement`, executed in the read only transaction if `read_only`
+    fn query(client: &mut Client, read_only: bool, statement: &str, params: &[SqlParam]) -> Result<Vec<Row>, postgres::Error> {
+        match read_only {
+            true => {
+                let mut transaction = Self::transaction(client, true)?;
+                let rows = transaction.query_raw(statement, params.iter())?.collect()?;
+                transaction.commit()?;
+                Ok(rows)
+            }
+            false => client.query_raw(statement, params.iter()).and_then(|rows| rows.collect()),
+        }
+    }
+    ///
+    /// Returns the converter of the postgres row into [RowMap], replacing the nulls if configured
+    fn row_map(&self) -> impl Fn(&Row) -> Result<RowMap, ApiError> + Send + 'static {
+        let nulls = self.nulls.clone();
+        move |row| {
+            let mut row_map = PgValue::row(row)?;
+            if let Some(nulls) = &nulls {
+                nulls.apply(&mut row_map, row.columns().iter().map(|column| (column.name(), PgValue::kind(column.type_()))));
+            }
+            Ok(row_map)
+        }
+    }
+    ///
+    /// Returns [ApiError] of the failed query, with the details of the database error
+    fn error(&self, method: &str, err: postgres::Error) -> ApiError {
+        let (message, details) = match err.as_db_error() {
+            Some(db) => {
+                let message = match db.code() {
+                    &SqlState::QUERY_CANCELED => "API SQL Service - query cancelled",
+                    _ => "API SQL Service - query error",
+                };
+                let mut details = format!("{}.{} | {} {}: {}", self.dbg, method, db.severity(), db.code().code(), db.message());
+                if let Some(detail) = db.detail() {
+                    details.push_str(&format!(", detail: {}", detail));
+                }
+                if let Some(hint) = db.hint() {
+                    details.push_str(&format!(", hint: {}", hint));
+                }
+                (message, details)
+            }
+            None => ("API SQL Service - query error", format!("{}.{} | {}", self.dbg, method, err)),
+        };
+        log::warn!("{}", details);
+        ApiError::new(message, details)
+    }
+}
+//
+//
+impl SqlService for PgService {
+    fn sql(&self, query: &ApiQuery, sql: &ApiQuerySql, session: &mut ApiSession) -> Result<Vec<RowMap>, ApiError> {
+        let (statement, params) = sql.bind(SqlDialect::Postgres)?;
+        let read_only = self.read_only(session);
+        let mut client = self.take()?;
+        let rows = {
+            let _job = session.cancel_registry().register(&query.auth_token(), &query.id(), CancelRegistry::postgres(client.cancel_token()));
+            Self::query(&mut client, read_only, &statement, &params).map_err(|err| self.error("sql", err))
+        };
+        self.give(client);
+        rows?.iter().map(self.row_map()).collect()
+    }
+    fn batch(&self, query: &ApiQuery, batch: &ApiQueryBatch, session: &mut ApiSession) -> Result<Vec<RowMap>, ApiError> {
+        let read_only = self.read_only(session);
+        let mut client = self.take()?;
+        let result = {
+            let _job = session.cancel_registry().register(&query.auth_token(), &query.id(), CancelRegistry::postgres(client.cancel_token()));
+            batch.execute(&mut client, read_only, self.row_map())
+        };
+        self.give(client);
+        result
+    }
+    fn cursor(&self, _: &ApiQuery, open: &ApiQueryCursorOpen, session: &mut ApiSession) -> Result<ApiHandlerReply, ApiError> {
+        let read_only = self.read_only(session);
+        let client = self.connect()?;
+        let (rows, cursor) = session.cursors.open(client, open, read_only, self.row_map())?;
+        Ok(ApiHandlerReply::new(rows).with_cursor(cursor))
+    }
+    fn subscribe(&self, query: &ApiQuery, subscribe: &ApiQuerySubscribe, session: &mut ApiSession) -> Result<Vec<RowMap>, ApiError> {
+        let client = self.connect()?;
+        let writer = session.writer();
+        let listener = PgListener::listen(query.id(), client, subscribe, move |notification| writer.notify(&notification))?;
+        session.listen(listener);
+        Ok(vec![])
+    }
+}
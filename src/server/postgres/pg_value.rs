@@ -0,0 +1,154 @@
+use std::error::Error;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, SecondsFormat, Utc};
+use postgres::{types::{FromSql, Kind, Type}, Row};
+use crate::{error::api_error::ApiError, server::api_query::{column_kind::ColumnKind, row_map::RowMap}};
+///
+/// The value of the postgres column converted into json
+/// - `int2` / `int4` / `int8` / `oid` / `float4` / `float8` - number, `NaN` & infinite values - null
+/// - `numeric` - string with the exact decimal value, `123.450` for `numeric(6, 3)`,
+///   as it can't be represented by the json number without rounding, `NaN` & infinite values - null
+/// - `bool` - boolean
+/// - `text` / `varchar` / `bpchar` / `name` / `char` / enums - string
+/// - `json` / `jsonb` - json as is
+/// - `timestamp` - `2024-01-31T12:00:00.123456`, `timestamptz` - `2024-01-31T12:00:00.123456Z`,
+///   `date` - `2024-01-31`, `time` - `12:00:00.123456`
+/// - `uuid` - hyphenated string
+/// - `bytea` - base64 string
+/// - one dimensional arrays of the types above - array
+/// - Other types are not supported, can be casted in the query, `column::text`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PgValue(pub serde_json::Value);
+//
+//
+impl PgValue {
+    ///
+    /// Returns the `row` converted into [RowMap], in the order of the columns
+    pub fn row(row: &Row) -> Result<RowMap, ApiError> {
+        row.columns().iter().enumerate().map(|(index, column)| {
+            match row.try_get::<_, PgValue>(index) {
+                Ok(value) => Ok((column.name().to_owned(), value.0)),
+                Err(err) => {
+                    let details = format!("PgValue.row | Column '{}' of type '{}' error: {}", column.name(), column.type_(), err);
+                    log::warn!("{}", details);
+                    Err(ApiError::new(format!("API SQL Service - unable to read column '{}'", column.name()), details))
+                }
+            }
+        }).collect()
+    }
+    ///
+    /// Returns the [ColumnKind] of the postgres type `ty`
+    pub fn kind(ty: &Type) -> ColumnKind {
+        match *ty {
+            Type::INT2 | Type::INT4 | Type::INT8 | Type::OID | Type::CHAR | Type::FLOAT4 | Type::FLOAT8 => ColumnKind::Number,
+            Type::NUMERIC => ColumnKind::Decimal,
+            Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::UNKNOWN => ColumnKind::Text,
+            Type::BOOL => ColumnKind::Bool,
+            Type::TIMESTAMP => ColumnKind::Timestamp,
+            Type::TIMESTAMPTZ => ColumnKind::TimestampTz,
+            _ => match ty.kind() {
+                Kind::Enum(_) => ColumnKind::Text,
+                Kind::Domain(inner) => Self::kind(inner),
+                _ => ColumnKind::Other,
+            },
+        }
+    }
+    ///
+    /// Returns the number, null if it can't be represented in json
+    fn number(value: f64) -> serde_json::Value {
+        serde_json::Number::from_f64(value).map_or(serde_json::Value::Null, serde_json::Value::Number)
+    }
+    ///
+    /// Returns the `numeric` decoded from the binary format as the exact decimal string:
+    /// `ndigits: u16 | weight: i16 | sign: u16 | dscale: u16 | digits: [u16; ndigits]`,
+    /// the digits in base 10000, the first one multiplied by 10000 ^ weight
+    fn numeric(raw: &[u8]) -> Result<serde_json::Value, Box<dyn Error + Sync + Send>> {
+        let word = |index: usize| raw.get(index * 2..index * 2 + 2).map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]));
+        let (ndigits, weight, sign, dscale) = match (word(0), word(1), word(2), word(3)) {
+            (Some(ndigits), Some(weight), Some(sign), Some(dscale)) => (ndigits as usize, weight as i16 as i64, sign, dscale as usize),
+            _ => return Err("invalid numeric header".into()),
+        };
+        if raw.len() != 8 + ndigits * 2 {
+            return Err(format!("invalid numeric length {}, expected {}", raw.len(), 8 + ndigits * 2).into());
+        }
+        match sign {
+            0x0000 | 0x4000 => {}
+            // NaN, Infinity, -Infinity
+            _ => return Ok(serde_json::Value::Null),
+        }
+        let digit = |index: i64| match index >= 0 && (index as usize) < ndigits {
+            true => word(4 + index as usize).unwrap_or(0),
+            false => 0,
+        };
+        let mut value = String::from(if sign == 0x4000 { "-" } else { "" });
+        match weight < 0 {
+            true => value.push('0'),
+            false => for index in 0..=weight {
+                value.push_str(&match index {
+                    0 => digit(index).to_string(),
+                    _ => format!("{:04}", digit(index)),
+                });
+            },
+        }
+        if dscale > 0 {
+            let mut fraction = String::new();
+            let mut index = weight + 1;
+            while fraction.len() < dscale {
+                fraction.push_str(&format!("{:04}", digit(index)));
+                index += 1;
+            }
+            fraction.truncate(dscale);
+            value.push('.');
+            value.push_str(&fraction);
+        }
+        Ok(serde_json::Value::String(value))
+    }
+    ///
+    /// Returns the `uuid` decoded from 16 bytes as hyphenated string
+    fn uuid(raw: &[u8]) -> Result<serde_json::Value, Box<dyn Error + Sync + Send>> {
+        if raw.len() != 16 {
+            return Err(format!("invalid uuid length {}", raw.len()).into());
+        }
+        let hex: String = raw.iter().map(|byte| format!("{:02x}", byte)).collect();
+        Ok(serde_json::Value::String(format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])))
+    }
+}
+//
+//
+impl<'a> FromSql<'a> for PgValue {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        use serde_json::Value;
+        let value = match *ty {
+            Type::BOOL => Value::Bool(bool::from_sql(ty, raw)?),
+            Type::CHAR => Value::from(i8::from_sql(ty, raw)?),
+            Type::INT2 => Value::from(i16::from_sql(ty, raw)?),
+            Type::INT4 => Value::from(i32::from_sql(ty, raw)?),
+            Type::INT8 => Value::from(i64::from_sql(ty, raw)?),
+            Type::OID => Value::from(u32::from_sql(ty, raw)?),
+            Type::FLOAT4 => Self::number(f32::from_sql(ty, raw)? as f64),
+            Type::FLOAT8 => Self::number(f64::from_sql(ty, raw)?),
+            Type::NUMERIC => Self::numeric(raw)?,
+            Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::UNKNOWN => Value::String(String::from_sql(ty, raw)?),
+            Type::JSON | Type::JSONB => Value::from_sql(ty, raw)?,
+            Type::TIMESTAMP => Value::String(NaiveDateTime::from_sql(ty, raw)?.format("%Y-%m-%dT%H:%M:%S%.f").to_string()),
+            Type::TIMESTAMPTZ => Value::String(DateTime::<Utc>::from_sql(ty, raw)?.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+            Type::DATE => Value::String(NaiveDate::from_sql(ty, raw)?.format("%Y-%m-%d").to_string()),
+            Type::TIME => Value::String(NaiveTime::from_sql(ty, raw)?.format("%H:%M:%S%.f").to_string()),
+            Type::UUID => Self::uuid(raw)?,
+            Type::BYTEA => Value::String(STANDARD.encode(raw)),
+            _ => match ty.kind() {
+                Kind::Array(_) => Value::Array(Vec::<PgValue>::from_sql(ty, raw)?.into_iter().map(|value| value.0).collect()),
+                Kind::Domain(inner) => Self::from_sql(inner, raw)?.0,
+                Kind::Enum(_) => Value::String(std::str::from_utf8(raw)?.to_owned()),
+                _ => return Err(format!("type '{}' is not supported, cast it in the query, like `column::text`", ty).into()),
+            },
+        };
+        Ok(Self(value))
+    }
+    fn from_sql_null(_: &Type) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(Self(serde_json::Value::Null))
+    }
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use crate::{
+    error::api_error::ApiError,
+    server::{
+        api_query::{api_query::ApiQuery, api_query_type::ApiQueryType},
+        api_server::{api_handler::{ApiHandler, ApiHandlerReply}, api_session::ApiSession},
+        config::service_type::ServiceType,
+        service::{executable_service::ExecutableService, service_registry::ServiceRegistry},
+    },
+};
+///
+/// Replies the `executable` queries by the executable, the query requests by the `name` field
+/// ```
+/// let registry = ServiceRegistry::from(ApiConfig::load("config.yaml")?);
+/// let router = ApiRouter::new().with_handler(ApiQueryTypeName::Executable, ExecutableHandler::from_registry(&registry));
+/// ```
+#[derive(Default)]
+pub struct ExecutableHandler {
+    executables: HashMap<String, ExecutableService>,
+}
+//
+//
+impl ExecutableHandler {
+    ///
+    /// Returns [ExecutableHandler] without executables
+    pub fn new() -> Self {
+        Self::default()
+    }
+    ///
+    /// Returns [ExecutableHandler] replying the queries to the executable `name` by the `service`
+    pub fn with_executable(mut self, name: impl Into<String>, service: ExecutableService) -> Self {
+        self.executables.insert(name.into(), service);
+        self
+    }
+    ///
+    /// Returns [ExecutableHandler] of all the executables of the `registry`
+    pub fn from_registry(registry: &ServiceRegistry) -> Self {
+        registry.services().iter()
+            .filter(|service| service.kind == ServiceType::Executable)
+            .fold(Self::new(), |handler, service| handler.with_executable(&service.name, ExecutableService::new(service)))
+    }
+}
+//
+//
+impl ApiHandler for ExecutableHandler {
+    fn handle(&self, query: &ApiQuery, session: &mut ApiSession) -> Result<ApiHandlerReply, ApiError> {
+        match query.query() {
+            ApiQueryType::Executable(executable) => match self.executables.get(&executable.name) {
+                Some(service) => service.run(query, &executable, session).map(ApiHandlerReply::new),
+                None => {
+                    let details = format!("ExecutableHandler.handle | Executable '{}' not found", executable.name);
+                    log::warn!("{}", details);
+                    Err(ApiError::new(format!("API Executable Service - executable '{}' not found", executable.name), details))
+                }
+            },
+            other => {
+                let details = format!("ExecutableHandler.handle | Query '{}' isn't the executable query", other.name().value());
+                log::warn!("{}", details);
+                Err(ApiError::new(format!("API Executable Service - query of type '{}' not supported", other.name().value()), details))
+            }
+        }
+    }
+}
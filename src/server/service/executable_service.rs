@@ -0,0 +1,117 @@
+use std::{collections::BTreeMap, io::ErrorKind, path::{Path, PathBuf}, process::Command};
+use sal_core::dbg::Dbg;
+use crate::{
+    error::api_error::ApiError,
+    server::{
+        api_query::{api_query::ApiQuery, api_query_executable::ApiQueryExecutable, row_map::RowMap},
+        api_server::api_session::ApiSession,
+        config::{params_mode::ParamsMode, service_config::ServiceConfig},
+        service::process_runner::{ProcessError, ProcessRunner},
+    },
+};
+///
+/// The executable service
+/// - Runs the executable of the [ServiceConfig] `path`, in the `workDir`, the folder of the executable by default
+/// - The environment is cleared, only the configured `env` is set, unless `inheritEnv`
+/// - `params` of the query are passed by the [ParamsMode] of the service
+/// - The json / NDJSON printed to the stdout is replied as the rows, see [ProcessOutput::rows](crate::server::service::process_runner::ProcessOutput::rows)
+/// - Non zero exit code is replied as the error, stderr - in the `details`
+/// - The executable is killed by the `timeout` / `outputLimit` of the service and by the `cancel` query
+pub struct ExecutableService {
+    dbg: Dbg,
+    path: PathBuf,
+    params: ParamsMode,
+    env: BTreeMap<String, String>,
+    inherit_env: bool,
+    work_dir: PathBuf,
+    runner: ProcessRunner,
+}
+//
+//
+impl ExecutableService {
+    ///
+    /// Returns [ExecutableService] of the configured executable
+    pub fn new(conf: &ServiceConfig) -> Self {
+        let dbg = Dbg::own(format!("ExecutableService({})", conf.name));
+        // Relative path of the program is ambiguous, when the working directory is changed
+        let path = std::fs::canonicalize(&conf.path).unwrap_or_else(|_| PathBuf::from(&conf.path));
+        let work_dir = match &conf.work_dir {
+            Some(work_dir) => PathBuf::from(work_dir),
+            None => path.parent().map_or_else(|| PathBuf::from("."), Path::to_path_buf),
+        };
+        Self {
+            path,
+            params: conf.params,
+            env: conf.env.clone(),
+            inherit_env: conf.inherit_env,
+            work_dir,
+            runner: ProcessRunner::new(&dbg, conf),
+            dbg,
+        }
+    }
+    ///
+    /// Returns the rows printed by the executable
+    pub fn run(&self, query: &ApiQuery, executable: &ApiQueryExecutable, session: &mut ApiSession) -> Result<Vec<RowMap>, ApiError> {
+        let mut command = Command::new(&self.path);
+        if !self.inherit_env {
+            command.env_clear();
+        }
+        command.envs(&self.env).current_dir(&self.work_dir);
+        let input = match self.params {
+            ParamsMode::Stdin => serde_json::to_vec(&executable.params)
+                .map_err(|err| self.error("API Executable Service - invalid params", format!("Params error: {}", err)))?,
+            ParamsMode::Args => {
+                command.args(Self::args(&executable.params));
+                vec![]
+            }
+        };
+        let output = self.runner.run(query, session, command, &input).map_err(|err| {
+            let message = match &err {
+                ProcessError::Spawn(err) => match err.kind() {
+                    ErrorKind::NotFound => "API Executable Service - executable not found",
+                    ErrorKind::PermissionDenied => "API Executable Service - executable permission denied",
+                    _ => "API Executable Service - unable to start executable",
+                },
+                ProcessError::Io(_) => "API Executable Service - executable error",
+                ProcessError::Timeout(_) => "API Executable Service - executable timeout",
+                ProcessError::OutputLimit(_) => "API Executable Service - executable output exceeds the limit",
+                ProcessError::Limit(_) => "API Executable Service - executable exceeds the resource limit",
+                ProcessError::Cancelled => "API Executable Service - executable cancelled",
+            };
+            let details = match &err {
+                ProcessError::Spawn(spawn) => format!("Executable '{}' in '{}' error ({:?}): {}", self.path.display(), self.work_dir.display(), spawn.kind(), err),
+                _ => format!("Executable '{}' error: {}", self.path.display(), err),
+            };
+            let error = self.error(message, details);
+            match err.code() {
+                Some(code) => error.with_code(code),
+                None => error,
+            }
+        })?;
+        if !output.status.success() {
+            return Err(self.error(
+                "API Executable Service - executable error",
+                format!("Executable '{}' exited with {}, stderr: {}", self.path.display(), output.status, output.stderr()),
+            ));
+        }
+        if !output.stderr.is_empty() {
+            log::debug!("{}.run | Executable '{}' stderr: {}", self.dbg, self.path.display(), output.stderr());
+        }
+        output.rows().map_err(|err| self.error("API Executable Service - invalid executable output", format!("Executable '{}': {}", self.path.display(), err)))
+    }
+    ///
+    /// Returns the `params` as the command line arguments `--name=value`, strings as is, other values as json
+    fn args(params: &serde_json::Map<String, serde_json::Value>) -> Vec<String> {
+        params.iter().map(|(name, value)| match value {
+            serde_json::Value::String(value) => format!("--{}={}", name, value),
+            value => format!("--{}={}", name, value),
+        }).collect()
+    }
+    ///
+    /// Returns [ApiError] with the `details` of the service
+    fn error(&self, message: &str, details: String) -> ApiError {
+        let details = format!("{}.run | {}", self.dbg, details);
+        log::warn!("{}", details);
+        ApiError::new(message, details)
+    }
+}
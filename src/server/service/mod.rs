@@ -0,0 +1,9 @@
+pub mod executable_handler;
+pub mod executable_service;
+pub mod process_runner;
+pub mod process_sandbox;
+pub mod python_handler;
+pub mod python_service;
+pub mod service_registry;
+pub mod sql_handler;
+pub mod sql_service;
\ No newline at end of file
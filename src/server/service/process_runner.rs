@@ -0,0 +1,270 @@
+use std::{
+    fmt::Display, io::{ErrorKind, Read, Write}, process::{Command, ExitStatus, Stdio},
+    sync::{atomic::{AtomicBool, Ordering}, mpsc, Arc}, thread, time::{Duration, Instant},
+};
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+use sal_core::dbg::Dbg;
+use serde::Deserialize;
+use crate::{error::api_error_code::ApiErrorCode, server::{
+    api_query::{api_query::ApiQuery, row_map::RowMap},
+    api_server::api_session::ApiSession,
+    cancel::cancel_registry::CancelRegistry,
+    config::service_config::ServiceConfig,
+    service::process_sandbox::ProcessSandbox,
+}};
+///
+/// Runs the process of the python script / executable service
+/// - The input is written to the stdin, the stdout & stderr are collected
+/// - The process is started as the leader of the new process group,
+///   so it's killed together with all it's children when:
+///     - it runs longer then the `timeout` of the service
+///     - it's stdout or stderr exceeds the `outputLimit` of the service
+///     - the `cancel` query received
+/// - The `limits` of the service applied by the [ProcessSandbox]
+pub struct ProcessRunner {
+    dbg: Dbg,
+    timeout: Duration,
+    output_limit: usize,
+    sandbox: ProcessSandbox,
+}
+//
+//
+impl ProcessRunner {
+    ///
+    /// Run time of the process, if the `timeout` isn't configured
+    pub const TIMEOUT: Duration = Duration::from_secs(60);
+    ///
+    /// Max size of the stdout / stderr, if the `outputLimit` isn't configured
+    pub const OUTPUT_LIMIT: usize = 16 * 1024 * 1024;
+    ///
+    /// The process state checked with
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+    ///
+    /// Returns [ProcessRunner] with the `timeout` & `outputLimit` of the service
+    pub fn new(parent: &Dbg, conf: &ServiceConfig) -> Self {
+        Self {
+            dbg: Dbg::new(parent, "ProcessRunner"),
+            timeout: conf.timeout.map_or(Self::TIMEOUT, Duration::from_secs),
+            output_limit: conf.output_limit.unwrap_or(Self::OUTPUT_LIMIT),
+            sandbox: ProcessSandbox::new(&conf.limits),
+        }
+    }
+    ///
+    /// Returns the output of the `command` completed
+    /// - `input` - written to the stdin of the process, which is closed then
+    /// - The process can be cancelled by the `id` of the `query`
+    pub fn run(&self, query: &ApiQuery, session: &mut ApiSession, mut command: Command, input: &[u8]) -> Result<ProcessOutput, ProcessError> {
+        command.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+        #[cfg(unix)]
+        command.process_group(0);
+        self.sandbox.apply(&mut command).map_err(ProcessError::Spawn)?;
+        let mut child = command.spawn().map_err(|err| match self.sandbox.exceeded_on_start(&err) {
+            Some(limit) => ProcessError::Limit(limit),
+            None => ProcessError::Spawn(err),
+        })?;
+        let pid = child.id();
+        log::debug!("{}.run | Started {:?}, pid: {}", self.dbg, command.get_program(), pid);
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let _job = session.cancel_registry().register(&query.auth_token(), &query.id(), {
+            let cancelled = cancelled.clone();
+            Box::new(move || {
+                cancelled.store(true, Ordering::SeqCst);
+                CancelRegistry::process(pid)()
+            })
+        });
+        let exceeded = Arc::new(AtomicBool::new(false));
+        let (done_send, done_recv) = mpsc::channel();
+        let mut readers = vec![];
+        let outputs: [Option<Box<dyn Read + Send>>; 2] = [
+            child.stdout.take().map(|stdout| Box::new(stdout) as Box<dyn Read + Send>),
+            child.stderr.take().map(|stderr| Box::new(stderr) as Box<dyn Read + Send>),
+        ];
+        for output in outputs.into_iter().flatten() {
+            let (limit, exceeded, done) = (self.output_limit, exceeded.clone(), done_send.clone());
+            readers.push(thread::spawn(move || {
+                let result = Self::read(output, limit, pid, &exceeded);
+                let _ = done.send(());
+                result
+            }));
+        }
+        drop(done_send);
+        if let Some(mut stdin) = child.stdin.take() {
+            let input = input.to_vec();
+            thread::spawn(move || {
+                // The process may exit without reading the input
+                if let Err(err) = stdin.write_all(&input) {
+                    if err.kind() != ErrorKind::BrokenPipe {
+                        log::warn!("ProcessRunner.run | Write stdin of the pid {} error: {}", pid, err);
+                    }
+                }
+            });
+        }
+        // The children of the process may keep the output open after it exits
+        let deadline = Instant::now() + self.timeout;
+        let mut status = None;
+        let mut open = readers.len();
+        while status.is_none() || open > 0 {
+            if status.is_none() {
+                status = match child.try_wait() {
+                    Ok(status) => status,
+                    Err(err) => {
+                        Self::kill(&self.dbg, pid);
+                        return Err(ProcessError::Io(err));
+                    }
+                };
+            }
+            match done_recv.recv_timeout(Self::POLL_INTERVAL) {
+                Ok(_) => open -= 1,
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => open = 0,
+            }
+            if (status.is_none() || open > 0) && Instant::now() >= deadline {
+                Self::kill(&self.dbg, pid);
+                let _ = child.wait();
+                return Err(ProcessError::Timeout(self.timeout));
+            }
+        }
+        let mut outputs = readers.into_iter().map(|reader| match reader.join() {
+            Ok(output) => output.map_err(ProcessError::Io),
+            Err(_) => Err(ProcessError::Io(std::io::Error::other("output reader panicked"))),
+        });
+        let stdout = outputs.next().unwrap_or(Ok(vec![]))?;
+        let stderr = outputs.next().unwrap_or(Ok(vec![]))?;
+        if cancelled.load(Ordering::SeqCst) {
+            return Err(ProcessError::Cancelled);
+        }
+        if exceeded.load(Ordering::SeqCst) {
+            return Err(ProcessError::OutputLimit(self.output_limit));
+        }
+        let status = status.ok_or_else(|| ProcessError::Io(std::io::Error::other("exit status is unknown")))?;
+        log::debug!("{}.run | Pid {} exited with {}, stdout: {} bytes, stderr: {} bytes", self.dbg, pid, status, stdout.len(), stderr.len());
+        let output = ProcessOutput { status, stdout, stderr };
+        if !output.status.success() {
+            if let Some(limit) = self.sandbox.exceeded(&output.status, &output.stderr()) {
+                return Err(ProcessError::Limit(format!("{}, exited with {}, stderr: {}", limit, output.status, output.stderr())));
+            }
+        }
+        Ok(output)
+    }
+    ///
+    /// Returns the output read until closed,
+    /// the process group is killed if the output exceeds the `limit`
+    fn read(mut output: impl Read, limit: usize, pid: u32, exceeded: &AtomicBool) -> Result<Vec<u8>, std::io::Error> {
+        let mut result = vec![];
+        let mut buf = [0; 8192];
+        loop {
+            match output.read(&mut buf) {
+                Ok(0) => return Ok(result),
+                Ok(len) => {
+                    if result.len() + len > limit {
+                        exceeded.store(true, Ordering::SeqCst);
+                        let _ = CancelRegistry::process(pid)();
+                        return Ok(result);
+                    }
+                    result.extend_from_slice(&buf[..len]);
+                }
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+    ///
+    /// Kills the process group
+    fn kill(dbg: &Dbg, pid: u32) {
+        if let Err(err) = CancelRegistry::process(pid)() {
+            log::warn!("{}.kill | {}", dbg, err);
+        }
+    }
+}
+///
+/// The output of the completed process
+#[derive(Debug)]
+pub struct ProcessOutput {
+    pub status: ExitStatus,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+//
+//
+impl ProcessOutput {
+    ///
+    /// Returns the rows printed to the stdout
+    /// - json object - single row
+    /// - json array of objects - the rows
+    /// - `null` or nothing - no rows
+    /// - json object per line (NDJSON) - the rows, empty lines are skipped
+    pub fn rows(&self) -> Result<Vec<RowMap>, String> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Rows {
+            Row(RowMap),
+            Rows(Vec<RowMap>),
+            Nothing(()),
+        }
+        if self.stdout.iter().all(u8::is_ascii_whitespace) {
+            return Ok(vec![]);
+        }
+        match serde_json::from_slice(&self.stdout) {
+            Ok(Rows::Row(row)) => Ok(vec![row]),
+            Ok(Rows::Rows(rows)) => Ok(rows),
+            Ok(Rows::Nothing(_)) => Ok(vec![]),
+            Err(_) => self.stdout.split(|byte| *byte == b'\n')
+                .enumerate()
+                .filter(|(_, line)| !line.iter().all(u8::is_ascii_whitespace))
+                .map(|(index, line)| serde_json::from_slice::<RowMap>(line).map_err(|err| format!(
+                    "Output must be json object, array of objects, null or json object per line, but found in line {}: {}, error: {}",
+                    index + 1, String::from_utf8_lossy(line), err,
+                )))
+                .collect(),
+        }
+    }
+    ///
+    /// Returns the stderr as text
+    pub fn stderr(&self) -> String {
+        String::from_utf8_lossy(&self.stderr).trim_end().to_owned()
+    }
+}
+///
+/// The reason the process has no output
+#[derive(Debug)]
+pub enum ProcessError {
+    /// The process can't be started
+    Spawn(std::io::Error),
+    /// Reading the output / waiting the process error
+    Io(std::io::Error),
+    /// The process killed by the timeout
+    Timeout(Duration),
+    /// The process killed, the output exceeds the limit, bytes
+    OutputLimit(usize),
+    /// The process failed, the resource limit exceeded
+    Limit(String),
+    /// The process killed by the `cancel` query
+    Cancelled,
+}
+//
+//
+impl ProcessError {
+    ///
+    /// Returns [ApiErrorCode::LimitExceeded] if the process exceeds the output or the resource limit
+    pub fn code(&self) -> Option<ApiErrorCode> {
+        match self {
+            ProcessError::OutputLimit(_) | ProcessError::Limit(_) => Some(ApiErrorCode::LimitExceeded),
+            _ => None,
+        }
+    }
+}
+//
+//
+impl Display for ProcessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProcessError::Spawn(err) => write!(f, "Start error: {}", err),
+            ProcessError::Io(err) => write!(f, "Io error: {}", err),
+            ProcessError::Timeout(timeout) => write!(f, "Killed, runs longer then {:?}", timeout),
+            ProcessError::OutputLimit(limit) => write!(f, "Killed, output exceeds {} bytes", limit),
+            ProcessError::Limit(limit) => write!(f, "Exceeds the limit of {}", limit),
+            ProcessError::Cancelled => write!(f, "Killed by the cancel query"),
+        }
+    }
+}
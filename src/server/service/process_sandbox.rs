@@ -0,0 +1,125 @@
+use std::process::{Command, ExitStatus};
+use crate::server::config::service_limits::ServiceLimits;
+///
+/// Applies the [ServiceLimits] to the process of the script / executable
+/// - Resource limits are set by `setrlimit`, the CPU time limit signals `SIGXCPU`,
+///   the process is killed a second later, if it still runs
+/// - The working directory is bind mounted read only in the new mount namespace
+/// - `no_new_privs` is set, the group & the user switched the last,
+///   after all the privileged steps are done
+/// - The limits are supported on Linux only, the process with the limits isn't started on other platforms
+#[derive(Debug, Clone)]
+pub struct ProcessSandbox {
+    limits: ServiceLimits,
+}
+//
+//
+impl ProcessSandbox {
+    ///
+    /// The messages of the failed allocation, open & fork, the resource limit is exceeded with
+    const MEMORY_ERRORS: [&'static str; 4] = ["MemoryError", "Cannot allocate memory", "out of memory", "bad_alloc"];
+    const FILES_ERRORS: [&'static str; 1] = ["Too many open files"];
+    const PROCESSES_ERRORS: [&'static str; 3] = ["Resource temporarily unavailable", "Cannot fork", "can't fork"];
+    ///
+    /// Returns [ProcessSandbox] of the `limits`
+    pub fn new(limits: &ServiceLimits) -> Self {
+        Self { limits: limits.clone() }
+    }
+    ///
+    /// Sets up the `command` to apply the limits in the started process, before it's program is executed
+    #[cfg(target_os = "linux")]
+    pub fn apply(&self, command: &mut Command) -> Result<(), std::io::Error> {
+        use std::{ffi::CString, os::unix::{ffi::OsStrExt, process::CommandExt}};
+        if self.limits.is_empty() {
+            return Ok(());
+        }
+        let work_dir = match command.get_current_dir() {
+            Some(dir) => dir.to_path_buf(),
+            None => std::env::current_dir()?,
+        };
+        let work_dir = CString::new(work_dir.as_os_str().as_bytes()).map_err(std::io::Error::other)?;
+        let root = CString::new("/").map_err(std::io::Error::other)?;
+        let limits = self.limits.clone();
+        // Only async-signal-safe calls are allowed between fork & exec
+        let sandbox = move || -> Result<(), std::io::Error> {
+            let check = |result: libc::c_int| match result {
+                -1 => Err(std::io::Error::last_os_error()),
+                _ => Ok(()),
+            };
+            let rlimits = [
+                (libc::RLIMIT_CPU, limits.cpu.map(|cpu| (cpu, cpu + 1))),
+                (libc::RLIMIT_AS, limits.memory.map(|memory| (memory, memory))),
+                (libc::RLIMIT_NOFILE, limits.files.map(|files| (files, files))),
+                (libc::RLIMIT_NPROC, limits.processes.map(|processes| (processes, processes))),
+            ];
+            for (resource, limit) in rlimits {
+                if let Some((soft, hard)) = limit {
+                    let rlimit = libc::rlimit { rlim_cur: soft as libc::rlim_t, rlim_max: hard as libc::rlim_t };
+                    check(unsafe { libc::setrlimit(resource, &rlimit) })?;
+                }
+            }
+            if limits.read_only_work_dir {
+                unsafe {
+                    check(libc::unshare(libc::CLONE_NEWNS))?;
+                    // The mounts of the new namespace aren't propagated back to the host
+                    check(libc::mount(std::ptr::null(), root.as_ptr(), std::ptr::null(), libc::MS_REC | libc::MS_PRIVATE, std::ptr::null()))?;
+                    check(libc::mount(work_dir.as_ptr(), work_dir.as_ptr(), std::ptr::null(), libc::MS_BIND | libc::MS_REC, std::ptr::null()))?;
+                    check(libc::mount(std::ptr::null(), work_dir.as_ptr(), std::ptr::null(), libc::MS_REMOUNT | libc::MS_BIND | libc::MS_RDONLY, std::ptr::null()))?;
+                    // The current directory still refers the directory under the mount
+                    check(libc::chdir(work_dir.as_ptr()))?;
+                }
+            }
+            if limits.no_new_privileges {
+                check(unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) })?;
+            }
+            if (limits.uid.is_some() || limits.gid.is_some()) && unsafe { libc::getuid() } == 0 {
+                check(unsafe { libc::setgroups(0, std::ptr::null()) })?;
+            }
+            if let Some(gid) = limits.gid {
+                check(unsafe { libc::setgid(gid) })?;
+            }
+            if let Some(uid) = limits.uid {
+                check(unsafe { libc::setuid(uid) })?;
+            }
+            Ok(())
+        };
+        unsafe { command.pre_exec(sandbox) };
+        Ok(())
+    }
+    ///
+    /// Sets up the `command` to apply the limits in the started process, not supported on this platform
+    #[cfg(not(target_os = "linux"))]
+    pub fn apply(&self, _: &mut Command) -> Result<(), std::io::Error> {
+        match self.limits.is_empty() {
+            true => Ok(()),
+            false => Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "Limits of the process are supported on Linux only")),
+        }
+    }
+    ///
+    /// Returns the limit exceeded, the process can't be started with
+    /// - The processes of the user are checked by the kernel, when the process switched to the user is executed
+    pub fn exceeded_on_start(&self, err: &std::io::Error) -> Option<String> {
+        match self.limits.processes {
+            Some(processes) if err.raw_os_error() == Some(libc::EAGAIN) => Some(format!("processes {}, start error: {}", processes, err)),
+            _ => None,
+        }
+    }
+    ///
+    /// Returns the limit exceeded by the failed process, recognized by the signal or by the `stderr`
+    pub fn exceeded(&self, status: &ExitStatus, stderr: &str) -> Option<String> {
+        #[cfg(unix)]
+        if let Some(cpu) = self.limits.cpu {
+            use std::os::unix::process::ExitStatusExt;
+            if status.signal() == Some(libc::SIGXCPU) {
+                return Some(format!("CPU time {} s", cpu));
+            }
+        }
+        let matched = |errors: &[&str]| errors.iter().any(|error| stderr.contains(error));
+        match &self.limits {
+            ServiceLimits { memory: Some(memory), .. } if matched(&Self::MEMORY_ERRORS) => Some(format!("address space {} bytes", memory)),
+            ServiceLimits { files: Some(files), .. } if matched(&Self::FILES_ERRORS) => Some(format!("open files {}", files)),
+            ServiceLimits { processes: Some(processes), .. } if matched(&Self::PROCESSES_ERRORS) => Some(format!("processes {}", processes)),
+            _ => None,
+        }
+    }
+}
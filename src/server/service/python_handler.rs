@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use crate::{
+    error::api_error::ApiError,
+    server::{
+        api_query::{api_query::ApiQuery, api_query_type::ApiQueryType},
+        api_server::{api_handler::{ApiHandler, ApiHandlerReply}, api_session::ApiSession},
+        config::service_type::ServiceType,
+        service::{python_service::PythonService, service_registry::ServiceRegistry},
+    },
+};
+///
+/// Replies the `python` queries by the script, the query requests by the `script` field
+/// ```
+/// let registry = ServiceRegistry::from(ApiConfig::load("config.yaml")?);
+/// let router = ApiRouter::new().with_handler(ApiQueryTypeName::Python, PythonHandler::from_registry(&registry));
+/// ```
+#[derive(Default)]
+pub struct PythonHandler {
+    scripts: HashMap<String, PythonService>,
+}
+//
+//
+impl PythonHandler {
+    ///
+    /// Returns [PythonHandler] without scripts
+    pub fn new() -> Self {
+        Self::default()
+    }
+    ///
+    /// Returns [PythonHandler] replying the queries to the script `name` by the `service`
+    pub fn with_script(mut self, name: impl Into<String>, service: PythonService) -> Self {
+        self.scripts.insert(name.into(), service);
+        self
+    }
+    ///
+    /// Returns [PythonHandler] of all the python scripts of the `registry`
+    pub fn from_registry(registry: &ServiceRegistry) -> Self {
+        registry.services().iter()
+            .filter(|service| service.kind == ServiceType::Python)
+            .fold(Self::new(), |handler, service| handler.with_script(&service.name, PythonService::new(service)))
+    }
+}
+//
+//
+impl ApiHandler for PythonHandler {
+    fn handle(&self, query: &ApiQuery, session: &mut ApiSession) -> Result<ApiHandlerReply, ApiError> {
+        match query.query() {
+            ApiQueryType::Python(python) => match self.scripts.get(&python.script) {
+                Some(service) => service.run(query, &python, session).map(ApiHandlerReply::new),
+                None => {
+                    let details = format!("PythonHandler.handle | Script '{}' not found", python.script);
+                    log::warn!("{}", details);
+                    Err(ApiError::new(format!("API Python Script Service - script '{}' not found", python.script), details))
+                }
+            },
+            other => {
+                let details = format!("PythonHandler.handle | Query '{}' isn't the python query", other.name().value());
+                log::warn!("{}", details);
+                Err(ApiError::new(format!("API Python Script Service - query of type '{}' not supported", other.name().value()), details))
+            }
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use std::process::Command;
+use sal_core::dbg::Dbg;
+use crate::{
+    error::api_error::ApiError,
+    server::{
+        api_query::{api_query::ApiQuery, api_query_python::ApiQueryPython, row_map::RowMap},
+        api_server::api_session::ApiSession,
+        config::service_config::ServiceConfig,
+        service::process_runner::{ProcessError, ProcessRunner},
+    },
+};
+///
+/// The python script service
+/// - Runs the script of the [ServiceConfig] `path` by the `interpreter`, `python3` by default
+/// - `params` of the query are written to the stdin as json object
+/// - The json printed to the stdout is replied as the rows, see [ProcessOutput::rows](crate::server::service::process_runner::ProcessOutput::rows)
+/// - Non zero exit code or any output to the stderr is replied as the error, stderr - in the `details`
+/// - The script is killed by the `timeout` / `outputLimit` of the service and by the `cancel` query
+pub struct PythonService {
+    dbg: Dbg,
+    interpreter: String,
+    script: String,
+    runner: ProcessRunner,
+}
+//
+//
+impl PythonService {
+    ///
+    /// The interpreter, if not configured
+    pub const INTERPRETER: &'static str = "python3";
+    ///
+    /// Returns [PythonService] of the configured script
+    pub fn new(conf: &ServiceConfig) -> Self {
+        let dbg = Dbg::own(format!("PythonService({})", conf.name));
+        Self {
+            interpreter: conf.interpreter.clone().unwrap_or_else(|| Self::INTERPRETER.to_owned()),
+            script: conf.path.clone(),
+            runner: ProcessRunner::new(&dbg, conf),
+            dbg,
+        }
+    }
+    ///
+    /// Returns the rows printed by the script
+    pub fn run(&self, query: &ApiQuery, python: &ApiQueryPython, session: &mut ApiSession) -> Result<Vec<RowMap>, ApiError> {
+        let input = serde_json::to_vec(&python.params).map_err(|err| self.error("API Python Script Service - invalid params", format!("Params error: {}", err)))?;
+        let mut command = Command::new(&self.interpreter);
+        command.arg(&self.script);
+        let output = self.runner.run(query, session, command, &input).map_err(|err| {
+            let message = match err {
+                ProcessError::Spawn(_) => "API Python Script Service - unable to start script",
+                ProcessError::Io(_) => "API Python Script Service - script error",
+                ProcessError::Timeout(_) => "API Python Script Service - script timeout",
+                ProcessError::OutputLimit(_) => "API Python Script Service - script output exceeds the limit",
+                ProcessError::Limit(_) => "API Python Script Service - script exceeds the resource limit",
+                ProcessError::Cancelled => "API Python Script Service - script cancelled",
+            };
+            let error = self.error(message, format!("Script '{}' error: {}", self.script, err));
+            match err.code() {
+                Some(code) => error.with_code(code),
+                None => error,
+            }
+        })?;
+        if !output.status.success() || !output.stderr.is_empty() {
+            return Err(self.error(
+                "API Python Script Service - script error",
+                format!("Script '{}' exited with {}, stderr: {}", self.script, output.status, output.stderr()),
+            ));
+        }
+        output.rows().map_err(|err| self.error("API Python Script Service - invalid script output", format!("Script '{}': {}", self.script, err)))
+    }
+    ///
+    /// Returns [ApiError] with the `details` of the service
+    fn error(&self, message: &str, details: String) -> ApiError {
+        let details = format!("{}.run | {}", self.dbg, details);
+        log::warn!("{}", details);
+        ApiError::new(message, details)
+    }
+}
@@ -0,0 +1,56 @@
+use crate::server::config::{api_config::ApiConfig, service_config::ServiceConfig, service_type::ServiceType};
+///
+/// The services configured, looked up by the name the query requests them
+/// - `sql` queries - by the `database`, any of the database types
+/// - `python` queries - by the `script`
+/// - `executable` queries - by the `name`
+#[derive(Debug, Clone, Default)]
+pub struct ServiceRegistry {
+    services: Vec<ServiceConfig>,
+}
+//
+//
+impl ServiceRegistry {
+    ///
+    /// Returns [ServiceRegistry] of the `services`
+    pub fn new(services: Vec<ServiceConfig>) -> Self {
+        Self { services }
+    }
+    ///
+    /// Returns the database service
+    pub fn database(&self, name: &str) -> Option<&ServiceConfig> {
+        self.services.iter().find(|service| service.kind.is_database() && service.name == name)
+    }
+    ///
+    /// Returns the python script service
+    pub fn python(&self, script: &str) -> Option<&ServiceConfig> {
+        self.find(ServiceType::Python, script)
+    }
+    ///
+    /// Returns the executable service
+    pub fn executable(&self, name: &str) -> Option<&ServiceConfig> {
+        self.find(ServiceType::Executable, name)
+    }
+    ///
+    /// Returns the service by it's internal unique key
+    pub fn get(&self, key: &str) -> Option<&ServiceConfig> {
+        self.services.iter().find(|service| service.key == key)
+    }
+    ///
+    /// Returns all the services, in the order they configured
+    pub fn services(&self) -> &[ServiceConfig] {
+        &self.services
+    }
+    ///
+    /// Returns the service of the `kind` by the `name`
+    fn find(&self, kind: ServiceType, name: &str) -> Option<&ServiceConfig> {
+        self.services.iter().find(|service| service.kind == kind && service.name == name)
+    }
+}
+//
+//
+impl From<ApiConfig> for ServiceRegistry {
+    fn from(config: ApiConfig) -> Self {
+        Self::new(config.services)
+    }
+}
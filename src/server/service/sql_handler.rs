@@ -0,0 +1,104 @@
+use std::{collections::HashMap, sync::Arc};
+use sal_core::error::Error;
+use crate::{
+    error::api_error::ApiError,
+    server::{
+        api_query::{api_query::ApiQuery, api_query_cursor::ApiQueryCursor, api_query_type::{ApiQueryType, ApiQueryTypeName}},
+        api_server::{api_handler::{ApiHandler, ApiHandlerReply}, api_router::ApiRouter, api_session::ApiSession},
+        config::service_type::ServiceType,
+        postgres::pg_service::PgService,
+        service::{service_registry::ServiceRegistry, sql_service::SqlService},
+    },
+};
+#[cfg(feature = "sqlite")]
+use crate::server::sqlite::sqlite_service::SqliteService;
+#[cfg(feature = "mysql")]
+use crate::server::mysql::mysql_service::MySqlService;
+///
+/// Replies the `sql`, `batch`, `cursor` & `subscribe` queries by the database,
+/// the query requests by the `database` field
+/// - `cursor` `next` / `close` queries are replied by the cursors of the session
+/// ```
+/// let registry = ServiceRegistry::from(ApiConfig::load("config.yaml")?);
+/// let router = SqlHandler::from_registry(&registry)?.register(ApiRouter::new());
+/// ```
+#[derive(Default)]
+pub struct SqlHandler {
+    databases: HashMap<String, Box<dyn SqlService>>,
+}
+//
+//
+impl SqlHandler {
+    ///
+    /// Returns [SqlHandler] without databases
+    pub fn new() -> Self {
+        Self::default()
+    }
+    ///
+    /// Returns [SqlHandler] replying the queries to the database `name` by the `service`
+    pub fn with_database(mut self, name: impl Into<String>, service: impl SqlService + 'static) -> Self {
+        self.databases.insert(name.into(), Box::new(service));
+        self
+    }
+    ///
+    /// Returns [SqlHandler] of all the databases of the `registry`
+    pub fn from_registry(registry: &ServiceRegistry) -> Result<Self, Error> {
+        let error = Error::new("SqlHandler", "from_registry");
+        let mut handler = Self::new();
+        for service in registry.services().iter().filter(|service| service.kind.is_database()) {
+            handler = match service.kind {
+                ServiceType::Postgres => handler.with_database(&service.name, PgService::new(service).map_err(|err| error.pass(err))?),
+                #[cfg(feature = "sqlite")]
+                ServiceType::Sqlite => handler.with_database(&service.name, SqliteService::new(service).map_err(|err| error.pass(err))?),
+                #[cfg(feature = "mysql")]
+                ServiceType::Mysql => handler.with_database(&service.name, MySqlService::new(service).map_err(|err| error.pass(err))?),
+                kind => return Err(error.err(format!("Database '{}' of type '{:?}' is not supported, check the features enabled", service.key, kind))),
+            };
+        }
+        Ok(handler)
+    }
+    ///
+    /// Returns the `router` replying the `sql`, `batch`, `cursor` & `subscribe` queries by this handler
+    pub fn register(self, router: ApiRouter) -> ApiRouter {
+        let handler = Arc::new(self);
+        [ApiQueryTypeName::Sql, ApiQueryTypeName::Batch, ApiQueryTypeName::Cursor, ApiQueryTypeName::Subscribe]
+            .into_iter()
+            .fold(router, |router, name| {
+                let handler = handler.clone();
+                router.with_handler(name, move |query: &ApiQuery, session: &mut ApiSession| handler.handle(query, session))
+            })
+    }
+    ///
+    /// Returns the service of the `database`
+    fn database(&self, database: &str) -> Result<&dyn SqlService, ApiError> {
+        match self.databases.get(database) {
+            Some(service) => Ok(service.as_ref()),
+            None => {
+                let details = format!("SqlHandler.database | Database '{}' not found", database);
+                log::warn!("{}", details);
+                Err(ApiError::new(format!("API SQL Service - database '{}' not found", database), details))
+            }
+        }
+    }
+}
+//
+//
+impl ApiHandler for SqlHandler {
+    fn handle(&self, query: &ApiQuery, session: &mut ApiSession) -> Result<ApiHandlerReply, ApiError> {
+        match query.query() {
+            ApiQueryType::Sql(sql) => self.database(&sql.database)?.sql(query, &sql, session).map(ApiHandlerReply::new),
+            ApiQueryType::Batch(batch) => self.database(&batch.database)?.batch(query, &batch, session).map(ApiHandlerReply::new),
+            ApiQueryType::Cursor(ApiQueryCursor::Open(open)) => self.database(&open.query.database)?.cursor(query, &open, session),
+            ApiQueryType::Cursor(ApiQueryCursor::Next(id)) => session.cursors.next(&id)
+                .map(|(rows, cursor)| ApiHandlerReply::new(rows).with_cursor(cursor)),
+            ApiQueryType::Cursor(ApiQueryCursor::Close(id)) => session.cursors.close(&id)
+                .map(|cursor| ApiHandlerReply::default().with_cursor(cursor)),
+            ApiQueryType::Subscribe(subscribe) => self.database(&subscribe.database)?.subscribe(query, &subscribe, session).map(ApiHandlerReply::new),
+            other => {
+                let details = format!("SqlHandler.handle | Query '{}' isn't the database query", other.name().value());
+                log::warn!("{}", details);
+                Err(ApiError::new(format!("API SQL Service - query of type '{}' not supported", other.name().value()), details))
+            }
+        }
+    }
+}
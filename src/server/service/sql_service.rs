@@ -0,0 +1,44 @@
+use crate::{
+    error::api_error::ApiError,
+    server::{
+        api_query::{
+            api_query::ApiQuery, api_query_batch::ApiQueryBatch, api_query_cursor::ApiQueryCursorOpen,
+            api_query_sql::ApiQuerySql, api_query_subscribe::ApiQuerySubscribe, row_map::RowMap,
+        },
+        api_server::{api_handler::ApiHandlerReply, api_session::ApiSession},
+    },
+};
+///
+/// The database, replying the queries of the [SqlHandler](crate::server::service::sql_handler::SqlHandler)
+/// - `query` - the whole query, it's `authToken` & `id` identify the job to be cancelled
+/// - Only `sql` must be implemented, other queries are not supported by default
+pub trait SqlService: Send + Sync {
+    ///
+    /// Returns the rows of the `sql` query
+    fn sql(&self, query: &ApiQuery, sql: &ApiQuerySql, session: &mut ApiSession) -> Result<Vec<RowMap>, ApiError>;
+    ///
+    /// Returns the result sets of the `batch` statements executed in the single transaction
+    fn batch(&self, query: &ApiQuery, batch: &ApiQueryBatch, session: &mut ApiSession) -> Result<Vec<RowMap>, ApiError> {
+        _ = (query, session);
+        Err(not_supported("batch", &batch.database))
+    }
+    ///
+    /// Returns the first page of the cursor opened in the `session`
+    fn cursor(&self, query: &ApiQuery, open: &ApiQueryCursorOpen, session: &mut ApiSession) -> Result<ApiHandlerReply, ApiError> {
+        _ = (query, session);
+        Err(not_supported("cursor", &open.query.database))
+    }
+    ///
+    /// Starts forwarding the notifications of the `subscribe` channels into the `session`
+    fn subscribe(&self, query: &ApiQuery, subscribe: &ApiQuerySubscribe, session: &mut ApiSession) -> Result<Vec<RowMap>, ApiError> {
+        _ = (query, session);
+        Err(not_supported("subscribe", &subscribe.database))
+    }
+}
+///
+/// Returns the error of the query not supported by the `database`
+fn not_supported(kind: &str, database: &str) -> ApiError {
+    let details = format!("SqlService | Query '{}' isn't supported by the database '{}'", kind, database);
+    log::warn!("{}", details);
+    ApiError::new(format!("API SQL Service - query of type '{}' not supported by the database '{}'", kind, database), details)
+}
@@ -0,0 +1,2 @@
+pub mod sqlite_service;
+pub mod sqlite_value;
\ No newline at end of file
@@ -0,0 +1,113 @@
+use std::{sync::Mutex, time::Duration};
+use rusqlite::{params_from_iter, Connection, ErrorCode, OpenFlags};
+use sal_core::{dbg::Dbg, error::Error};
+use crate::{
+    error::api_error::ApiError,
+    server::{
+        api_query::{api_query::ApiQuery, api_query_sql::ApiQuerySql, row_map::RowMap, sql_lexer::SqlDialect},
+        api_server::api_session::ApiSession,
+        config::{null_defaults::NullDefaults, service_config::ServiceConfig},
+        service::sql_service::SqlService,
+        sqlite::sqlite_value::SqliteValue,
+    },
+};
+///
+/// The sqlite database service
+/// - Opens the existing file of the [ServiceConfig] `path`, for reading only if it's `readOnly`
+/// - The queries are executed one by one over the single connection,
+///   the queries of the user granted reading only - over the separate connection opened for reading only
+/// - Placeholders: `?` / `?n` with the positional params, `:name` with the named ones
+/// - The running statement is interrupted by the `cancel` query
+/// - Column values converted into json by [SqliteValue], the order of the columns is kept,
+///   the nulls are replaced by [NullDefaults] if `replaceNullWithDefault` specified
+pub struct SqliteService {
+    dbg: Dbg,
+    connection: Mutex<Connection>,
+    reader: Option<Mutex<Connection>>,
+    nulls: Option<NullDefaults>,
+}
+//
+//
+impl SqliteService {
+    ///
+    /// The time the locked database is waited for
+    const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+    ///
+    /// Returns [SqliteService] of the configured database
+    pub fn new(conf: &ServiceConfig) -> Result<Self, Error> {
+        let dbg = Dbg::own(format!("SqliteService({})", conf.name));
+        let error = Error::new(&dbg, "new");
+        let connection = Self::open(&conf.path, conf.read_only).map_err(|err| error.pass(err))?;
+        // The connection of the service opened for reading only is already read only
+        let reader = match conf.read_only {
+            true => None,
+            false => Some(Mutex::new(Self::open(&conf.path, true).map_err(|err| error.pass(err))?)),
+        };
+        log::debug!("{}.new | Opened '{}', read only: {}", dbg, conf.path, conf.read_only);
+        Ok(Self { dbg, connection: Mutex::new(connection), reader, nulls: conf.replace_null_with_default.clone() })
+    }
+    ///
+    /// Returns the connection to the existing database file, for reading only if `read_only`
+    fn open(path: &str, read_only: bool) -> Result<Connection, Error> {
+        let error = Error::new("SqliteService", "open");
+        let mode = match read_only {
+            true => OpenFlags::SQLITE_OPEN_READ_ONLY,
+            false => OpenFlags::SQLITE_OPEN_READ_WRITE,
+        };
+        let connection = Connection::open_with_flags(path, mode | OpenFlags::SQLITE_OPEN_NO_MUTEX)
+            .map_err(|err| error.pass_with(format!("Open '{}' error", path), err.to_string()))?;
+        connection.busy_timeout(Self::BUSY_TIMEOUT).map_err(|err| error.pass_with("Busy timeout error", err.to_string()))?;
+        Ok(connection)
+    }
+    ///
+    /// Returns the connection the query of the `session` executed over,
+    /// the read only one if the user of the query is granted reading only
+    fn connection(&self, session: &ApiSession) -> &Mutex<Connection> {
+        match &self.reader {
+            Some(reader) if session.is_read_only() => reader,
+            _ => &self.connection,
+        }
+    }
+    ///
+    /// Returns [ApiError] of the failed query
+    fn error(&self, method: &str, err: rusqlite::Error) -> ApiError {
+        let message = match err.sqlite_error_code() {
+            Some(ErrorCode::OperationInterrupted) => "API SQL Service - query cancelled",
+            _ => "API SQL Service - query error",
+        };
+        let details = format!("{}.{} | {}", self.dbg, method, err);
+        log::warn!("{}", details);
+        ApiError::new(message, details)
+    }
+}
+//
+//
+impl SqlService for SqliteService {
+    fn sql(&self, query: &ApiQuery, sql: &ApiQuerySql, session: &mut ApiSession) -> Result<Vec<RowMap>, ApiError> {
+        let (statement, params) = sql.bind(SqlDialect::Sqlite)?;
+        let connection = self.connection(session).lock().map_err(|err| {
+            let details = format!("{}.sql | Lock error: {}", self.dbg, err);
+            log::warn!("{}", details);
+            ApiError::new("API SQL Service - database connection error", details)
+        })?;
+        let interrupt = connection.get_interrupt_handle();
+        let _job = session.cancel_registry().register(&query.auth_token(), &query.id(), Box::new(move || {
+            interrupt.interrupt();
+            Ok(())
+        }));
+        let mut statement = connection.prepare(&statement).map_err(|err| self.error("sql", err))?;
+        let columns: Vec<(String, Option<String>)> = statement.columns().iter()
+            .map(|column| (column.name().to_owned(), column.decl_type().map(str::to_owned)))
+            .collect();
+        let mut rows = statement.query(params_from_iter(params.iter())).map_err(|err| self.error("sql", err))?;
+        let mut result = vec![];
+        while let Some(row) = rows.next().map_err(|err| self.error("sql", err))? {
+            let mut row_map = SqliteValue::row(row, &columns)?;
+            if let Some(nulls) = &self.nulls {
+                nulls.apply(&mut row_map, columns.iter().map(|(name, decl_type)| (name.as_str(), SqliteValue::kind(decl_type.as_deref()))));
+            }
+            result.push(row_map);
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,90 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rusqlite::{types::{ToSqlOutput, Value, ValueRef}, Row, ToSql};
+use crate::{error::api_error::ApiError, server::api_query::{api_query_sql_params::SqlParam, column_kind::ColumnKind, row_map::RowMap}};
+///
+/// The value of the sqlite column converted into json, the same way as the postgres one
+/// - `INTEGER` - number, boolean if the column declared `BOOL` / `BOOLEAN`
+/// - `REAL` - number, `NaN` - null
+/// - `TEXT` - string, json as is if the column declared `JSON` / `JSONB`
+/// - `BLOB` - base64 string
+/// - Dates & times are stored by sqlite as the text or the number, returned as is
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqliteValue(pub serde_json::Value);
+//
+//
+impl SqliteValue {
+    ///
+    /// Returns the `value` of the column of the declared type `decl_type`
+    pub fn new(value: ValueRef, decl_type: Option<&str>) -> Result<Self, String> {
+        let decl_type = decl_type.unwrap_or_default().to_uppercase();
+        let value = match value {
+            ValueRef::Null => serde_json::Value::Null,
+            ValueRef::Integer(value) => match decl_type.as_str() {
+                "BOOL" | "BOOLEAN" => serde_json::Value::Bool(value != 0),
+                _ => serde_json::Value::from(value),
+            },
+            ValueRef::Real(value) => serde_json::Number::from_f64(value).map_or(serde_json::Value::Null, serde_json::Value::Number),
+            ValueRef::Text(value) => {
+                let value = std::str::from_utf8(value).map_err(|err| format!("invalid utf8 text: {}", err))?;
+                match decl_type.as_str() {
+                    "JSON" | "JSONB" => serde_json::from_str(value).map_err(|err| format!("invalid json: {}", err))?,
+                    _ => serde_json::Value::String(value.to_owned()),
+                }
+            }
+            ValueRef::Blob(value) => serde_json::Value::String(STANDARD.encode(value)),
+        };
+        Ok(Self(value))
+    }
+    ///
+    /// Returns the `row` converted into [RowMap], in the order of the columns
+    /// - `columns` - the names & the declared types of the columns
+    pub fn row(row: &Row, columns: &[(String, Option<String>)]) -> Result<RowMap, ApiError> {
+        columns.iter().enumerate().map(|(index, (name, decl_type))| {
+            row.get_ref(index)
+                .map_err(|err| err.to_string())
+                .and_then(|value| Self::new(value, decl_type.as_deref()))
+                .map(|value| (name.to_owned(), value.0))
+                .map_err(|err| {
+                    let details = format!("SqliteValue.row | Column '{}' of type '{}' error: {}", name, decl_type.as_deref().unwrap_or_default(), err);
+                    log::warn!("{}", details);
+                    ApiError::new(format!("API SQL Service - unable to read column '{}'", name), details)
+                })
+        }).collect()
+    }
+    ///
+    /// Returns the [ColumnKind] of the declared type `decl_type`, by the sqlite type affinity rules
+    /// - `BOOL` / `BOOLEAN` - bool, `DATETIME` / `TIMESTAMP` - timestamp, `JSON` / `JSONB` / `DATE` / `TIME` - other
+    /// - Containing `INT` / `REAL` / `FLOA` / `DOUB` / `NUM` / `DEC` - number, `CHAR` / `CLOB` / `TEXT` - text
+    /// - Not declared, the expression or `BLOB` - other
+    pub fn kind(decl_type: Option<&str>) -> ColumnKind {
+        let decl_type = decl_type.unwrap_or_default().to_uppercase();
+        let contains = |parts: &[&str]| parts.iter().any(|part| decl_type.contains(part));
+        match decl_type.as_str() {
+            "BOOL" | "BOOLEAN" => ColumnKind::Bool,
+            "DATETIME" | "TIMESTAMP" => ColumnKind::Timestamp,
+            "JSON" | "JSONB" | "DATE" | "TIME" => ColumnKind::Other,
+            _ if contains(&["INT"]) => ColumnKind::Number,
+            _ if contains(&["CHAR", "CLOB", "TEXT"]) => ColumnKind::Text,
+            _ if contains(&["REAL", "FLOA", "DOUB", "NUM", "DEC"]) => ColumnKind::Number,
+            _ => ColumnKind::Other,
+        }
+    }
+}
+//
+// Binding the json parameter to the sqlite statement
+// - null - NULL, bool - INTEGER 0 / 1, number - INTEGER / REAL, string - TEXT, array & object - json TEXT
+impl ToSql for SqlParam {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let value = match &self.0 {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(value) => Value::Integer(*value as i64),
+            serde_json::Value::Number(value) => match value.as_i64() {
+                Some(value) => Value::Integer(value),
+                None => Value::Real(value.as_f64().unwrap_or(f64::NAN)),
+            },
+            serde_json::Value::String(value) => return Ok(ToSqlOutput::Borrowed(ValueRef::Text(value.as_bytes()))),
+            value => Value::Text(value.to_string()),
+        };
+        Ok(ToSqlOutput::Owned(value))
+    }
+}
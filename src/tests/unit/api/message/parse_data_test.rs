@@ -139,9 +139,14 @@ mod parse_data {
                     }
                 }
             }
-            message.reset();
             assert!(result_bytes == target_bytes, "step: {} \nresult: {:?}\ntarget: {:?}", step, result_bytes, target_bytes);
         }
+        // The beginning of the next message received together with the previous one is dropped by the reset
+        message.parse([to_bytes("1", 7), to_bytes("2", 8)[..8].to_vec()].concat()).unwrap();
+        message.reset();
+        let result = message.parse(to_bytes("3", 9)).map(|(id, _, _, bytes)| (id, bytes)).ok();
+        let target = Some((FieldId(9), vec![51]));
+        assert!(result == target, "step: reset \nresult: {:?}\ntarget: {:?}", result, target);
         test_duration.exit();
     }
     ///
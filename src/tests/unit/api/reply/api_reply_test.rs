@@ -2,6 +2,8 @@
 
 mod api_reply {
     use std::{sync::Once, time::Duration};
+    use indexmap::IndexMap;
+    use serde::Deserialize;
     use serde_json::json;
     use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
     use testing::stuff::max_test_duration::TestDuration;
@@ -45,6 +47,7 @@ mod api_reply {
                     data: vec![],
                     keep_alive: service_keep_alive,
                     error: ApiError::empty(),
+                    cursor: None,
                 },
                 r#"{"authToken":"123zxy456!@#","id":"1","keepAlive":false,"query":"{\"database\":\"test_api_query\",\"sql\":\"select * from customer limit 3;\"}","data":[],"error":{"message":""}}"#,
             ),
@@ -104,6 +107,7 @@ mod api_reply {
                     query: String::new(), 
                     data: vec![], 
                     error: ApiError::empty(),
+                    cursor: None,
                 },
             ),
             (
@@ -115,6 +119,7 @@ mod api_reply {
                     query: String::new(), 
                     data: vec![], 
                     error: ApiError::empty(),
+                    cursor: None,
                 },
             ),
         ];
@@ -126,4 +131,69 @@ mod api_reply {
         }
         test_duration.exit();
     }
+    ///
+    /// 
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Customer {
+        id: i64,
+        name: String,
+        #[serde(default)]
+        email: Option<String>,
+    }
+    ///
+    /// 
+    #[test]
+    fn data_as() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let self_id = "test ApiReply.data_as";
+        println!("{}", self_id);
+        let test_duration = TestDuration::new(self_id, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let reply = |data: Vec<serde_json::Value>| ApiReply::new(
+            "authToken", "1", false, "",
+            data.into_iter().map(|row| serde_json::from_value::<IndexMap<String, serde_json::Value>>(row).unwrap()).collect(),
+        );
+        let test_data: [(i32, ApiReply, Result<Vec<Customer>, &str>); 4] = [
+            (
+                1,
+                reply(vec![]),
+                Ok(vec![]),
+            ),
+            (
+                2,
+                reply(vec![
+                    json!({"id": 1, "name": "Alex", "email": "alex@mail.com"}),
+                    json!({"id": 2, "name": "Bob", "phone": "+123"}),
+                ]),
+                Ok(vec![
+                    Customer { id: 1, name: "Alex".to_owned(), email: Some("alex@mail.com".to_owned()) },
+                    Customer { id: 2, name: "Bob".to_owned(), email: None },
+                ]),
+            ),
+            (
+                3,
+                reply(vec![
+                    json!({"id": 1, "name": "Alex"}),
+                    json!({"id": 2}),
+                ]),
+                Err("ApiReply.data_as | Row 1: missing field `name`"),
+            ),
+            (
+                4,
+                reply(vec![
+                    json!({"id": "1", "name": "Alex"}),
+                ]),
+                Err("ApiReply.data_as | Row 0, column 'id': invalid type: string \"1\", expected i64"),
+            ),
+        ];
+        for (step, reply, target) in test_data {
+            let result = reply.data_as::<Customer>();
+            let target = target.map_err(|err| err.to_owned());
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
 }
@@ -0,0 +1,85 @@
+#[cfg(test)]
+
+mod cli_query {
+    use std::sync::Once;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{
+        api::query::{
+            api_query_executable::ApiQueryExecutable, api_query_kind::ApiQueryKind, api_query_python::ApiQueryPython,
+            api_query_sql::ApiQuerySql, api_query_sql_params::ApiQuerySqlParams,
+        },
+        cli::{cli_query::CliQuery, reply_format::ReplyFormat, repl::ReplCommand},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns json object
+    fn object(value: serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+        value.as_object().unwrap().clone()
+    }
+    ///
+    /// Testing [CliQuery] builds the query from the arguments & json
+    #[test]
+    fn query() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let test_data = [
+            (1, CliQuery::sql("database", None, "select 1;"), Some(ApiQueryKind::Sql(ApiQuerySql::new("database", "select 1;")))),
+            (2, CliQuery::sql("database", Some("[1, \"a\"]"), "select $1, $2;"), Some(ApiQueryKind::Sql(
+                ApiQuerySql::new("database", "select $1, $2;").with_params(ApiQuerySqlParams::Positional(vec![json!(1), json!("a")]))
+            ))),
+            (3, CliQuery::sql("database", Some("1"), "select $1;"), None),
+            (4, CliQuery::python("script", "{\"a\": 4}"), Some(ApiQueryKind::Python(ApiQueryPython::new("script", object(json!({"a": 4})))))),
+            (5, CliQuery::python("script", "[4]"), None),
+            (6, CliQuery::executable("app", "{}"), Some(ApiQueryKind::Executable(ApiQueryExecutable::new("app", object(json!({})))))),
+            (7, CliQuery::json(r#"{"authToken":"token","id":"7","keepAlive":true,"debug":false,"sql":{"database":"database","sql":"select 1;"}}"#),
+                Some(ApiQueryKind::Sql(ApiQuerySql::new("database", "select 1;")))),
+            (8, CliQuery::json(r#"{"unknown":{}}"#), None),
+            (9, CliQuery::json("not a json"), None),
+        ];
+        for (step, result, target) in test_data {
+            assert!(result.clone().ok() == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+    }
+    ///
+    /// Testing [ReplCommand::parse]
+    #[test]
+    fn repl_command() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let test_data = [
+            (1, "", Some(ReplCommand::Empty)),
+            (2, "  sql database  select * from event where name = 'a b';", Some(ReplCommand::Query(ApiQueryKind::Sql(
+                ApiQuerySql::new("database", "select * from event where name = 'a b';")
+            )))),
+            (3, "sql database", None),
+            (4, "python script {\"a\": 4}", Some(ReplCommand::Query(ApiQueryKind::Python(ApiQueryPython::new("script", object(json!({"a": 4}))))))),
+            (5, "executable app", Some(ReplCommand::Query(ApiQueryKind::Executable(ApiQueryExecutable::new("app", object(json!({}))))))),
+            (6, r#"{"sql":{"database":"database","sql":"select 1;"}}"#, Some(ReplCommand::Query(ApiQueryKind::Sql(ApiQuerySql::new("database", "select 1;"))))),
+            (7, ".format csv", Some(ReplCommand::Format(ReplyFormat::Csv))),
+            (8, ".format xml", None),
+            (9, ".help", Some(ReplCommand::Help)),
+            (10, ".exit", Some(ReplCommand::Exit)),
+            (11, "select 1;", None),
+        ];
+        for (step, line, target) in test_data {
+            let result = ReplCommand::parse(line);
+            assert!(result.as_ref().ok() == target.as_ref(), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+    }
+}
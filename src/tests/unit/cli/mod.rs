@@ -0,0 +1,2 @@
+mod cli_query_test;
+mod reply_format_test;
\ No newline at end of file
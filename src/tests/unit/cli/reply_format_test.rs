@@ -0,0 +1,59 @@
+#[cfg(test)]
+
+mod reply_format {
+    use std::sync::Once;
+    use indexmap::IndexMap;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::cli::reply_format::ReplyFormat;
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the rows of the reply
+    fn rows() -> Vec<IndexMap<String, serde_json::Value>> {
+        vec![
+            serde_json::from_value(json!({"id": 1, "name": "Markus", "role": null})).unwrap(),
+            serde_json::from_value(json!({"id": 22, "name": "Dan, \"the\" one", "tags": ["a"]})).unwrap(),
+        ]
+    }
+    ///
+    /// Testing [ReplyFormat::format]
+    #[test]
+    fn format() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let test_data = [
+            (1, ReplyFormat::Table, rows(), [
+                "id | name           | role | tags",
+                "---+----------------+------+------",
+                "1  | Markus         |      |",
+                "22 | Dan, \"the\" one |      | [\"a\"]",
+                "(2 rows)",
+            ].join("\n")),
+            (2, ReplyFormat::Table, vec![], "(0 rows)".to_owned()),
+            (3, ReplyFormat::Csv, rows(), [
+                "id,name,role,tags",
+                "1,Markus,,",
+                "22,\"Dan, \"\"the\"\" one\",,\"[\"\"a\"\"]\"",
+            ].join("\n")),
+            (4, ReplyFormat::Json, rows()[..1].to_vec(), serde_json::to_string_pretty(&json!([{"id": 1, "name": "Markus", "role": null}])).unwrap()),
+        ];
+        for (step, format, rows, target) in test_data {
+            let result = format.format(&rows);
+            assert!(result == target, "step {} \nresult:\n{}\ntarget:\n{}", step, result, target);
+        }
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+
+mod api_call {
+    use std::{collections::BTreeMap, sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::{
+        client::{api_call::ApiCall, api_query::{ApiQuery, ApiQueryKind, ApiQuerySql}, api_request::ApiRequest},
+        mock::{mock_api_server::MockApiServer, mock_expectation::MockExpectation},
+    };
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Params of the test script
+    #[derive(Debug, Serialize)]
+    struct Params {
+        a: i64,
+        b: i64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    }
+    ///
+    /// Output row of the test script
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Sum {
+        sum: i64,
+    }
+    ///
+    /// Testing the params checked to be serialized into the object
+    #[test]
+    fn params() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiCall.params");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let test_data = [
+            (01, ApiCall::<Sum>::python("py-sum", &Params { a: 4, b: 7, name: None }).map(|call| call.query().clone()), Some(json!({"python": {"script": "py-sum", "params": {"a": 4, "b": 7}}}))),
+            (02, ApiCall::<Sum>::executable("sum", &Params { a: 1, b: 2, name: Some("n".to_owned()) }).map(|call| call.query().clone()), Some(json!({"executable": {"name": "sum", "params": {"a": 1, "b": 2, "name": "n"}}}))),
+            (03, ApiCall::<Sum>::python("py-sum", &BTreeMap::from([("a", 1)])).map(|call| call.query().clone()), Some(json!({"python": {"script": "py-sum", "params": {"a": 1}}}))),
+            (04, ApiCall::<Sum>::python("py-sum", &json!({})).map(|call| call.query().clone()), Some(json!({"python": {"script": "py-sum", "params": {}}}))),
+            (05, ApiCall::<Sum>::python("py-sum", &vec![1, 2]).map(|call| call.query().clone()), None),
+            (06, ApiCall::<Sum>::executable("sum", &12).map(|call| call.query().clone()), None),
+            (07, ApiCall::<Sum>::executable("sum", &"params").map(|call| call.query().clone()), None),
+            (08, ApiCall::<Sum>::python("py-sum", &()).map(|call| call.query().clone()), None),
+        ];
+        for (step, result, target) in test_data {
+            let result: Option<serde_json::Value> = result.ok().map(|query: ApiQuery| serde_json::to_value(query.query).unwrap());
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the script output deserialized into the typed reply
+    #[test]
+    fn call() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiCall.call");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let server = MockApiServer::start(&dbg).unwrap();
+        let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("", "")), true);
+        let mut request = ApiRequest::new(&dbg, server.address(), "token", query, true, false);
+        let call = ApiCall::<Sum>::python("py-sum", &Params { a: 4, b: 7, name: None }).unwrap();
+        // (step, script output, target)
+        let test_data = [
+            (1, json!([{"sum": 11}]), Some(vec![Sum { sum: 11 }])),
+            (2, json!([{"sum": 11}, {"sum": 12}]), Some(vec![Sum { sum: 11 }, Sum { sum: 12 }])),
+            (3, json!([]), Some(vec![])),
+            (4, json!([{"sum": "11"}]), None),
+        ];
+        for (step, output, target) in test_data {
+            server.expect(MockExpectation::json(output));
+            let result = request.call(&call, true).ok();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            let result = server.received().pop().unwrap()["python"].clone();
+            let target = json!({"script": "py-sum", "params": {"a": 4, "b": 7}});
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+}
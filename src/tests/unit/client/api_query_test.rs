@@ -2,10 +2,10 @@
 
 mod tests {
     use log::{debug, info};
-    use std::{collections::HashMap, sync::Once};
+    use std::sync::Once;
     use serde_json::json;
     use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
-    use crate::client::api_query::{ApiQuery, ApiQueryExecutable, ApiQueryKind, ApiQueryPython, ApiQuerySql};
+    use crate::client::api_query::{ApiQuery, ApiQueryBatch, ApiQueryBatchStatement, ApiQueryCancel, ApiQueryCursor, ApiQueryCursorOpen, ApiQueryExecutable, ApiQueryKind, ApiQueryLogin, ApiQueryPython, ApiQuerySql, ApiQuerySqlParams, ApiQuerySubscribe};
     
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     // use super::*;
@@ -42,6 +42,7 @@ mod tests {
                     ApiQueryKind::Sql(ApiQuerySql { 
                         database: "database1".to_string(), 
                         sql: "Some valid sql query1".to_string(), 
+                        params: ApiQuerySqlParams::default(),
                     }),
                     true,
                 ),
@@ -52,6 +53,7 @@ mod tests {
                     ApiQueryKind::Sql(ApiQuerySql { 
                         database: "database2".to_string(), 
                         sql: "Some valid sql query2".to_string(), 
+                        params: ApiQuerySqlParams::default(),
                     }),
                     true,
                 ),
@@ -61,7 +63,7 @@ mod tests {
                 ApiQuery::new(
                     ApiQueryKind::Python(ApiQueryPython { 
                         script: "python_script".to_string(), 
-                        params: json!(HashMap::<String, f64>::new()), 
+                        params: serde_json::Map::new(), 
                     }),
                     true,
                 ),
@@ -71,15 +73,77 @@ mod tests {
                 ApiQuery::new(
                     ApiQueryKind::Executable(ApiQueryExecutable { 
                         name: "executable_name".to_string(), 
-                        params: json!(HashMap::<String, f64>::new()), 
+                        params: serde_json::Map::new(), 
                     }),
                     true,
                 ),
                 r#"{"query":{"executable":{"name":"executable_name","params":{}}}}"#
             ),
+            (
+                ApiQuery::new(
+                    ApiQueryKind::Sql(
+                        ApiQuerySql::new("database3", "select * from customer where id = $1 and name = $2;")
+                            .with_params(ApiQuerySqlParams::Positional(vec![json!(3), json!("Alex")])),
+                    ),
+                    true,
+                ),
+                r#"{"query":{"sql":{"database":"database3","sql":"select * from customer where id = $1 and name = $2;","params":[3,"Alex"]}}}"#
+            ),
+            (
+                ApiQuery::new(
+                    ApiQueryKind::Sql(
+                        ApiQuerySql::new("database4", "select * from customer where id = :id;")
+                            .with_params(ApiQuerySqlParams::Named(json!({"id": 4}).as_object().unwrap().to_owned())),
+                    ),
+                    true,
+                ),
+                r#"{"query":{"sql":{"database":"database4","sql":"select * from customer where id = :id;","params":{"id":4}}}}"#
+            ),
+            (
+                ApiQuery::new(
+                    ApiQueryKind::Batch(ApiQueryBatch::new("database5", vec![
+                        ApiQueryBatchStatement::new("insert into header (name) values ($1);")
+                            .with_params(ApiQuerySqlParams::Positional(vec![json!("order")])),
+                        ApiQueryBatchStatement::new("delete from line;"),
+                    ])),
+                    true,
+                ),
+                r#"{"query":{"batch":{"database":"database5","statements":[{"sql":"insert into header (name) values ($1);","params":["order"]},{"sql":"delete from line;"}]}}}"#
+            ),
+            (
+                ApiQuery::new(
+                    ApiQueryKind::Cursor(ApiQueryCursor::Open(ApiQueryCursorOpen::new(
+                        ApiQuerySql::new("database6", "select * from event where id > $1;")
+                            .with_params(ApiQuerySqlParams::Positional(vec![json!(6)])),
+                        100,
+                    ))),
+                    true,
+                ),
+                r#"{"query":{"cursor":{"open":{"database":"database6","sql":"select * from event where id > $1;","params":[6],"pageSize":100}}}}"#
+            ),
+            (
+                ApiQuery::new(ApiQueryKind::Cursor(ApiQueryCursor::Next("7".to_owned())), true),
+                r#"{"query":{"cursor":{"next":"7"}}}"#
+            ),
+            (
+                ApiQuery::new(ApiQueryKind::Cursor(ApiQueryCursor::Close("8".to_owned())), true),
+                r#"{"query":{"cursor":{"close":"8"}}}"#
+            ),
+            (
+                ApiQuery::new(ApiQueryKind::Cancel(ApiQueryCancel::new("9")), true),
+                r#"{"query":{"cancel":{"id":"9"}}}"#
+            ),
+            (
+                ApiQuery::new(ApiQueryKind::Subscribe(ApiQuerySubscribe::new("database10", &["event", "alarm"])), true),
+                r#"{"query":{"subscribe":{"database":"database10","channels":["event","alarm"]}}}"#
+            ),
+            (
+                ApiQuery::new(ApiQueryKind::Login(ApiQueryLogin::new("user", "pass")), true),
+                r#"{"query":{"login":{"user":"user","pass":"pass"}}}"#
+            ),
         ];
         for (query, target) in test_data {
-            let result = match serde_json::to_string(&query) {
+            let json = match serde_json::to_string(&query) {
                 Ok(query) => {
                     debug!("query json: {:?}", query);
                     query
@@ -89,10 +153,13 @@ mod tests {
                     panic!("{}", message);
                 },
             };
-            let result: serde_json::Value = serde_json::from_str(&result).unwrap();
+            let result: serde_json::Value = serde_json::from_str(&json).unwrap();
             let target: serde_json::Value = serde_json::from_str(target).unwrap();
             println!("\n result: {:?}\n target: {:?}", result, target);
             assert!(result == target, "\n result: {:?}\n target: {:?}", result, target);
+            // Round trip
+            let result: ApiQuery = serde_json::from_str(&json).unwrap();
+            assert!(result.query == query.query, "\n result: {:?}\n target: {:?}", result.query, query.query);
         }
     }
 }
@@ -0,0 +1,149 @@
+#[cfg(test)]
+
+mod api_request_builder {
+    use std::{io::{Read, Write}, net::TcpListener, sync::{mpsc, Once}, thread, time::Duration};
+    use sal_core::dbg::Dbg;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::{session::test_session::TestSession, stuff::max_test_duration::TestDuration};
+    use crate::{
+        api::message::{api_framing::ApiFraming, message_kind::MessageKind},
+        client::{api_query::{ApiQuery, ApiQueryKind, ApiQuerySql}, api_request::ApiRequest},
+        mock::{mock_api_server::MockApiServer, mock_expectation::MockExpectation},
+    };
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing the invalid configuration rejected by `build`
+    #[test]
+    fn build_invalid() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiRequestBuilder.build_invalid");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let test_data = [
+            (01, ApiRequest::builder(&dbg), "Address is not specified"),
+            (02, ApiRequest::builder(&dbg).address("invalid address"), "Address"),
+            (03, ApiRequest::builder(&dbg).address("127.0.0.1:8080").timeout(Duration::ZERO), "Timeout must be greater than zero"),
+            (04, ApiRequest::builder(&dbg).address("127.0.0.1:8080").framing(ApiFraming::new(22, MessageKind::F64)), "not supported"),
+        ];
+        for (step, builder, target) in test_data {
+            let result = builder.build();
+            assert!(result.is_err(), "step {} \nresult: {:?}\ntarget: Err", step, result.is_ok());
+            let result = result.err().unwrap().to_string();
+            assert!(result.contains(target), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the request built with all the parameters specified
+    #[test]
+    fn build_fetch() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiRequestBuilder.build_fetch");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let server = MockApiServer::start(&dbg).unwrap();
+        let token = "123zxy456!@#";
+        let rows = json!([{"id": 1, "name": "Markus"}]);
+        let mut request = ApiRequest::builder(&dbg)
+            .address(server.address())
+            .auth_token(token)
+            .query(ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "select * from customer;")), true))
+            .keep_alive(true)
+            .debug(true)
+            .timeout(Duration::from_secs(3))
+            .framing(ApiFraming::default())
+            .build()
+            .unwrap();
+        server.expect(MockExpectation::json(rows.clone()));
+        let reply = request.fetch_reply(true).unwrap();
+        let result = json!(reply.data);
+        assert!(result == rows, "\nresult: {:?}\ntarget: {:?}", result, rows);
+        let mut result = server.received().pop().unwrap();
+        // The session part of the id is random, checking the counter only
+        result["id"] = json!(result["id"].as_str().and_then(|id| id.split_once('-')).map(|(_, counter)| counter));
+        let target = json!({"authToken": token, "id": "1", "sql": {"database": "database", "sql": "select * from customer;"}, "keepAlive": true, "debug": true});
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+    ///
+    /// Testing the [CancelHandle] sends the cancel query with the framing of the request
+    #[test]
+    fn cancel_framing() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiRequestBuilder.cancel_framing");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let framing = ApiFraming::new(33, MessageKind::String);
+        let addr = format!("127.0.0.1:{}", TestSession::free_tcp_port_str());
+        let listener = TcpListener::bind(&addr).unwrap();
+        let (queries_send, queries_recv) = mpsc::channel();
+        let server_framing = framing.clone();
+        // The cancel query comes over the separate connection, while the request connection is kept alive
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let (queries_send, framing) = (queries_send.clone(), server_framing.clone());
+                thread::spawn(move || {
+                    let mut header = [0; 10];
+                    while stream.read_exact(&mut header).is_ok() {
+                        let size = u32::from_be_bytes(header[6..10].try_into().unwrap()) as usize;
+                        let mut data = vec![0; size];
+                        stream.read_exact(&mut data).unwrap();
+                        let query: serde_json::Value = serde_json::from_slice(&data).unwrap();
+                        let data = match query["cancel"].is_object() {
+                            true => json!([{"cancelled": true}]),
+                            false => json!([]),
+                        };
+                        queries_send.send((header[0], header[5], query.clone())).unwrap();
+                        let reply = json!({"authToken": query["authToken"], "id": query["id"], "keepAlive": query["keepAlive"], "query": "", "data": data, "error": {"message": ""}});
+                        stream.write_all(&framing.frame(1, reply.to_string().as_bytes())).unwrap();
+                    }
+                });
+            }
+        });
+        let mut request = ApiRequest::builder(&dbg)
+            .address(&addr)
+            .auth_token("token")
+            .query(ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "select 1;")), true))
+            .timeout(Duration::from_secs(3))
+            .framing(framing.clone())
+            .build()
+            .unwrap();
+        request.fetch_reply(true).unwrap();
+        let result = request.cancel_handle().cancel().unwrap();
+        assert!(result, "\nresult: {:?}\ntarget: {:?}", result, true);
+        let queries: Vec<(u8, u8, serde_json::Value)> = queries_recv.try_iter().collect();
+        let result: Vec<(u8, u8)> = queries.iter().map(|(syn, kind, _)| (*syn, *kind)).collect();
+        let target = vec![(33, MessageKind::String as u8); 2];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = &queries[1].2["cancel"]["id"];
+        let target = &queries[0].2["id"];
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        test_duration.exit();
+    }
+}
@@ -0,0 +1,184 @@
+#[cfg(test)]
+
+mod api_request_mock {
+    use std::{sync::Once, time::{Duration, Instant}};
+    use sal_core::dbg::Dbg;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::{
+        client::{api_query::{ApiQuery, ApiQueryExecutable, ApiQueryKind, ApiQueryPython, ApiQuerySql}, api_request::ApiRequest, reply_cache::ReplyCache},
+        error::api_error::ApiError,
+        mock::{mock_api_server::MockApiServer, mock_expectation::MockExpectation},
+    };
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing the requests of all kinds & the replies, with and without debug
+    #[test]
+    fn fetch() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiRequest.mock.fetch");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let token = "123zxy456!@#";
+        for debug in [false, true] {
+            let server = MockApiServer::start(&dbg).unwrap();
+            let rows = json!([{"id": 1, "name": "Markus"}, {"id": 2, "name": "Daniel"}]);
+            let test_data = [
+                (
+                    ApiQueryKind::Sql(ApiQuerySql::new("database", "select * from customer;")),
+                    true,
+                    json!({"authToken": token, "id": "1", "sql": {"database": "database", "sql": "select * from customer;"}, "keepAlive": true, "debug": debug}),
+                ),
+                (
+                    ApiQueryKind::Python(ApiQueryPython::new("test_script", serde_json::Map::new())),
+                    true,
+                    json!({"authToken": token, "id": "2", "python": {"script": "test_script", "params": {}}, "keepAlive": true, "debug": debug}),
+                ),
+                (
+                    ApiQueryKind::Executable(ApiQueryExecutable::new("test_app", serde_json::Map::new())),
+                    false,
+                    json!({"authToken": token, "id": "3", "executable": {"name": "test_app", "params": {}}, "keepAlive": false, "debug": debug}),
+                ),
+            ];
+            let mut request = ApiRequest::new(
+                &dbg,
+                server.address(),
+                token,
+                ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("", "")), false),
+                true,
+                debug,
+            );
+            for (step, (query, keep_alive, target)) in test_data.into_iter().enumerate() {
+                server.expect(MockExpectation::json(rows.clone()));
+                let reply = request.fetch_reply_with(&ApiQuery::new(query, keep_alive), keep_alive).unwrap();
+                let result = json!(reply.data);
+                assert!(result == rows, "step {} debug {} \nresult: {:?}\ntarget: {:?}", step, debug, result, rows);
+                let result = reply.query.is_empty();
+                assert!(result != debug, "step {} debug {} \nresult: {:?}\ntarget: {:?}", step, debug, reply.query, debug);
+                let mut result = server.received().pop().unwrap();
+                // The session part of the id is random, checking the counter only
+                result["id"] = json!(result["id"].as_str().and_then(|id| id.split_once('-')).map(|(_, counter)| counter));
+                assert!(result == target, "step {} debug {} \nresult: {:?}\ntarget: {:?}", step, debug, result, target);
+            }
+            let result = server.pending();
+            assert!(result == 0, "debug {} \nresult: {:?}\ntarget: {:?}", debug, result, 0);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the error reply, the delay, the dropped connection & the unexpected query
+    #[test]
+    fn failures() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiRequest.mock.failures");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let server = MockApiServer::start(&dbg).unwrap();
+        let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "select 1;")), true);
+        let mut request = ApiRequest::new(&dbg, server.address(), "token", query, true, true)
+            .with_timeout(Duration::from_millis(500));
+        let python = ApiQuery::new(ApiQueryKind::Python(ApiQueryPython::new("script", serde_json::Map::new())), true);
+        // (step, expectation, query, is reply accepted, min duration, pending expectations after)
+        let test_data = [
+            (1, Some(MockExpectation::error(ApiError::new("message", "details"))), None, false, Duration::ZERO, 0),
+            (2, Some(MockExpectation::json(json!([{"id": 1}])).with_delay(Duration::from_millis(200))), None, true, Duration::from_millis(200), 0),
+            (3, Some(MockExpectation::json(json!([{"id": 1}])).with_delay(Duration::from_millis(1000))), None, false, Duration::from_millis(500), 0),
+            (4, Some(MockExpectation::drop()), None, false, Duration::ZERO, 0),
+            (5, Some(MockExpectation::json(json!([{"id": 1}]))), None, true, Duration::ZERO, 0),
+            (6, Some(MockExpectation::json(json!([])).when(|query| query["python"].is_object())), None, false, Duration::ZERO, 1),
+            (7, None, Some(python), true, Duration::ZERO, 0),
+            (8, None, None, false, Duration::ZERO, 0),
+        ];
+        for (step, expectation, query, target, min_duration, target_pending) in test_data {
+            if let Some(expectation) = expectation {
+                server.expect(expectation);
+            }
+            let time = Instant::now();
+            let result = match query {
+                Some(query) => request.fetch_reply_with(&query, true),
+                None => request.fetch_reply(true),
+            };
+            println!("step {} \nresult: {:?}", step, result);
+            assert!(result.is_ok() == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            assert!(time.elapsed() >= min_duration, "step {} \nresult: {:?}\ntarget: >= {:?}", step, time.elapsed(), min_duration);
+            let result = server.pending();
+            assert!(result == target_pending, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_pending);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the cached replies never touching the socket
+    #[test]
+    fn cache() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiRequest.mock.cache");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let server = MockApiServer::start(&dbg).unwrap();
+        let cache = ReplyCache::new(Duration::from_secs(60), 100);
+        let mut request = ApiRequest::builder(&dbg)
+            .address(server.address())
+            .auth_token("token")
+            .keep_alive(true)
+            .cache(cache.clone())
+            .build()
+            .unwrap();
+        let customer = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "select * from customer;")), true).with_idempotent(true);
+        let role = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "select * from role;")), true).with_cache_ttl(Duration::from_secs(60));
+        let delete = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "delete from role;")), true);
+        // (step, expectation, query, invalidate before, is reply accepted, received queries after)
+        let test_data = [
+            (1, Some(MockExpectation::json(json!([{"id": 1}]))), &customer, false, true, 1),
+            (2, None, &customer, false, true, 1),
+            (3, Some(MockExpectation::error(ApiError::new("message", "details"))), &role, false, false, 2),
+            (4, Some(MockExpectation::json(json!([{"id": 2}]))), &role, false, true, 3),
+            (5, None, &role, false, true, 3),
+            (6, Some(MockExpectation::json(json!([{"id": 1}]))), &customer, true, true, 4),
+            (7, None, &customer, false, true, 4),
+            (8, Some(MockExpectation::json(json!([]))), &delete, false, true, 5),
+            (9, Some(MockExpectation::json(json!([]))), &delete, false, true, 6),
+        ];
+        for (step, expectation, query, invalidate, target, target_received) in test_data {
+            if let Some(expectation) = expectation {
+                server.expect(expectation);
+            }
+            if invalidate {
+                request.cache().unwrap().invalidate(query);
+            }
+            let result = request.fetch_reply_with(query, true);
+            assert!(result.is_ok() == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            let result = server.received().len();
+            assert!(result == target_received, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_received);
+            let result = server.pending();
+            assert!(result == 0, "step {} \nresult: {:?}\ntarget: {:?}", step, result, 0);
+        }
+        let result = cache.len();
+        assert!(result == 2, "\nresult: {:?}\ntarget: {:?}", result, 2);
+        test_duration.exit();
+    }
+}
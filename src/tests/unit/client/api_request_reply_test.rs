@@ -0,0 +1,234 @@
+#[cfg(test)]
+
+mod api_request_reply {
+    use std::{io::{Read, Write}, net::{TcpListener, TcpStream}, sync::{mpsc, Once}, thread, time::Duration};
+    use sal_core::dbg::Dbg;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::{session::test_session::TestSession, stuff::max_test_duration::TestDuration};
+    use crate::client::{api_query::{ApiQuery, ApiQueryKind, ApiQuerySql}, api_request::ApiRequest};
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the reply framed as `Syn | Id | Kind::Bytes | Size | Data`
+    fn frame(id: &str, auth_token: &str) -> Vec<u8> {
+        let data = json!({"authToken": auth_token, "id": id, "keepAlive": true, "query": "", "data": [], "error": {"message": ""}}).to_string();
+        let data = data.as_bytes();
+        [
+            vec![22],
+            1u32.to_be_bytes().to_vec(),
+            vec![2],
+            (data.len() as u32).to_be_bytes().to_vec(),
+            data.to_vec(),
+        ].concat()
+    }
+    ///
+    /// Reads single request framed as `Syn | Id | Kind | Size | Data`
+    fn read_frame(stream: &mut TcpStream) -> Option<serde_json::Value> {
+        let mut header = [0; 10];
+        stream.read_exact(&mut header).ok()?;
+        let size = u32::from_be_bytes(header[6..10].try_into().unwrap()) as usize;
+        let mut data = vec![0; size];
+        stream.read_exact(&mut data).ok()?;
+        serde_json::from_slice(&data).ok()
+    }
+    ///
+    /// Returns the reply framed as `Syn | Id | Kind::Bytes | Size | Data`
+    fn reply_frame(reply: serde_json::Value) -> Vec<u8> {
+        let data = reply.to_string();
+        let data = data.as_bytes();
+        [
+            vec![22],
+            1u32.to_be_bytes().to_vec(),
+            vec![2],
+            (data.len() as u32).to_be_bytes().to_vec(),
+            data.to_vec(),
+        ].concat()
+    }
+    ///
+    /// Testing reply `id` & `authToken` verification
+    /// - Stale reply is drained
+    /// - Reply to the unknown query or with wrong token returns Err
+    /// - Reply with the id of the other client (same counter, other session) returns Err
+    #[test]
+    fn verify() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiRequest.verify");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let token = "123zxy456!@#";
+        let addr = format!("127.0.0.1:{}", TestSession::free_tcp_port_str());
+        // (step, replies sent by server (session, counter, token), is reply accepted)
+        // - Empty session - the session of the request
+        let test_data = [
+            (1, vec![("", "1", token)], true),
+            (2, vec![("", "1", token), ("", "2", token)], true),
+            (3, vec![("", "5", token)], false),
+            (4, vec![("", "4", "wrong token")], false),
+            (5, vec![("", "5", token)], true),
+            (6, vec![("0000000000000000", "6", token)], false),
+        ];
+        let listener = TcpListener::bind(&addr).unwrap();
+        let replies: Vec<Vec<(&str, &str, &str)>> = test_data.iter().map(|(_, replies, _)| replies.clone()).collect();
+        thread::spawn(move || {
+            let mut replies = replies.into_iter();
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                while let Some(query) = read_frame(&mut stream) {
+                    let session = query["id"].as_str().and_then(|id| id.split_once('-')).map(|(session, _)| session.to_owned()).unwrap_or_default();
+                    for (reply_session, counter, token) in replies.next().unwrap_or_default() {
+                        let reply_session = if reply_session.is_empty() { &session } else { reply_session };
+                        stream.write_all(&frame(&format!("{}-{}", reply_session, counter), token)).unwrap();
+                        thread::sleep(Duration::from_millis(50));
+                    }
+                }
+            }
+        });
+        let mut request = ApiRequest::new(
+            &dbg,
+            &addr,
+            token,
+            ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "select 1;")), false),
+            true,
+            false,
+        );
+        for (step, _, target) in test_data {
+            let result = request.fetch(true);
+            println!("step {} \nresult: {:?}", step, result);
+            assert!(result.is_ok() == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the connection closed on the mismatched reply is reopened with the clean parser
+    /// - The bytes of the partial frame received after the mismatched reply aren't parsed into the next reply
+    #[test]
+    fn desync() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiRequest.desync");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let token = "123zxy456!@#";
+        let addr = format!("127.0.0.1:{}", TestSession::free_tcp_port_str());
+        let listener = TcpListener::bind(&addr).unwrap();
+        thread::spawn(move || {
+            for (connection, stream) in listener.incoming().enumerate() {
+                let mut stream = stream.unwrap();
+                while let Some(query) = read_frame(&mut stream) {
+                    let id = query["id"].as_str().unwrap_or_default().to_owned();
+                    match connection {
+                        // The reply with the wrong token & the beginning of the next frame in the single write
+                        0 => {
+                            let partial = frame(&id, token);
+                            stream.write_all(&[frame(&id, "wrong token"), partial[..partial.len() / 2].to_vec()].concat()).unwrap();
+                        }
+                        _ => stream.write_all(&frame(&id, token)).unwrap(),
+                    }
+                }
+            }
+        });
+        let mut request = ApiRequest::new(
+            &dbg,
+            &addr,
+            token,
+            ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "select 1;")), false),
+            true,
+            false,
+        );
+        // (step, is reply accepted)
+        let test_data = [
+            (1, false),
+            (2, true),
+            (3, true),
+        ];
+        for (step, target) in test_data {
+            let result = request.fetch(true);
+            assert!(result.is_ok() == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the `authToken` refreshed transparently by the `login` query
+    /// - Before the first request if the token is empty
+    /// - When the server replies the token is expired, the request repeated once
+    #[test]
+    fn token_refresh() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiRequest.token_refresh");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let addr = format!("127.0.0.1:{}", TestSession::free_tcp_port_str());
+        let listener = TcpListener::bind(&addr).unwrap();
+        let (queries_send, queries_recv) = mpsc::channel();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                while let Some(query) = read_frame(&mut stream) {
+                    let auth_token = query["authToken"].as_str().unwrap().to_owned();
+                    let (kind, data, error) = match query["login"].is_object() {
+                        true => {
+                            let expires = chrono::Utc::now().timestamp() + 3600;
+                            ("login", json!([{"token": "valid", "user": query["login"]["user"], "expires": expires}]), json!({"message": ""}))
+                        }
+                        false => match auth_token.as_str() {
+                            "valid" => ("sql", json!([{"id": 1}]), json!({"message": ""})),
+                            _ => ("sql", json!([]), json!({"message": "API Auth Service - authToken expired", "code": "tokenExpired"})),
+                        }
+                    };
+                    queries_send.send((kind, auth_token.clone())).unwrap();
+                    let reply = json!({"authToken": auth_token, "id": query["id"], "keepAlive": true, "query": "", "data": data, "error": error});
+                    stream.write_all(&reply_frame(reply)).unwrap();
+                }
+            }
+        });
+        let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "select 1;")), false);
+        // (step, initial token, requests sent for each fetch)
+        let test_data = [
+            (1, "expired", vec![
+                vec![("sql", "expired"), ("login", "expired"), ("sql", "valid")],
+                vec![("sql", "valid")],
+            ]),
+            (2, "", vec![
+                vec![("login", ""), ("sql", "valid")],
+                vec![("sql", "valid")],
+            ]),
+        ];
+        for (step, token, targets) in test_data {
+            let mut request = ApiRequest::new(&dbg, &addr, token, query.clone(), true, false)
+                .with_login("user", "pass");
+            for target in targets {
+                let result = request.fetch_reply(true);
+                assert!(result.is_ok(), "step {} \nresult: {:?}\ntarget: {:?}", step, result, "Ok");
+                let result: Vec<(&str, String)> = queries_recv.try_iter().collect();
+                let target: Vec<(&str, String)> = target.into_iter().map(|(kind, token)| (kind, token.to_owned())).collect();
+                assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            }
+            let result = request.auth_token();
+            assert!(result == "valid", "step {} \nresult: {:?}\ntarget: {:?}", step, result, "valid");
+        }
+        test_duration.exit();
+    }
+}
\ No newline at end of file
@@ -0,0 +1,113 @@
+#[cfg(test)]
+
+mod api_request_retry {
+    use std::{sync::Once, time::{Duration, Instant}};
+    use sal_core::dbg::Dbg;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::{
+        client::{api_query::{ApiQuery, ApiQueryKind, ApiQuerySql}, api_request::ApiRequest, retry_policy::{RetryClass, RetryPolicy}},
+        mock::{mock_api_server::MockApiServer, mock_expectation::MockExpectation},
+    };
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing the idempotent query repeated after the dropped connection, the read timeout & the truncated reply,
+    /// and the not idempotent / not retryable ones failed at once
+    #[test]
+    fn retry() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiRequest.retry");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(20));
+        test_duration.run().unwrap();
+        let rows = json!([{"id": 1}]);
+        let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "select 1;")), true);
+        let all = RetryPolicy::new(3, Duration::from_millis(50));
+        let connect = RetryPolicy::new(3, Duration::from_millis(50)).with_retry_on(&[RetryClass::Connect]);
+        // (step, policy, idempotent, failure, is reply accepted, pending expectations after, ids received)
+        let test_data = [
+            (1, &all, true, MockExpectation::drop(), true, 0, vec!["1", "2"]),
+            (2, &all, true, MockExpectation::silence(), true, 0, vec!["1", "2"]),
+            (3, &all, true, MockExpectation::truncated(serde_json::from_value(json!([{"id": 0}])).unwrap()), true, 0, vec!["1", "2"]),
+            (4, &all, false, MockExpectation::drop(), false, 1, vec!["1"]),
+            (5, &connect, true, MockExpectation::drop(), false, 1, vec!["1"]),
+        ];
+        for (step, policy, idempotent, failure, target, target_pending, target_ids) in test_data {
+            let server = MockApiServer::start(&dbg).unwrap();
+            server.expect(failure);
+            server.expect(MockExpectation::json(rows.clone()));
+            let mut request = ApiRequest::builder(&dbg)
+                .address(server.address())
+                .auth_token("token")
+                .query(query.clone().with_idempotent(idempotent))
+                .keep_alive(true)
+                .timeout(Duration::from_millis(300))
+                .retry(policy.clone())
+                .build()
+                .unwrap();
+            let result = request.fetch_reply(true);
+            println!("step {} \nresult: {:?}", step, result);
+            assert!(result.is_ok() == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            if let Ok(reply) = result {
+                let result = json!(reply.data);
+                assert!(result == rows, "step {} \nresult: {:?}\ntarget: {:?}", step, result, rows);
+            }
+            let result = server.pending();
+            assert!(result == target_pending, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_pending);
+            // The session part of the id is random, checking the counter only
+            let result: Vec<String> = server.received().iter()
+                .filter_map(|query| query["id"].as_str().and_then(|id| id.split_once('-')).map(|(_, counter)| counter.to_owned()))
+                .collect();
+            assert!(result == target_ids, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_ids);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the attempts to connect to the stopped server
+    #[test]
+    fn retry_connect() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiRequest.retry_connect");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let mut server = MockApiServer::start(&dbg).unwrap();
+        let address = server.address();
+        server.stop();
+        let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "select 1;")), false).with_idempotent(true);
+        let mut request = ApiRequest::builder(&dbg)
+            .address(address)
+            .query(query)
+            .timeout(Duration::from_millis(100))
+            .retry(RetryPolicy::new(3, Duration::from_millis(100)))
+            .build()
+            .unwrap();
+        let time = Instant::now();
+        let result = request.fetch(false);
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        // 3 connect timeouts and 100 + 200 ms backoff
+        let result = time.elapsed();
+        let target = Duration::from_millis(600);
+        assert!(result >= target, "\nresult: {:?}\ntarget: >= {:?}", result, target);
+        test_duration.exit();
+    }
+}
@@ -1,96 +1,50 @@
 #[cfg(test)]
 
 mod api_request {
-    use std::{collections::HashMap, process::Command, sync::{atomic::AtomicUsize, Once}, thread, time::{Duration, Instant}};
+    use std::{sync::Once, time::{Duration, Instant}};
     use sal_core::dbg::Dbg;
     use serde_json::json;
     use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
-    use testing::{session::teardown::Teardown, stuff::max_test_duration::TestDuration};
+    use testing::stuff::max_test_duration::TestDuration;
     use crate::{
         api::reply::api_reply::ApiReply, client::{api_query::{ApiQuery, ApiQueryExecutable, ApiQueryKind, ApiQueryPython, ApiQuerySql}, api_request::ApiRequest},
-        tests::unit::client::prepare_postgres::TestDatabasePostgres,
+        mock::{mock_api_server::MockApiServer, mock_expectation::MockExpectation},
     };
-    ///    
+    ///
     static INIT: Once = Once::new();
-    static TEARDOWN_COUNT: AtomicUsize = AtomicUsize::new(0);
     ///
     /// once called initialisation
-    /// returns ApiServer PID
-    fn init_once(dbg: &Dbg, database: &str, tmp_path: &str, git_repo: &str) -> u32 {
-        let mut child: u32 = 0;
+    fn init_once() {
         INIT.call_once(|| {
                 // implement your initialisation code to be called only once for current test file
-                let mut client = TestDatabasePostgres::connect_db(dbg, "postgres", "postgres", "localhost:5432", "").unwrap();
-                TestDatabasePostgres::create_db(dbg, &mut client, database).unwrap();
-                client.close().unwrap();
-                let mut client = TestDatabasePostgres::connect_db(dbg, database, database, "localhost:5432", database).unwrap();
-                TestDatabasePostgres::create_db_table(dbg, &mut client, database, "customer").unwrap();
-                client.close().unwrap();
-                let setup_sh = "./src/tests/unit/client/setup-build.sh";
-                log::debug!("{}.init_once | Preparing new instance of api-server...
-                    \t - Be sure you are have internet connection for git clone
-                    \t - Be sure local address '0.0.0.0:8080' is not busy before test executed
-                    \t - Check '{}'",dbg, setup_sh
-                );
-                let output = Command::new(setup_sh)
-                    .arg(tmp_path)
-                    .arg(git_repo)
-                    .output()
-                    .expect("Failed to exec setup-build.sh");
-                log::debug!("{}.init_once | setup-build: {:?}",dbg, output);
-                let run_sh = "./src/tests/unit/client/setup-run.sh";
-                let p = Command::new(run_sh)
-                    .arg(tmp_path)
-                    .spawn()
-                    .expect("Failed to exec setup-run.sh");
-                log::debug!("{}.init_once | setup-run: {:?}",dbg, p);
-                child = p.id()
-            }
-        );
-        child
-    }
-    ///
-    /// Once called after all tests
-    fn teardown_once(dbg: &Dbg, database: &str, tmp_path: &str, child_id: u32) {
-        let mut client = TestDatabasePostgres::connect_db(dbg, "postgres", "postgres", "localhost:5432", "").unwrap();
-        TestDatabasePostgres::drop_db(dbg, &mut client, database).unwrap();
-        let p = Command::new("rm")
-            .arg("rf")
-            .arg(tmp_path)
-            .output()
-            .expect("Failed to remove tmp dir");
-        log::debug!("std: {:#?}", p);
-        let p = Command::new("kill")
-            .arg(format!("{}", child_id))
-            .spawn()
-            .expect("Failed to remove tmp dir");
-        log::debug!("std: {:#?}", p.stdout);
+        });
     }
     ///
     /// returns:
     ///  - ...
     fn init_each() -> () {}
     ///
-    /// 
+    /// Returns the json of the `request` with the counter part of the id only, the session part of the id is random
+    fn request_json(request: &ApiRequest) -> serde_json::Value {
+        let mut result = json!(request);
+        result["id"] = json!(result["id"].as_str().and_then(|id| id.split_once('-')).map(|(_, counter)| counter));
+        result
+    }
+    ///
+    /// Testing the request of the each kind sent to the mock server, without debug
     #[test]
     fn debug_false() {
         DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
         init_each();
         println!("");
         let dbg = Dbg::own("test ApiRequest");
         println!("{}", dbg);
-        let test_duration = TestDuration::new(&dbg, Duration::from_secs(120));
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
         test_duration.run().unwrap();
         let database = "test_api_query";
-        let tmp_path = "/tmp/api-tools-test/api-server/";
-        let git_repo = "https://github.com/a-givertzman/api-server.git";
-        let child_id = init_once(&dbg, database, tmp_path, git_repo);
-        let _teardown_once = || {
-            teardown_once(&dbg, &database, &tmp_path, child_id);
-        };
-        let _teardown = Teardown::new(&TEARDOWN_COUNT, &|| {}, &_teardown_once);
-        let port = "8080";     //TestSession::free_tcp_port_str();
-        let addtess = format!("0.0.0.0:{}", port);
+        let server = MockApiServer::start(&dbg).unwrap();
+        let rows = json!([{"id": 1, "name": "Markus"}, {"id": 2, "name": "Daniel"}]);
         let token = "123zxy456!@#";
         let keep_alive = true;
         let close_connection = false;
@@ -100,23 +54,23 @@ mod api_request {
             (
                 ApiQuery::new(
                     ApiQueryKind::Sql(ApiQuerySql::new(database, "select * from customer;")),
-                    service_keep_alive, 
+                    service_keep_alive,
                 ),
                 keep_alive,
                 r#"{"authToken":"123zxy456!@#","id":"1","sql":{"database":"test_api_query","sql":"select * from customer;"},"keepAlive":true,"debug":false}"#,
-                
+
             ),
             (
                 ApiQuery::new(
                     ApiQueryKind::Sql(ApiQuerySql::new(database, "select * from customer limit 3;")),
-                    service_keep_alive, 
+                    service_keep_alive,
                 ),
                 keep_alive,
                 r#"{"authToken":"123zxy456!@#","id":"2","sql":{"database":"test_api_query","sql":"select * from customer limit 3;"},"keepAlive":true,"debug":false}"#,
             ),
             (
                 ApiQuery::new(
-                    ApiQueryKind::Python(ApiQueryPython::new("test_script", json!(HashMap::<String, f64>::new()))),
+                    ApiQueryKind::Python(ApiQueryPython::new("test_script", serde_json::Map::new())),
                     service_keep_alive,
                 ),
                 keep_alive,
@@ -124,7 +78,7 @@ mod api_request {
             ),
             (
                 ApiQuery::new(
-                    ApiQueryKind::Executable(ApiQueryExecutable::new("test_app", json!(HashMap::<String, f64>::new()))),
+                    ApiQueryKind::Executable(ApiQueryExecutable::new("test_app", serde_json::Map::new())),
                     service_keep_alive,
                 ),
                 close_connection,
@@ -133,142 +87,110 @@ mod api_request {
         ];
         let mut request = ApiRequest::new(
             &dbg,
-            &addtess,
-            token, 
+            server.address(),
+            token,
             ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("", "")), false),
             true,
             debug,
         );
-        thread::sleep(Duration::from_secs(1));
         for (query, keep_alive, target) in test_data {
+            server.expect(MockExpectation::json(rows.clone()));
             println!("\nrequest: {:?}", request);
             match request.fetch_with(&query, keep_alive) {
                 Ok(bytes) => {
-                    let reply = ApiReply::try_from(bytes);
+                    let reply = ApiReply::try_from(bytes).unwrap();
                     println!("\nreply: {:?}", reply);
+                    let result = json!(reply.data);
+                    assert!(result == rows, "\n result: {:?}\n target: {:?}", result, rows);
                 },
                 Err(err) => {
                     panic!("{} | Error: {:?}", dbg, err);
                 },
             };
-            let result = json!(request);
+            let result = request_json(&request);
             let target: serde_json::Value = serde_json::from_str(target).unwrap();
             assert!(result == target, "\n result: {:?}\n target: {:?}", result, target);
             println!("\n result: {:?}\n target: {:?}", result, target);
         }
-        // let mut client = TestDatabasePostgres::connect_db(self_id, "postgres", "postgres", "localhost:5432", "").unwrap();
-        // TestDatabasePostgres::drop_db(self_id, &mut client, database).unwrap();
+        let result = server.pending();
+        assert!(result == 0, "\n result: {:?}\n target: {:?}", result, 0);
         test_duration.exit();
     }
     ///
-    /// 
+    /// Testing the request sent to the mock server with debug, the query returned in the reply
     #[test]
     fn debug_true() {
         DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
         init_each();
         println!("");
         let dbg = Dbg::own("test ApiRequest");
         println!("{}", dbg);
-        let test_duration = TestDuration::new(&dbg, Duration::from_secs(120));
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
         test_duration.run().unwrap();
         let database = "test_api_query";
-        let tmp_path = "/tmp/api-tools-test/api-server/";
-        let git_repo = "https://github.com/a-givertzman/api-server.git";
-        let child_id = init_once(&dbg, database, tmp_path, git_repo);
-        let _teardown_once = || {
-            teardown_once(&dbg, &database, &tmp_path, child_id);
-        };
-        let _teardown = Teardown::new(&TEARDOWN_COUNT, &|| {}, &_teardown_once);
-        let port = "8080";     //TestSession::free_tcp_port_str();
-        let addtess = format!("0.0.0.0:{}", port);
+        let server = MockApiServer::start(&dbg).unwrap();
+        let rows = json!([{"id": 1, "name": "Markus"}]);
         let token = "123zxy456!@#";
         let keep_alive = true;
-        // let close_connection = false;
         let service_keep_alive = false;
         let debug = true;
         let test_data = [
             (
                 ApiQuery::new(
                     ApiQueryKind::Sql(ApiQuerySql::new(database, "select * from customer;")),
-                    service_keep_alive, 
+                    service_keep_alive,
                 ),
                 keep_alive,
                 r#"{"authToken":"123zxy456!@#","id":"1","sql":{"database":"test_api_query","sql":"select * from customer;"},"keepAlive":true,"debug":true}"#,
-                
+
             ),
-            // (
-            //     ApiQuery::new(
-            //         ApiQueryKind::Sql(ApiQuerySql::new(database, "select * from customer limit 3;")),
-            //         service_keep_alive, 
-            //     ),
-            //     keep_alive,
-            //     r#"{"authToken":"123zxy456!@#","id":"2","sql":{"database":"test_api_query","sql":"select * from customer limit 3;"},"keepAlive":true,"debug":false}"#,
-            // ),
-            // (
-            //     ApiQuery::new(
-            //         ApiQueryKind::Python(ApiQueryPython::new("test_script", json!(HashMap::<String, f64>::new()))),
-            //         service_keep_alive,
-            //     ),
-            //     keep_alive,
-            //     r#"{"authToken":"123zxy456!@#","id":"3","python":{"script":"test_script","params":{}},"keepAlive":true,"debug":false}"#,
-            // ),
-            // (
-            //     ApiQuery::new(
-            //         ApiQueryKind::Executable(ApiQueryExecutable::new("test_app", json!(HashMap::<String, f64>::new()))),
-            //         service_keep_alive,
-            //     ),
-            //     close_connection,
-            //     r#"{"authToken":"123zxy456!@#","id":"4","executable":{"name":"test_app","params":{}},"keepAlive":false,"debug":false}"#,
-            // ),
         ];
         let mut request = ApiRequest::new(
             &dbg,
-            &addtess,
-            token, 
+            server.address(),
+            token,
             ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("", "")), false),
             true,
             debug,
         );
         for (query, keep_alive, target) in test_data {
+            server.expect(MockExpectation::json(rows.clone()));
             println!("\nrequest: {:?}", request);
             match request.fetch_with(&query, keep_alive) {
                 Ok(bytes) => {
-                    let reply = ApiReply::try_from(bytes);
+                    let reply = ApiReply::try_from(bytes).unwrap();
                     println!("\nreply: {:?}", reply);
+                    let result = json!(reply.data);
+                    assert!(result == rows, "\n result: {:?}\n target: {:?}", result, rows);
+                    assert!(!reply.query.is_empty(), "\n result: {:?}\n target: the query of the request", reply.query);
                 },
                 Err(err) => {
                     panic!("{} | Error: {:?}", dbg, err);
                 },
             };
-            let result = json!(request);
+            let result = request_json(&request);
             let target: serde_json::Value = serde_json::from_str(target).unwrap();
             assert!(result == target, "\n result: {:?}\n target: {:?}", result, target);
             println!("\n result: {:?}\n target: {:?}", result, target);
         }
         test_duration.exit();
-        test_duration.exit();
     }
     ///
     /// ApiRequest performance test
     #[test]
     fn performance() {
         DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
         init_each();
         println!("");
         let dbg = Dbg::own("test ApiRequest");
         println!("{}", dbg);
-        let test_duration = TestDuration::new(&dbg, Duration::from_secs(120));
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(30));
         test_duration.run().unwrap();
         let database = "test_api_query";
-        let tmp_path = "/tmp/api-tools-test/api-server/";
-        let git_repo = "https://github.com/a-givertzman/api-server.git";
-        let child_id = init_once(&dbg, database, tmp_path, git_repo);
-        let _teardown_once = || {
-            teardown_once(&dbg, &database, &tmp_path, child_id);
-        };
-        let _teardown = Teardown::new(&TEARDOWN_COUNT, &|| {}, &_teardown_once);
-        let port = "8080";     //TestSession::free_tcp_port_str();
-        let addtess = format!("0.0.0.0:{}", port);
+        let server = MockApiServer::start(&dbg).unwrap();
+        let rows = json!([{"id": 1, "name": "Markus"}, {"id": 2, "name": "Daniel"}, {"id": 3, "name": "Pere"}]);
         let token = "123zxy456!@#";
         let keep_alive = true;
         let service_keep_alive = false;
@@ -277,57 +199,58 @@ mod api_request {
             (
                 ApiQuery::new(
                     ApiQueryKind::Sql(ApiQuerySql::new(database, "select * from customer limit 4;")),
-                    service_keep_alive, 
+                    service_keep_alive,
                 ),
                 keep_alive,
-                r#"{"authToken":"123zxy456!@#","id":"1","sql":{"database":"test_api_query","sql":"select * from customer limit 4;"},"keepAlive":true,"debug":false}"#,
-                
+                r#"{"authToken":"123zxy456!@#","sql":{"database":"test_api_query","sql":"select * from customer limit 4;"},"keepAlive":true,"debug":false}"#,
+
             ),
             (
                 ApiQuery::new(
                     ApiQueryKind::Sql(ApiQuerySql::new(database, "select * from customer limit 3;")),
-                    service_keep_alive, 
+                    service_keep_alive,
                 ),
                 keep_alive,
-                r#"{"authToken":"123zxy456!@#","id":"2","sql":{"database":"test_api_query","sql":"select * from customer limit 3;"},"keepAlive":true,"debug":false}"#,
+                r#"{"authToken":"123zxy456!@#","sql":{"database":"test_api_query","sql":"select * from customer limit 3;"},"keepAlive":true,"debug":false}"#,
             ),
         ];
         let mut request = ApiRequest::new(
             &dbg,
-            &addtess,
-            token, 
+            server.address(),
+            token,
             ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("", "")), false),
             true,
             debug,
         );
-        thread::sleep(Duration::from_secs(1));
         let t_total = Instant::now();
         let mut t: Instant;
         let queries = 0..100;
         for _ in queries.clone() {
             for (query, keep_alive, target) in &test_data {
-                println!("\nrequest: {:?}", request);
+                server.expect(MockExpectation::json(rows.clone()));
                 t = Instant::now();
-                match request.fetch_with(&query, *keep_alive) {
+                match request.fetch_with(query, *keep_alive) {
                     Ok(bytes) => {
-                        let reply = ApiReply::try_from(bytes);
-                        println!("\nreply: {:?}", reply);
+                        let result = ApiReply::try_from(bytes).map(|reply| json!(reply.data)).unwrap();
+                        assert!(result == rows, "\n result: {:?}\n target: {:?}", result, rows);
                     },
                     Err(err) => {
                         panic!("{} | Error: {:?}", dbg, err);
                     },
                 };
-                let result = json!(request);
+                let mut result = json!(request);
+                result.as_object_mut().unwrap().remove("id");
                 let target: serde_json::Value = serde_json::from_str(target).unwrap();
-                // assert!(result == target, "\n result: {:?}\n target: {:?}", result, target);
-                println!("\n result: {:?}\n target: {:?}", result, target);
-                println!("Elapsed: {:?}", t.elapsed());
+                assert!(result == target, "\n result: {:?}\n target: {:?}", result, target);
+                log::trace!("Elapsed: {:?}", t.elapsed());
             }
         }
         let queries_total = queries.len() * test_data.len();
         println!("Total queries: {:?}", queries_total);
         println!("Average elapsed per query: {:?}", t_total.elapsed() / queries_total as u32);
         println!("Total elapsed: {:?}", t_total.elapsed());
+        let result = server.received().len();
+        assert!(result == queries_total, "\n result: {:?}\n target: {:?}", result, queries_total);
         test_duration.exit();
     }
 }
@@ -0,0 +1,135 @@
+#[cfg(test)]
+
+mod api_stream {
+    use std::{io::{Read, Write}, net::{TcpListener, TcpStream}, sync::{mpsc, Once}, thread, time::Duration};
+    use sal_core::dbg::Dbg;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::{session::test_session::TestSession, stuff::max_test_duration::TestDuration};
+    use crate::client::{api_query::{ApiQuery, ApiQueryKind, ApiQuerySql}, api_request::ApiRequest};
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Reads single request framed as `Syn | Id | Kind | Size | Data`
+    fn read_frame(stream: &mut TcpStream) -> Option<serde_json::Value> {
+        let mut header = [0; 10];
+        stream.read_exact(&mut header).ok()?;
+        let size = u32::from_be_bytes(header[6..10].try_into().unwrap()) as usize;
+        let mut data = vec![0; size];
+        stream.read_exact(&mut data).ok()?;
+        serde_json::from_slice(&data).ok()
+    }
+    ///
+    /// Writes the reply framed as `Syn | Id | Kind::Bytes | Size | Data`
+    fn write_frame(stream: &mut TcpStream, reply: serde_json::Value) {
+        let data = reply.to_string();
+        let data = data.as_bytes();
+        let frame = [
+            vec![22],
+            1u32.to_be_bytes().to_vec(),
+            vec![2],
+            (data.len() as u32).to_be_bytes().to_vec(),
+            data.to_vec(),
+        ].concat();
+        stream.write_all(&frame).unwrap();
+    }
+    ///
+    /// Testing rows streamed page by page by [ApiRequest::fetch_stream]
+    #[test]
+    fn fetch_stream() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiRequest.fetch_stream");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let token = "123zxy456!@#";
+        let addr = format!("127.0.0.1:{}", TestSession::free_tcp_port_str());
+        let rows: Vec<serde_json::Value> = (0..5).map(|i| json!({"id": i})).collect();
+        let page_size = 2;
+        let listener = TcpListener::bind(&addr).unwrap();
+        let (queries_send, queries_recv) = mpsc::channel();
+        let server_rows = rows.clone();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut offset = 0;
+            while let Some(query) = read_frame(&mut stream) {
+                let cursor = query["cursor"].clone();
+                if cursor.is_null() {
+                    queries_send.send(json!({"sql": query["sql"], "keepAlive": query["keepAlive"]})).unwrap();
+                    write_frame(&mut stream, json!({
+                        "authToken": query["authToken"], "id": query["id"], "keepAlive": true, "query": "",
+                        "data": [], "error": {"message": ""},
+                    }));
+                    continue;
+                }
+                queries_send.send(cursor.clone()).unwrap();
+                if cursor["open"].is_object() {
+                    offset = 0;
+                }
+                let page: Vec<serde_json::Value> = server_rows.iter().skip(offset).take(page_size).cloned().collect();
+                offset += page.len();
+                let done = page.len() < page_size || cursor["close"].is_string();
+                write_frame(&mut stream, json!({
+                    "authToken": query["authToken"], "id": query["id"], "keepAlive": true, "query": "",
+                    "data": if cursor["close"].is_string() { vec![] } else { page },
+                    "error": {"message": ""},
+                    "cursor": {"id": "7", "done": done},
+                }));
+            }
+        });
+        let mut request = ApiRequest::new(
+            &dbg,
+            &addr,
+            token,
+            ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "select 1;")), false),
+            true,
+            false,
+        );
+        // Whole stream
+        let result: Vec<serde_json::Value> = request
+            .fetch_stream(ApiQuerySql::new("database", "select * from event;"), page_size)
+            .map(|row| serde_json::Value::Object(row.unwrap().into_iter().collect()))
+            .collect();
+        assert!(result == rows, "\nresult: {:?}\ntarget: {:?}", result, rows);
+        let queries: Vec<serde_json::Value> = queries_recv.try_iter().collect();
+        let target = vec![
+            json!({"open": {"database": "database", "sql": "select * from event;", "pageSize": 2}}),
+            json!({"next": "7"}),
+            json!({"next": "7"}),
+        ];
+        assert!(queries == target, "\nresult: {:?}\ntarget: {:?}", queries, target);
+        // Stream dropped before the last page
+        let result: Vec<serde_json::Value> = request
+            .fetch_stream(ApiQuerySql::new("database", "select * from event;"), page_size)
+            .take(1)
+            .map(|row| serde_json::Value::Object(row.unwrap().into_iter().collect()))
+            .collect();
+        assert!(result == rows[..1], "\nresult: {:?}\ntarget: {:?}", result, &rows[..1]);
+        let queries: Vec<serde_json::Value> = queries_recv.try_iter().collect();
+        let target = vec![
+            json!({"open": {"database": "database", "sql": "select * from event;", "pageSize": 2}}),
+            json!({"close": "7"}),
+        ];
+        assert!(queries == target, "\nresult: {:?}\ntarget: {:?}", queries, target);
+        // The configured query is sent after the stream
+        request.fetch(true).unwrap();
+        let queries: Vec<serde_json::Value> = queries_recv.try_iter().collect();
+        let target = vec![json!({"sql": {"database": "database", "sql": "select 1;"}, "keepAlive": true})];
+        assert!(queries == target, "\nresult: {:?}\ntarget: {:?}", queries, target);
+        test_duration.exit();
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+
+mod api_subscription {
+    use std::{sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::{
+        api::reply::api_notification::ApiNotification,
+        client::{api_query::{ApiQuery, ApiQueryKind, ApiQuerySql, ApiQuerySubscribe}, api_request::ApiRequest},
+        error::api_error::ApiError,
+        mock::{mock_api_server::MockApiServer, mock_expectation::MockExpectation},
+    };
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing the notifications received by the subscription,
+    /// the request stays usable, the notifications on it's connection are skipped
+    #[test]
+    fn subscribe() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiSubscription.subscribe");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let server = MockApiServer::start(&dbg).unwrap();
+        let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "select 1;")), true);
+        let mut request = ApiRequest::new(&dbg, server.address(), "token", query, true, false);
+        server.expect(MockExpectation::json(json!([{"id": 1}])));
+        request.fetch_reply(true).unwrap();
+        server.expect(MockExpectation::json(json!([])));
+        let mut subscription = request.subscribe(ApiQuerySubscribe::new("database", &["event", "alarm"])).unwrap();
+        let mut result = server.received().pop().unwrap();
+        // The session part of the id is random, checking the counter only
+        result["id"] = json!(result["id"].as_str().and_then(|id| id.split_once('-')).map(|(_, counter)| counter));
+        let target = json!({"authToken": "token", "id": "1", "subscribe": {"database": "database", "channels": ["event", "alarm"]}, "keepAlive": true, "debug": false});
+        assert!(result == target, "subscribe \nresult: {:?}\ntarget: {:?}", result, target);
+        let notifications = [
+            ApiNotification::new("event", "1", 101),
+            ApiNotification::new("alarm", "{\"id\": 2}", 102),
+            ApiNotification::new("event", "", 103),
+        ];
+        // Sent at once, so several frames can be received by the single read
+        for notification in &notifications {
+            server.notify(notification);
+        }
+        for (step, target) in notifications.iter().enumerate() {
+            let result = subscription.recv_timeout(Duration::from_secs(1));
+            assert!(result.as_ref() == Some(target), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        server.expect(MockExpectation::json(json!([{"id": 2}])));
+        let reply = request.fetch_reply(true).unwrap();
+        let result = json!(reply.data);
+        let target = json!([{"id": 2}]);
+        assert!(result == target, "request \nresult: {:?}\ntarget: {:?}", result, target);
+        subscription.close();
+        let result = subscription.receiver().recv_timeout(Duration::from_secs(1));
+        assert!(result.is_err(), "close \nresult: {:?}\ntarget: Disconnected", result);
+        test_duration.exit();
+    }
+    ///
+    /// Testing the subscribe query rejected by the server
+    #[test]
+    fn subscribe_error() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiSubscription.subscribe_error");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let server = MockApiServer::start(&dbg).unwrap();
+        let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "select 1;")), true);
+        let mut request = ApiRequest::new(&dbg, server.address(), "token", query, true, false);
+        server.expect(MockExpectation::error(ApiError::new("API SQL Service - subscribe error", "")));
+        let result = request.subscribe(ApiQuerySubscribe::new("database", &["event"]));
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result.is_ok());
+        test_duration.exit();
+    }
+}
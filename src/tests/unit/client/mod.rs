@@ -1,7 +1,25 @@
+mod api_call_test;
+
 mod api_query_test;
 
 mod api_request_test;
 
-mod prepare_postgres;
+mod api_request_builder_test;
+
+mod api_request_mock_test;
+
+mod api_request_reply_test;
+
+mod api_request_retry_test;
+
+mod api_stream_test;
+
+mod api_subscription_test;
+
+pub mod prepare_postgres;
+
+mod reply_cache_test;
+
+mod retry_policy_test;
 
 // mod api_reply_test;
\ No newline at end of file
@@ -0,0 +1,130 @@
+#[cfg(test)]
+
+mod reply_cache {
+    use std::{net::SocketAddr, sync::Once, thread, time::Duration};
+    use sal_core::dbg::Dbg;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::client::{
+        api_query::{ApiQuery, ApiQueryCancel, ApiQueryKind, ApiQueryPython, ApiQuerySql, ApiQuerySqlParams},
+        reply_cache::ReplyCache,
+    };
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns sql query, opted in to be cached
+    fn sql(sql: &str, params: ApiQuerySqlParams) -> ApiQuery {
+        ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", sql).with_params(params)), false).with_idempotent(true)
+    }
+    ///
+    /// Testing the queries found by the normalized key
+    #[test]
+    fn key() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ReplyCache.key");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let named = |value: serde_json::Value| ApiQuerySqlParams::Named(value.as_object().unwrap().to_owned());
+        let address: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+        let other_address: SocketAddr = "127.0.0.1:8081".parse().unwrap();
+        let cache = ReplyCache::new(Duration::from_secs(60), 100);
+        cache.insert(address, "token", &sql("select * from customer where id = :id and name = :name;", named(json!({"id": 1, "name": "Alex"}))), false, b"customer".to_vec());
+        cache.insert(address, "token", &sql("select * from role where name = 'a  b';", ApiQuerySqlParams::default()), false, b"role".to_vec());
+        cache.insert(address, "token", &ApiQuery::new(ApiQueryKind::Python(ApiQueryPython::new("script", json!({"a": 1, "b": 2}).as_object().unwrap().to_owned())), false).with_idempotent(true), false, b"python".to_vec());
+        cache.insert(address, "token", &ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "delete from role;")), false), false, b"delete".to_vec());
+        cache.insert(address, "token", &ApiQuery::new(ApiQueryKind::Cancel(ApiQueryCancel::new("1")), false), false, b"cancel".to_vec());
+        let test_data = [
+            (01, sql("select * from customer where id = :id and name = :name;", named(json!({"id": 1, "name": "Alex"}))), false, Some("customer")),
+            (02, sql("  select *\n\tfrom customer   where id = :id and name = :name;  ", named(json!({"name": "Alex", "id": 1}))), false, Some("customer")),
+            (03, sql("select * from customer where id = :id and name = :name;", named(json!({"id": 2, "name": "Alex"}))), false, None),
+            (04, sql("select * from customer where id = :id and name = :name;", named(json!({"id": 1, "name": "Alex"}))), true, None),
+            (05, sql("select  *  from role where name = 'a  b';", ApiQuerySqlParams::default()), false, Some("role")),
+            (06, sql("select * from role where name = 'a b';", ApiQuerySqlParams::default()), false, None),
+            (07, ApiQuery::new(ApiQueryKind::Python(ApiQueryPython::new("script", json!({"b": 2, "a": 1}).as_object().unwrap().to_owned())), true), false, Some("python")),
+            (08, ApiQuery::new(ApiQueryKind::Cancel(ApiQueryCancel::new("1")), false), false, None),
+            (09, ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "delete from role;")), false), false, None),
+            (10, ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("database", "delete from role;")), false).with_idempotent(true), false, None),
+        ];
+        for (step, query, debug, target) in test_data {
+            let result = cache.get(address, "token", &query, debug);
+            let target = target.map(|target| target.as_bytes().to_vec());
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        // The replies to the other server or user
+        let query = sql("select * from role where name = 'a  b';", ApiQuerySqlParams::default());
+        let test_data = [
+            (11, address, "token", Some("role")),
+            (12, address, "other token", None),
+            (13, address, "", None),
+            (14, other_address, "token", None),
+        ];
+        for (step, address, token, target) in test_data {
+            let result = cache.get(address, token, &query, false);
+            let target = target.map(|target| target.as_bytes().to_vec());
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the TTL, the capacity & the invalidation
+    #[test]
+    fn expiry() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ReplyCache.expiry");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let address: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+        let query = |id: usize| sql(&format!("select {};", id), ApiQuerySqlParams::default());
+        let cache = ReplyCache::new(Duration::from_millis(200), 3);
+        cache.insert(address, "token", &query(1), false, vec![1]);
+        cache.insert(address, "token", &query(2).with_cache_ttl(Duration::from_secs(60)), false, vec![2]);
+        cache.insert(address, "token", &query(3).with_cache_ttl(Duration::ZERO), false, vec![3]);
+        // TTL
+        let result = (cache.get(address, "token", &query(1), false), cache.get(address, "token", &query(2), false), cache.get(address, "token", &query(3), false));
+        let target = (Some(vec![1]), Some(vec![2]), None);
+        assert!(result == target, "ttl \nresult: {:?}\ntarget: {:?}", result, target);
+        thread::sleep(Duration::from_millis(300));
+        let result = (cache.get(address, "token", &query(1), false), cache.get(address, "token", &query(2), false));
+        let target = (None, Some(vec![2]));
+        assert!(result == target, "expired \nresult: {:?}\ntarget: {:?}", result, target);
+        // Capacity, the least recently used removed
+        let cache = ReplyCache::new(Duration::from_secs(60), 3);
+        for id in 1..=3 {
+            cache.insert(address, "token", &query(id), false, vec![id as u8]);
+        }
+        cache.get(address, "token", &query(1), false);
+        cache.insert(address, "token", &query(4), false, vec![4]);
+        let result: Vec<_> = (1..=4).map(|id| cache.get(address, "token", &query(id), false)).collect();
+        let target = vec![Some(vec![1]), None, Some(vec![3]), Some(vec![4])];
+        assert!(result == target, "capacity \nresult: {:?}\ntarget: {:?}", result, target);
+        // Invalidation
+        cache.invalidate(&query(3));
+        let result: Vec<_> = (1..=4).map(|id| cache.get(address, "token", &query(id), false)).collect();
+        let target = vec![Some(vec![1]), None, None, Some(vec![4])];
+        assert!(result == target, "invalidate \nresult: {:?}\ntarget: {:?}", result, target);
+        cache.clear();
+        let result = cache.is_empty();
+        assert!(result, "clear \nresult: {:?}\ntarget: {:?}", result, true);
+        test_duration.exit();
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(test)]
+
+mod retry_policy {
+    use std::{sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::client::retry_policy::{RetryClass, RetryPolicy};
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing the delay between attempts
+    #[test]
+    fn delay() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test RetryPolicy.delay");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let policy = RetryPolicy::new(10, Duration::from_millis(100)).with_max_backoff(Duration::from_millis(500));
+        let test_data = [
+            (01, 1, Duration::from_millis(100)),
+            (02, 2, Duration::from_millis(200)),
+            (03, 3, Duration::from_millis(400)),
+            (04, 4, Duration::from_millis(500)),
+            (05, 64, Duration::from_millis(500)),
+        ];
+        for (step, attempt, target) in test_data {
+            let result = policy.delay(attempt);
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the retryable failures
+    #[test]
+    fn retryable() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test RetryPolicy.retryable");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let all = RetryPolicy::new(3, Duration::ZERO);
+        let connect = RetryPolicy::new(3, Duration::ZERO).with_retry_on(&[RetryClass::Connect]);
+        let test_data = [
+            (01, &all, RetryClass::Connect, 1, true),
+            (02, &all, RetryClass::Write, 2, true),
+            (03, &all, RetryClass::ReadTimeout, 3, false),
+            (04, &connect, RetryClass::Connect, 1, true),
+            (05, &connect, RetryClass::Write, 1, false),
+            (06, &connect, RetryClass::ReadTimeout, 1, false),
+        ];
+        for (step, policy, class, attempt, target) in test_data {
+            let result = policy.retryable(class, attempt);
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let result = RetryPolicy::none().retryable(RetryClass::Connect, 1);
+        assert!(!result, "none \nresult: {:?}\ntarget: {:?}", result, false);
+        test_duration.exit();
+    }
+}
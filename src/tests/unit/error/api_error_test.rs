@@ -6,7 +6,7 @@ mod tests {
     use std::{sync::Once, time::{Duration, Instant}};
     use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
     use testing::stuff::max_test_duration::TestDuration;
-    use crate::error::api_error::ApiError; 
+    use crate::error::{api_error::ApiError, api_error_code::ApiErrorCode}; 
     
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     // use super::*;
@@ -45,6 +45,8 @@ mod tests {
             (r#"{"message":"mmm"}"#, ApiError::new(String::from("mmm"), String::new())),
             (r#"{"message":"", "details":""}"#, ApiError::new(String::new(), String::new())),
             (r#"{"message":"mmm", "details":"ddd"}"#, ApiError::new(String::from("mmm"), String::from("ddd"))),
+            (r#"{"message":"mmm", "code":"tokenExpired"}"#, ApiError::new(String::from("mmm"), String::new()).with_code(ApiErrorCode::TokenExpired)),
+            (r#"{"message":"mmm", "code":"someNewCode"}"#, ApiError::new(String::from("mmm"), String::new()).with_code(ApiErrorCode::Unknown)),
         ];
         for (err, target) in errs {
 
@@ -2,3 +2,5 @@ mod error;
 mod server;
 mod client;
 mod api;
+#[cfg(feature = "cli")]
+mod cli;
\ No newline at end of file
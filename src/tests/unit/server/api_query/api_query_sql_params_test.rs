@@ -0,0 +1,229 @@
+#[cfg(test)]
+
+mod api_query_sql_params {
+    use std::{sync::Once, time::Duration};
+    use bytes::BytesMut;
+    use postgres::types::{IsNull, ToSql, Type};
+    use serde_json::json;
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::server::api_query::{api_query_sql::ApiQuerySql, api_query_sql_params::{ApiQuerySqlParams, SqlParam}, sql_lexer::SqlDialect};
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing [ApiQuerySql] deserialized with `params` field
+    #[test]
+    fn deserialize() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("api_query_sql_params_from_json");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let test_data = [
+            (1, json!({"database": "db", "sql": "select 1;"}), Some(ApiQuerySqlParams::Positional(vec![]))),
+            (2, json!({"database": "db", "sql": "select $1;", "params": [1]}), Some(ApiQuerySqlParams::Positional(vec![json!(1)]))),
+            (3, json!({"database": "db", "sql": "select :a;", "params": {"a": "b"}}), Some(ApiQuerySqlParams::Named(json!({"a": "b"}).as_object().unwrap().to_owned()))),
+            (4, json!({"database": "db", "sql": "select $1;", "params": "1"}), None),
+            (5, json!({"database": "db", "sql": "select $1;", "params": 1}), None),
+            (6, json!({"database": "db", "sql": "select 1;", "params": null}), Some(ApiQuerySqlParams::Positional(vec![]))),
+        ];
+        for (step, json, target) in test_data {
+            let result = serde_json::from_value::<ApiQuerySql>(json).map(|query| query.params).ok();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing [ApiQuerySqlParams::bind]
+    #[test]
+    fn bind() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("api_query_sql_params_bind");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let named = |value: serde_json::Value| ApiQuerySqlParams::Named(value.as_object().unwrap().to_owned());
+        let test_data = [
+            (
+                1,
+                SqlDialect::Postgres,
+                "select * from t where a = $1 and b = $2;",
+                ApiQuerySqlParams::Positional(vec![json!(1), json!("b")]),
+                Some(("select * from t where a = $1 and b = $2;", vec![json!(1), json!("b")])),
+            ),
+            (
+                2,
+                SqlDialect::Postgres,
+                "select * from t where a = :a and b = :b_2 or a = :a;",
+                named(json!({"b_2": "b", "a": 1})),
+                Some(("select * from t where a = $1 and b = $2 or a = $1;", vec![json!(1), json!("b")])),
+            ),
+            (
+                3,
+                SqlDialect::Postgres,
+                "select a::text, ':b' as \":c\" from t where a = :a;",
+                named(json!({"a": null})),
+                Some(("select a::text, ':b' as \":c\" from t where a = $1;", vec![json!(null)])),
+            ),
+            (
+                4,
+                SqlDialect::Postgres,
+                "select * from t where a = :a and b = :b;",
+                named(json!({"a": 1})),
+                None,
+            ),
+            (
+                5,
+                SqlDialect::Postgres,
+                "select :a -- :x\n, :b /* :y /* :z */ */ from t",
+                named(json!({"a": 1, "b": 2})),
+                Some(("select $1 -- :x\n, $2 /* :y /* :z */ */ from t", vec![json!(1), json!(2)])),
+            ),
+            (
+                6,
+                SqlDialect::Postgres,
+                "select $tag$ :x ' $tag$, $$ :y $$, $1, :a",
+                named(json!({"a": 1})),
+                Some(("select $tag$ :x ' $tag$, $$ :y $$, $1, $1", vec![json!(1)])),
+            ),
+            (
+                7,
+                SqlDialect::Postgres,
+                "select E'it\\'s :x', 'it''s :y', :a",
+                named(json!({"a": 1})),
+                Some(("select E'it\\'s :x', 'it''s :y', $1", vec![json!(1)])),
+            ),
+            (
+                8,
+                SqlDialect::Postgres,
+                "select a[1:n], a[:n][2:3], a[:a::int] from t where b = :b::int",
+                named(json!({"b": 2})),
+                Some(("select a[1:n], a[:n][2:3], a[:a::int] from t where b = $1::int", vec![json!(2)])),
+            ),
+            (
+                9,
+                SqlDialect::Sqlite,
+                "select [:x], `:y`, :a from t -- :z",
+                named(json!({"a": 1})),
+                Some(("select [:x], `:y`, $1 from t -- :z", vec![json!(1)])),
+            ),
+        ];
+        for (step, dialect, sql, params, target) in test_data {
+            let result = params.bind(sql, dialect).ok();
+            let target = target.map(|(sql, params)| (sql.to_owned(), params.into_iter().map(SqlParam).collect::<Vec<_>>()));
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing [ApiQuerySqlParams::bind_anonymous]
+    #[test]
+    fn bind_anonymous() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("api_query_sql_params_bind_anonymous");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let named = |value: serde_json::Value| ApiQuerySqlParams::Named(value.as_object().unwrap().to_owned());
+        let test_data = [
+            (
+                1,
+                "select * from t where a = ? and b = ?;",
+                ApiQuerySqlParams::Positional(vec![json!(1), json!("b")]),
+                Some(("select * from t where a = ? and b = ?;", vec![json!(1), json!("b")])),
+            ),
+            (
+                2,
+                "select * from t where a = :a and b = :b_2 or a = :a;",
+                named(json!({"b_2": "b", "a": 1})),
+                Some(("select * from t where a = ? and b = ? or a = ?;", vec![json!(1), json!("b"), json!(1)])),
+            ),
+            (
+                3,
+                "select ':b' as `:d`, \":c\" from t where a = :a;",
+                named(json!({"a": null})),
+                Some(("select ':b' as `:d`, \":c\" from t where a = ?;", vec![json!(null)])),
+            ),
+            (
+                4,
+                "select * from t where a = :a and b = :b;",
+                named(json!({"a": 1})),
+                None,
+            ),
+            (
+                5,
+                "select '\\'' , :p",
+                named(json!({"p": 1})),
+                Some(("select '\\'' , ?", vec![json!(1)])),
+            ),
+            (
+                6,
+                "select 'it\\'s :x', \"a\\\":y\", :a # :z\n, :b -- :w\nfrom t",
+                named(json!({"a": 1, "b": 2})),
+                Some(("select 'it\\'s :x', \"a\\\":y\", ? # :z\n, ? -- :w\nfrom t", vec![json!(1), json!(2)])),
+            ),
+            (
+                7,
+                "select 1--:a, :b/*:c*/",
+                named(json!({"a": 1, "b": 2})),
+                Some(("select 1--?, ?/*:c*/", vec![json!(1), json!(2)])),
+            ),
+        ];
+        for (step, sql, params, target) in test_data {
+            let result = params.bind_anonymous(sql).ok();
+            let target = target.map(|(sql, params)| (sql.to_owned(), params.into_iter().map(SqlParam).collect::<Vec<_>>()));
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing [SqlParam::to_sql] conversion of the JSON value into the postgres type
+    #[test]
+    fn to_sql() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("api_query_sql_params_to_sql");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let test_data: [(i32, serde_json::Value, Type, Option<(bool, Vec<u8>)>); 9] = [
+            (1, json!(null), Type::INT4, Some((true, vec![]))),
+            (2, json!(true), Type::BOOL, Some((false, vec![1]))),
+            (3, json!(258), Type::INT2, Some((false, vec![1, 2]))),
+            (4, json!(258), Type::INT4, Some((false, vec![0, 0, 1, 2]))),
+            (5, json!(70000), Type::INT2, None),
+            (6, json!("abc"), Type::TEXT, Some((false, b"abc".to_vec()))),
+            (7, json!(12), Type::TEXT, Some((false, b"12".to_vec()))),
+            (8, json!("abc"), Type::INT8, None),
+            (9, json!("2000-01-01T00:00:00Z"), Type::TIMESTAMPTZ, Some((false, vec![0; 8]))),
+        ];
+        for (step, value, ty, target) in test_data {
+            let mut out = BytesMut::new();
+            let result = SqlParam(value).to_sql(&ty, &mut out)
+                .map(|is_null| (matches!(is_null, IsNull::Yes), out.to_vec()))
+                .ok();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+}
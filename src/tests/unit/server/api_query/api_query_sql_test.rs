@@ -0,0 +1,80 @@
+#[cfg(test)]
+
+mod api_query_sql {
+    use std::{sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::server::api_query::api_query_sql::ApiQuerySql;
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing [ApiQuerySql::read_only] detects the modifying statements
+    #[test]
+    fn read_only() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("api_query_sql_read_only");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        // (step, sql, target)
+        let test_data = [
+            (01, "select * from item where id = $1", true),
+            (02, "  SELECT name FROM item; select 1;", true),
+            (03, "with ids as (select id from item) select * from ids", true),
+            (04, "select 'delete from item' as text, \"update\" from item", true),
+            (05, "select 1 -- delete from item\n", true),
+            (06, "select /* drop; update */ 1", true),
+            (07, "explain select * from item", true),
+            (08, "show tables", true),
+            (09, "insert into item (name) values ('bolt')", false),
+            (10, "update item set name = 'x'", false),
+            (11, "select 1; delete from item", false),
+            (12, "with gone as (delete from item returning id) select * from gone", false),
+            (13, "select * into copy from item", false),
+            (14, "select * from item for update", false),
+            (15, "select nextval('item_id_seq')", false),
+            (16, "explain analyze create table copy as select * from item", false),
+            (17, "create table item (id int)", false),
+            (18, "pragma journal_mode = wal", false),
+            (19, "", false),
+            (20, " ; -- nothing", false),
+            (21, "WITH a AS (SELECT $$'$$), x AS (DELETE FROM t RETURNING 1) SELECT 1 --'", false),
+            (22, "select $tag$ ' $tag$; delete from item; select $tag$ ' $tag$", false),
+            (23, "select E'\\''; delete from item; --'", false),
+            (24, "select 'a\\'; delete from item; --'", false),
+            (25, "select \"a\\\"; delete from item; --\"", false),
+            (26, "select 1 --x; delete from item", false),
+            (27, "select 1 # '\n; delete from item; -- '", false),
+            (28, "select 1 /*! ; delete from item */", false),
+            (29, "select 1 /* /* */ ; delete from item; /* */", false),
+            (30, "select [a'] from item; delete from item; select [']", false),
+            (31, "select 'unterminated", false),
+            (32, "select 1 /* unterminated", false),
+            (33, "select $tag$ unterminated", false),
+            (34, "select 1 \\; delete from item", false),
+            (35, "select $$delete$$ as text, $1::int, item.id$ from item where id = $2", true),
+            (36, "select e'\\\\', 'it''s', \"a\"\"b\" from item", true),
+            (37, "select 1 -- delete\n, 2 /* update */ from item", true),
+        ];
+        for (step, sql, target) in test_data {
+            let result = ApiQuerySql::read_only(sql);
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+}
@@ -7,8 +7,9 @@ mod tests {
     use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
 
     use crate::{
+        api::query::api_query_kind::ApiQueryKind,
         error::api_error::ApiError,
-        server::api_query::{api_query::ApiQuery, api_query_error::ApiQueryError, api_query_sql::ApiQuerySql, api_query_type::ApiQueryType},
+        server::api_query::{api_query::ApiQuery, api_query_batch::{ApiQueryBatch, ApiQueryBatchStatement}, api_query_cancel::ApiQueryCancel, api_query_subscribe::ApiQuerySubscribe, api_query_cursor::{ApiQueryCursor, ApiQueryCursorOpen}, api_query_error::ApiQueryError, api_query_executable::ApiQueryExecutable, api_query_login::ApiQueryLogin, api_query_python::ApiQueryPython, api_query_sql::ApiQuerySql, api_query_sql_params::ApiQuerySqlParams, api_query_type::ApiQueryType},
     };
 
     // Note this useful idiom: importing names from outer (for mod tests) scope.
@@ -42,6 +43,11 @@ mod tests {
     #[derive(std::fmt::Debug)]
     enum ApiQueryTypeVariant {
         Sql,
+        Batch,
+        Cursor,
+        Cancel,
+        Subscribe,
+        Login,
         Python,
         Executable,
         Unknown,
@@ -63,7 +69,7 @@ mod tests {
                 input: r#"{"authToken":"123zxy456!@#","id":"11","sql":{"database":"database","sql":"select id from do_data;"}}"#,
                 out: ApiQuery::new(
                     "123zxy456!@#".into(), "11".into(), 
-                    ApiQueryType::Sql(ApiQuerySql{ database: "database".to_string(), sql: "select id from do_data;".to_string() }), 
+                    ApiQueryType::Sql(ApiQuerySql{ database: "database".to_string(), sql: "select id from do_data;".to_string(), params: ApiQuerySqlParams::default() }), 
                     "", 
                     false, false
                 ),
@@ -73,13 +79,112 @@ mod tests {
                 input: r#"{"authToken":"123zxy456!@#","id":"12","debug":true,"sql":{"database":"database","sql":"select id from do_data;"}}"#,
                 out: ApiQuery::new(
                     "123zxy456!@#".into(), "12".into(), 
-                    ApiQueryType::Sql(ApiQuerySql{ database: "database".to_string(), sql: "select id from do_data;".to_string() }), 
+                    ApiQueryType::Sql(ApiQuerySql{ database: "database".to_string(), sql: "select id from do_data;".to_string(), params: ApiQuerySqlParams::default() }), 
                     r#"{"authToken":"123zxy456!@#","id":"12","debug":true,"sql":{"database":"database","sql":"select id from do_data;"}}"#, 
                     false, true
                 ),
                 queryVariant: ApiQueryTypeVariant::Sql,
             },
 
+            TestEntry {
+                input: r#"{"authToken":"123zxy456!@#","id":"14","batch":{"database":"database","statements":[{"sql":"insert into t (a) values ($1);","params":[1]},{"sql":"select * from t;"}]}}"#,
+                out: ApiQuery::new(
+                    "123zxy456!@#".into(), "14".into(), 
+                    ApiQueryType::Batch(ApiQueryBatch {
+                        database: "database".to_string(),
+                        statements: vec![
+                            ApiQueryBatchStatement { sql: "insert into t (a) values ($1);".to_string(), params: ApiQuerySqlParams::Positional(vec![serde_json::json!(1)]) },
+                            ApiQueryBatchStatement { sql: "select * from t;".to_string(), params: ApiQuerySqlParams::default() },
+                        ],
+                    }), 
+                    "", 
+                    false, false
+                ),
+                queryVariant: ApiQueryTypeVariant::Batch,
+            },
+            TestEntry {
+                input: r#"{"authToken":"123zxy456!@#","id":"15","batch":{"database":"database","statements":[]}}"#,
+                out: ApiQuery::new(
+                    "123zxy456!@#".into(), "15".into(), 
+                    ApiQueryType::Error(ApiQueryError::new(ApiError::new("", ""))), 
+                    "", 
+                    false, false
+                ),
+                queryVariant: ApiQueryTypeVariant::Error,
+            },
+            TestEntry {
+                input: r#"{"authToken":"123zxy456!@#","id":"16","cursor":{"open":{"database":"database","sql":"select * from t;","pageSize":10}}}"#,
+                out: ApiQuery::new(
+                    "123zxy456!@#".into(), "16".into(), 
+                    ApiQueryType::Cursor(ApiQueryCursor::Open(ApiQueryCursorOpen {
+                        query: ApiQuerySql{ database: "database".to_string(), sql: "select * from t;".to_string(), params: ApiQuerySqlParams::default() },
+                        page_size: 10,
+                    })), 
+                    "", 
+                    false, false
+                ),
+                queryVariant: ApiQueryTypeVariant::Cursor,
+            },
+            TestEntry {
+                input: r#"{"authToken":"123zxy456!@#","id":"17","cursor":{"next":"1"}}"#,
+                out: ApiQuery::new(
+                    "123zxy456!@#".into(), "17".into(), 
+                    ApiQueryType::Cursor(ApiQueryCursor::Next("1".to_string())), 
+                    "", 
+                    false, false
+                ),
+                queryVariant: ApiQueryTypeVariant::Cursor,
+            },
+            TestEntry {
+                input: r#"{"authToken":"123zxy456!@#","id":"18","cancel":{"id":"17"}}"#,
+                out: ApiQuery::new(
+                    "123zxy456!@#".into(), "18".into(), 
+                    ApiQueryType::Cancel(ApiQueryCancel { id: "17".to_string() }), 
+                    "", 
+                    false, false
+                ),
+                queryVariant: ApiQueryTypeVariant::Cancel,
+            },
+            TestEntry {
+                input: r#"{"authToken":"123zxy456!@#","id":"18","keepAlive":true,"subscribe":{"database":"database","channels":["event","alarm"]}}"#,
+                out: ApiQuery::new(
+                    "123zxy456!@#".into(), "18".into(), 
+                    ApiQueryType::Subscribe(ApiQuerySubscribe { database: "database".to_string(), channels: vec!["event".to_string(), "alarm".to_string()] }), 
+                    "", 
+                    true, false
+                ),
+                queryVariant: ApiQueryTypeVariant::Subscribe,
+            },
+            TestEntry {
+                input: r#"{"authToken":"123zxy456!@#","id":"18","keepAlive":true,"subscribe":{"database":"database","channels":[]}}"#,
+                out: ApiQuery::new(
+                    "123zxy456!@#".into(), "18".into(), 
+                    ApiQueryType::Error(ApiQueryError::new(ApiError::new("", ""))), 
+                    "", 
+                    true, false
+                ),
+                queryVariant: ApiQueryTypeVariant::Error,
+            },
+            TestEntry {
+                input: r#"{"authToken":"","id":"19","keepAlive":true,"login":{"user":"user","pass":"pass"}}"#,
+                out: ApiQuery::new(
+                    "".into(), "19".into(), 
+                    ApiQueryType::Login(ApiQueryLogin { user: "user".to_string(), pass: "pass".to_string() }), 
+                    "", 
+                    true, false
+                ),
+                queryVariant: ApiQueryTypeVariant::Login,
+            },
+            TestEntry {
+                input: r#"{"authToken":"","id":"20","login":{"user":"user"}}"#,
+                out: ApiQuery::new(
+                    "".into(), "20".into(), 
+                    ApiQueryType::Error(ApiQueryError::new(ApiError::new("", ""))), 
+                    "", 
+                    false, false
+                ),
+                queryVariant: ApiQueryTypeVariant::Error,
+            },
             TestEntry {
                 input: r#"{"authToken":"123zxy456!@#","id":"13","sql":{"database":"database","sql":"select id from do_data;"},"python":{"script":"py-test","params":{"a": 4, "b": 7}}}"#,
                 out: ApiQuery::new(
@@ -170,6 +275,36 @@ mod tests {
                         "\nparsed apiQuery query: 'ApiQueryType::Sql' \ntarget apiQuery query: {:?}", testEntry.queryVariant,
                     );        
                 },
+                ApiQueryType::Batch(_) => {
+                    assert!(
+                        apiQuery.keep_alive == testEntry.out.keep_alive, 
+                        "\nparsed apiQuery query: 'ApiQueryType::Batch' \ntarget apiQuery query: {:?}", testEntry.queryVariant,
+                    );
+                },
+                ApiQueryType::Cursor(_) => {
+                    assert!(
+                        apiQuery.keep_alive == testEntry.out.keep_alive, 
+                        "\nparsed apiQuery query: 'ApiQueryType::Cursor' \ntarget apiQuery query: {:?}", testEntry.queryVariant,
+                    );
+                },
+                ApiQueryType::Cancel(_) => {
+                    assert!(
+                        apiQuery.keep_alive == testEntry.out.keep_alive, 
+                        "\nparsed apiQuery query: 'ApiQueryType::Cancel' \ntarget apiQuery query: {:?}", testEntry.queryVariant,
+                    );
+                },
+                ApiQueryType::Subscribe(_) => {
+                    assert!(
+                        apiQuery.query() == testEntry.out.query() && apiQuery.keep_alive == testEntry.out.keep_alive, 
+                        "\nparsed apiQuery query: 'ApiQueryType::Subscribe' \ntarget apiQuery query: {:?}", testEntry.queryVariant,
+                    );
+                },
+                ApiQueryType::Login(_) => {
+                    assert!(
+                        apiQuery.keep_alive == testEntry.out.keep_alive, 
+                        "\nparsed apiQuery query: 'ApiQueryType::Login' \ntarget apiQuery query: {:?}", testEntry.queryVariant,
+                    );
+                },
                 ApiQueryType::Python(_) => {
                     assert!(
                         apiQuery.keep_alive == testEntry.out.keep_alive, 
@@ -198,4 +333,91 @@ mod tests {
     
         }    
     }
+    ///
+    /// Testing the password of the `login` query never comes back in the debug reply
+    #[test]
+    fn login_pass_masked() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let input = r#"{"authToken":"","id":"1","debug":true,"login":{"user":"user","pass":"secret pass"}}"#;
+        let api_query = ApiQuery::from_bytes(input.as_bytes());
+        let result = api_query.query();
+        let target = ApiQueryType::Login(ApiQueryLogin { user: "user".to_string(), pass: "secret pass".to_string() });
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        let result = api_query.src_query(true);
+        assert!(!result.contains("secret pass"), "\nresult: {:?}\ntarget: {:?}", result, "password masked");
+        let result = format!("{:?}", api_query);
+        assert!(!result.contains("secret pass"), "\nresult: {:?}\ntarget: {:?}", result, "password masked");
+    }
+    ///
+    /// Testing the query kinds built by the client are parsed by the server into the same shared model
+    #[test]
+    fn shared_model() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let params = serde_json::json!({"a": 4, "b": [7]}).as_object().unwrap().clone();
+        let test_data = [
+            ApiQueryKind::Sql(ApiQuerySql::new("database", "select * from event where id = :id;")
+                .with_params(ApiQuerySqlParams::Named(params.clone()))),
+            ApiQueryKind::Batch(ApiQueryBatch::new("database", vec![
+                ApiQueryBatchStatement::new("delete from event where id = $1;").with_params(ApiQuerySqlParams::Positional(vec![serde_json::json!(1)])),
+            ])),
+            ApiQueryKind::Cursor(ApiQueryCursor::Open(ApiQueryCursorOpen::new(ApiQuerySql::new("database", "select 1;"), 10))),
+            ApiQueryKind::Cursor(ApiQueryCursor::Next("1".to_owned())),
+            ApiQueryKind::Cancel(ApiQueryCancel::new("2")),
+            ApiQueryKind::Subscribe(ApiQuerySubscribe::new("database", &["event"])),
+            ApiQueryKind::Login(ApiQueryLogin::new("user", "pass")),
+            ApiQueryKind::Python(ApiQueryPython::new("script", params.clone())),
+            ApiQueryKind::Executable(ApiQueryExecutable::new("app", params.clone())),
+        ];
+        for (step, kind) in test_data.into_iter().enumerate() {
+            let mut json = serde_json::to_value(&kind).unwrap();
+            json["authToken"] = serde_json::json!("token");
+            json["id"] = serde_json::json!(step.to_string());
+            let result = ApiQuery::from_bytes(json.to_string().as_bytes()).query();
+            let target = match kind {
+                ApiQueryKind::Sql(query) => ApiQueryType::Sql(query),
+                ApiQueryKind::Batch(query) => ApiQueryType::Batch(query),
+                ApiQueryKind::Cursor(query) => ApiQueryType::Cursor(query),
+                ApiQueryKind::Cancel(query) => ApiQueryType::Cancel(query),
+                ApiQueryKind::Subscribe(query) => ApiQueryType::Subscribe(query),
+                ApiQueryKind::Login(query) => ApiQueryType::Login(query),
+                ApiQueryKind::Python(query) => ApiQueryType::Python(query),
+                ApiQueryKind::Executable(query) => ApiQueryType::Executable(query),
+            };
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+    }    ///
+    /// Testing the invalid queries are replied with the error pointing to the invalid field
+    #[test]
+    fn invalid_field() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let test_data = [
+            (01, serde_json::json!({"sql": {"database": "db"}}), "API SQL Service - invalid query (near field \"sql\")"),
+            (02, serde_json::json!({"sql": {"database": "db", "sql": 1}}), "API SQL Service - invalid query (near field \"sql\")"),
+            (03, serde_json::json!({"sql": {"database": "db", "sql": "select 1;", "params": "1"}}), "API SQL Service - invalid query (near field \"params\")"),
+            (04, serde_json::json!({"batch": {"database": "db", "statements": []}}), "API SQL Service - invalid query (near field \"statements\")"),
+            (05, serde_json::json!({"batch": {"database": "db", "statements": [{"sql": "select 1;"}, {"params": [1]}]}}), "API SQL Service - invalid query (near field \"statements[1].sql\")"),
+            (06, serde_json::json!({"cursor": {"open": {"database": "db", "sql": "select 1;", "pageSize": 0}}}), "API SQL Service - invalid query (near field \"open.pageSize\")"),
+            (07, serde_json::json!({"cursor": {"rewind": "1"}}), "API SQL Service - invalid query (near field \"cursor\")"),
+            (08, serde_json::json!({"cancel": {"id": 1}}), "API Service - invalid cancel query (near field \"id\")"),
+            (09, serde_json::json!({"subscribe": {"database": "db", "channels": ["event", ""]}}), "API SQL Service - invalid subscribe query (near field \"channels\")"),
+            (10, serde_json::json!({"login": {"user": "user"}}), "API Auth Service - invalid login query (near field \"pass\")"),
+            (11, serde_json::json!({"python": {"script": "script", "params": [1]}}), "API Python Script Service - invalid query (near field \"params\")"),
+            (12, serde_json::json!({"executable": {"params": {}}}), "API Executable service - invalid query (near field \"name\")"),
+        ];
+        for (step, mut json, target) in test_data {
+            json["authToken"] = serde_json::json!("token");
+            json["id"] = serde_json::json!(step.to_string());
+            let result = match ApiQuery::from_bytes(json.to_string().as_bytes()).query() {
+                ApiQueryType::Error(err) => err.err().asJason(false)["message"].as_str().unwrap_or_default().to_owned(),
+                query => format!("{:?}", query),
+            };
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+    }
 }
\ No newline at end of file
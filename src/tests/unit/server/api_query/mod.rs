@@ -1 +1,3 @@
-mod api_query_test;
\ No newline at end of file
+mod api_query_test;
+mod api_query_sql_params_test;
+mod api_query_sql_test;
@@ -0,0 +1,202 @@
+#[cfg(test)]
+
+mod api_server {
+    use std::{io::{Read, Write}, net::{SocketAddr, TcpStream}, sync::{atomic::{AtomicUsize, Ordering}, Arc, Once}, time::Duration};
+    use sal_core::dbg::Dbg;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::{
+        api::{message::api_framing::ApiFraming, reply::api_reply::ApiReply},
+        client::{api_query::{ApiQuery, ApiQueryExecutable, ApiQueryKind, ApiQueryPython, ApiQuerySql}, api_request::ApiRequest},
+        error::{api_error::ApiError, api_error_code::ApiErrorCode},
+        server::{
+            api_query::{api_query::ApiQuery as ServerQuery, api_query_type::{ApiQueryType, ApiQueryTypeName}, row_map::RowMap},
+            api_server::{api_handler::ApiHandlerReply, api_router::ApiRouter, api_server::ApiServer, api_session::ApiSession},
+            auth::{auth_operation::AuthOperation, auth_service::AuthService},
+        },
+    };
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the router replying `sql` with the source sql & failing `python`
+    fn router() -> ApiRouter {
+        ApiRouter::new()
+            .with_handler(ApiQueryTypeName::Sql, |query: &ServerQuery, _: &mut ApiSession| match query.query() {
+                ApiQueryType::Sql(sql) => Ok(ApiHandlerReply::new(vec![RowMap::from([("sql".to_owned(), json!(sql.sql))])])),
+                _ => Err(ApiError::new("unexpected", "")),
+            })
+            .with_handler(ApiQueryTypeName::Python, |_: &ServerQuery, _: &mut ApiSession| {
+                Err(ApiError::new("script failed", "exit code 1"))
+            })
+    }
+    ///
+    /// Returns the reply on the raw message
+    fn raw(address: SocketAddr, id: u32, bytes: &[u8]) -> (u32, serde_json::Value) {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        stream.write_all(&ApiFraming::default().frame(id, bytes)).unwrap();
+        let mut header = [0; 10];
+        stream.read_exact(&mut header).unwrap();
+        let size = u32::from_be_bytes([header[6], header[7], header[8], header[9]]) as usize;
+        let mut data = vec![0; size];
+        stream.read_exact(&mut data).unwrap();
+        (u32::from_be_bytes([header[1], header[2], header[3], header[4]]), serde_json::from_slice(&data).unwrap())
+    }
+    ///
+    /// Testing the queries replied by the registered handlers
+    #[test]
+    fn reply() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiServer.reply");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let mut server = ApiServer::new(&dbg, "127.0.0.1:0", router());
+        let address = server.start().unwrap();
+        assert!(server.address() == Some(address), "\nresult: {:?}\ntarget: {:?}", server.address(), Some(address));
+        // (step, query, debug, target data, target error)
+        let test_data = [
+            (01, ApiQueryKind::Sql(ApiQuerySql::new("db", "select 1")), false, vec![json!({"sql": "select 1"})], None),
+            (02, ApiQueryKind::Sql(ApiQuerySql::new("db", "select 2")), true, vec![json!({"sql": "select 2"})], None),
+            (03, ApiQueryKind::Python(ApiQueryPython::new("script", serde_json::Map::new())), false, vec![], Some(json!({"message": "script failed"}))),
+            (04, ApiQueryKind::Python(ApiQueryPython::new("script", serde_json::Map::new())), true, vec![], Some(json!({"message": "script failed", "details": "exit code 1"}))),
+            (05, ApiQueryKind::Executable(ApiQueryExecutable::new("exec", serde_json::Map::new())), false, vec![], Some(json!({"message": "API Service - query of type 'executable' not supported"}))),
+        ];
+        for (step, query, debug, target_data, target_error) in test_data {
+            let query = ApiQuery::new(query, true);
+            let mut request = ApiRequest::new(&dbg, address, "token", query.clone(), true, debug);
+            let reply = ApiReply::try_from(request.fetch(true).unwrap()).unwrap();
+            let result: Vec<serde_json::Value> = reply.data.iter().map(|row| serde_json::to_value(row).unwrap()).collect();
+            assert!(result == target_data, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_data);
+            let result = reply.has_error().then(|| serde_json::to_value(&reply.error.clone().debug(debug)).unwrap());
+            assert!(result == target_error, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_error);
+            assert!(reply.auth_token == "token", "step {} \nresult: {:?}\ntarget: {:?}", step, reply.auth_token, "token");
+            assert!(reply.keep_alive, "step {} \nresult: {:?}\ntarget: {:?}", step, reply.keep_alive, true);
+            let result = reply.query.is_empty();
+            assert!(result == !debug, "step {} \nresult: {:?}\ntarget: {:?}", step, result, !debug);
+        }
+        server.stop();
+        assert!(server.address().is_none(), "\nresult: {:?}\ntarget: {:?}", server.address(), None::<SocketAddr>);
+        test_duration.exit();
+    }
+    ///
+    /// Testing the unknown & invalid queries replied with the error, id & frame id echoed
+    #[test]
+    fn errors() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiServer.errors");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let mut server = ApiServer::new(&dbg, "127.0.0.1:0", router());
+        let address = server.start().unwrap();
+        // (step, frame id, query, target id, target error message)
+        let test_data = [
+            (01, 7, json!({"authToken": "t1", "id": "11", "keepAlive": false, "debug": false, "foo": {}}).to_string(), "11", "API Service - unknown query"),
+            (02, 8, json!({"authToken": "t2", "id": "12", "keepAlive": false, "debug": false, "sql": {"database": "db"}}).to_string(), "12", "API SQL Service - invalid query (near field \"sql\")"),
+            (03, 9, json!({"authToken": "t3", "id": "13", "keepAlive": false, "debug": false, "sql": {}, "python": {}}).to_string(), "13", ""),
+        ];
+        for (step, frame_id, query, target_id, target_error) in test_data {
+            let (result_frame_id, reply) = raw(address, frame_id, query.as_bytes());
+            assert!(result_frame_id == frame_id, "step {} \nresult: {:?}\ntarget: {:?}", step, result_frame_id, frame_id);
+            let reply = ApiReply::try_from(reply.to_string().into_bytes()).unwrap();
+            assert!(reply.id == target_id, "step {} \nresult: {:?}\ntarget: {:?}", step, reply.id, target_id);
+            assert!(reply.has_error(), "step {} \nresult: {:?}\ntarget: {:?}", step, reply.has_error(), true);
+            let result = serde_json::to_value(&reply.error).unwrap()["message"].as_str().unwrap().to_owned();
+            assert!(target_error.is_empty() || result == target_error, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_error);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the `login` replied & the `authToken` verified by the [AuthService]
+    #[test]
+    fn auth() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiServer.auth");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let auth = AuthService::new("secret", Duration::from_secs(3600)).with_user("user", "pass");
+        let mut server = ApiServer::new(&dbg, "127.0.0.1:0", router().with_auth(auth));
+        let address = server.start().unwrap();
+        let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("db", "select 1")), true);
+        // (step, request, target code)
+        let test_data = [
+            (1, ApiRequest::builder(&dbg).address(address).query(query.clone()).keep_alive(true).login("user", "pass").build().unwrap(), None),
+            (2, ApiRequest::builder(&dbg).address(address).query(query.clone()).keep_alive(true).auth_token("forged.token").build().unwrap(), Some(ApiErrorCode::TokenInvalid)),
+            (3, ApiRequest::builder(&dbg).address(address).query(query.clone()).keep_alive(true).login("user", "wrong").build().unwrap(), Some(ApiErrorCode::LoginFailed)),
+        ];
+        for (step, mut request, target) in test_data {
+            let result = match request.fetch(true) {
+                Ok(bytes) => ApiReply::try_from(bytes).unwrap().error.code(),
+                Err(err) => Some(if err.to_string().contains("login failed") {ApiErrorCode::LoginFailed} else {ApiErrorCode::Unknown}),
+            };
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the forbidden queries replied with the `forbidden` error before the handler called
+    #[test]
+    fn forbidden() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiServer.forbidden");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let handler_calls = calls.clone();
+        let router = ApiRouter::new()
+            .with_handler(ApiQueryTypeName::Sql, move |_: &ServerQuery, _: &mut ApiSession| {
+                handler_calls.fetch_add(1, Ordering::SeqCst);
+                Ok(ApiHandlerReply::new(vec![]))
+            })
+            .with_auth(AuthService::new("secret", Duration::from_secs(3600))
+                .with_user("reader", "pass")
+                .with_grant("reader", "db", &[AuthOperation::Read]));
+        let mut server = ApiServer::new(&dbg, "127.0.0.1:0", router);
+        let address = server.start().unwrap();
+        // (step, query, target code, target handler calls)
+        let test_data = [
+            (1, ApiQueryKind::Sql(ApiQuerySql::new("db", "select * from item")), None, 1),
+            (2, ApiQueryKind::Sql(ApiQuerySql::new("db", "delete from item")), Some(ApiErrorCode::Forbidden), 1),
+            (3, ApiQueryKind::Sql(ApiQuerySql::new("other", "select 1")), Some(ApiErrorCode::Forbidden), 1),
+            (4, ApiQueryKind::Python(ApiQueryPython::new("script", serde_json::Map::new())), Some(ApiErrorCode::Forbidden), 1),
+        ];
+        for (step, query, target, target_calls) in test_data {
+            let query = ApiQuery::new(query, true);
+            let mut request = ApiRequest::builder(&dbg).address(address).query(query).keep_alive(true).login("reader", "pass").build().unwrap();
+            let reply = ApiReply::try_from(request.fetch(true).unwrap()).unwrap();
+            let result = reply.error.code();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            let result = calls.load(Ordering::SeqCst);
+            assert!(result == target_calls, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_calls);
+        }
+        server.stop();
+        test_duration.exit();
+    }
+}
@@ -0,0 +1 @@
+mod api_server_test;
\ No newline at end of file
@@ -0,0 +1,165 @@
+#[cfg(test)]
+
+mod auth_service {
+    use std::{sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::{
+        error::api_error_code::ApiErrorCode,
+        server::{
+            api_query::{
+                api_query_batch::{ApiQueryBatch, ApiQueryBatchStatement}, api_query_cursor::{ApiQueryCursor, ApiQueryCursorOpen},
+                api_query_executable::ApiQueryExecutable, api_query_login::ApiQueryLogin, api_query_python::ApiQueryPython,
+                api_query_sql::ApiQuerySql, api_query_subscribe::ApiQuerySubscribe, api_query_type::ApiQueryType,
+            },
+            auth::{auth_operation::AuthOperation, auth_service::AuthService},
+            config::api_config::ApiConfig,
+            service::service_registry::ServiceRegistry,
+        },
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns `login` query
+    fn login(user: &str, pass: &str) -> ApiQueryLogin {
+        ApiQueryLogin { user: user.to_owned(), pass: pass.to_owned() }
+    }
+    ///
+    /// Testing [AuthService::login]
+    #[test]
+    fn login_() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("auth_service_login");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let auth = AuthService::new("secret", Duration::from_secs(3600))
+            .with_user("user", "pass");
+        let now = chrono::Utc::now().timestamp();
+        // (step, query, target)
+        let test_data = [
+            (1, login("user", "pass"), Ok("user")),
+            (2, login("user", "wrong"), Err(ApiErrorCode::LoginFailed)),
+            (3, login("user", "pass "), Err(ApiErrorCode::LoginFailed)),
+            (4, login("unknown", "pass"), Err(ApiErrorCode::LoginFailed)),
+        ];
+        for (step, query, target) in test_data {
+            let result = auth.login(&query);
+            match (&result, target) {
+                (Ok(token), Ok(user)) => {
+                    assert!(token.user == user, "step {} \nresult: {:?}\ntarget: {:?}", step, token.user, user);
+                    assert!(token.expires >= now + 3600, "step {} \nresult: {:?}\ntarget: {:?}", step, token.expires, now + 3600);
+                    let claims = auth.verify(&token.token).unwrap();
+                    assert!(claims.user == user, "step {} \nresult: {:?}\ntarget: {:?}", step, claims.user, user);
+                }
+                (Err(err), Err(code)) => assert!(err.code() == Some(code), "step {} \nresult: {:?}\ntarget: {:?}", step, err.code(), code),
+                (_, target) => panic!("step {} \nresult: {:?}\ntarget: {:?}", step, result, target),
+            }
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing [AuthService::verify] rejects expired, tampered & malformed tokens
+    #[test]
+    fn verify() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("auth_service_verify");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let auth = AuthService::new("secret", Duration::from_secs(3600));
+        let expired = AuthService::new("secret", Duration::ZERO).issue("user").token;
+        let other_secret = AuthService::new("other secret", Duration::from_secs(3600)).issue("user").token;
+        let valid = auth.issue("user").token;
+        let (payload, signature) = valid.split_once('.').unwrap();
+        let forged = AuthService::new("secret", Duration::from_secs(7200)).issue("admin").token;
+        let forged = format!("{}.{}", forged.split_once('.').unwrap().0, signature);
+        // (step, token, target)
+        let test_data = [
+            (1, valid.clone(), None),
+            (2, expired, Some(ApiErrorCode::TokenExpired)),
+            (3, other_secret, Some(ApiErrorCode::TokenInvalid)),
+            (4, forged, Some(ApiErrorCode::TokenInvalid)),
+            (5, payload.to_owned(), Some(ApiErrorCode::TokenInvalid)),
+            (6, format!("{}.!!!", payload), Some(ApiErrorCode::TokenInvalid)),
+            (7, String::new(), Some(ApiErrorCode::TokenInvalid)),
+        ];
+        for (step, token, target) in test_data {
+            let result = auth.verify(&token).err().and_then(|err| err.code());
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing [AuthService::authorize] allows the granted operations only,
+    /// the reading query of the user not granted `write` is read only
+    #[test]
+    fn authorize() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("auth_service_authorize");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let sql = |database: &str, sql: &str| ApiQueryType::Sql(ApiQuerySql::new(database, sql));
+        let yaml = "address: '127.0.0.1:0'\nservices:\n  - db:\n      name: archive\n      type: postgres\n      path: host\n      user: auditor\n      pass: secret\n      readOnly: true\n";
+        let registry = ServiceRegistry::from(ApiConfig::from_yaml(yaml, "").unwrap());
+        let auth = AuthService::new("secret", Duration::from_secs(3600))
+            .with_user("reader", "pass")
+            .with_grant("reader", "database", &[AuthOperation::Read])
+            .with_grant("reader", "py-test", &[AuthOperation::Execute])
+            .with_grant("admin", "*", &[AuthOperation::Read, AuthOperation::Write, AuthOperation::Execute])
+            .with_services(&registry);
+        let open_auth = AuthService::new("secret", Duration::from_secs(3600));
+        // (step, auth, user, query, target code, target read only)
+        let test_data = [
+            (01, &auth, "reader", sql("database", "select * from item"), None, true),
+            (02, &auth, "reader", sql("database", "with last as (select max(id) as id from item) select * from last"), None, true),
+            (03, &auth, "reader", sql("database", "update item set name = 'x'"), Some(ApiErrorCode::Forbidden), false),
+            (04, &auth, "reader", sql("database", "select 1; delete from item"), Some(ApiErrorCode::Forbidden), false),
+            (05, &auth, "reader", sql("other", "select 1"), Some(ApiErrorCode::Forbidden), false),
+            (06, &auth, "reader", ApiQueryType::Batch(ApiQueryBatch::new("database", vec![ApiQueryBatchStatement::new("select 1"), ApiQueryBatchStatement::new("insert into item default values")])), Some(ApiErrorCode::Forbidden), false),
+            (07, &auth, "reader", ApiQueryType::Cursor(ApiQueryCursor::Open(ApiQueryCursorOpen::new(ApiQuerySql::new("database", "select * from item"), 10))), None, true),
+            (08, &auth, "reader", ApiQueryType::Cursor(ApiQueryCursor::Next("1".to_owned())), None, false),
+            (09, &auth, "reader", ApiQueryType::Subscribe(ApiQuerySubscribe::new("database", &["events"])), None, true),
+            (10, &auth, "reader", ApiQueryType::Python(ApiQueryPython::new("py-test", Default::default())), None, false),
+            (11, &auth, "reader", ApiQueryType::Executable(ApiQueryExecutable::new("py-test-bin", Default::default())), Some(ApiErrorCode::Forbidden), false),
+            (12, &auth, "admin", sql("other", "drop table item"), None, false),
+            (13, &auth, "admin", ApiQueryType::Executable(ApiQueryExecutable::new("any", Default::default())), None, false),
+            (14, &auth, "auditor", sql("archive", "select * from log"), None, true),
+            (15, &auth, "auditor", sql("archive", "truncate log"), Some(ApiErrorCode::Forbidden), false),
+            (16, &auth, "unknown", sql("database", "select 1"), Some(ApiErrorCode::Forbidden), false),
+            (17, &open_auth, "unknown", sql("database", "drop table item"), None, false),
+            (18, &auth, "admin", sql("other", "select 1"), None, false),
+        ];
+        for (step, auth, user, query, target, target_read_only) in test_data {
+            let (result, read_only) = match auth.authorize(user, &query) {
+                Ok(read_only) => (None, read_only),
+                Err(err) => (err.code(), false),
+            };
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            assert!(read_only == target_read_only, "step {} \nresult: {:?}\ntarget: {:?}", step, read_only, target_read_only);
+        }
+        let result = auth.login(&login("auditor", "secret")).is_ok();
+        assert!(result, "\nresult: {:?}\ntarget: {:?}", result, true);
+        test_duration.exit();
+    }
+}
@@ -0,0 +1 @@
+mod auth_service_test;
@@ -0,0 +1,84 @@
+#[cfg(test)]
+
+mod cancel_registry {
+    use std::{os::unix::process::CommandExt, process::Command, sync::{atomic::{AtomicUsize, Ordering}, Arc, Once}, time::{Duration, Instant}};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use crate::server::cancel::cancel_registry::CancelRegistry;
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing [CancelRegistry] register / cancel / unregister
+    #[test]
+    fn cancel() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("cancel_registry_cancel");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let registry = CancelRegistry::default();
+        let cancelled = Arc::new(AtomicUsize::new(0));
+        let cancel = |cancelled: &Arc<AtomicUsize>| {
+            let cancelled = cancelled.clone();
+            Box::new(move || {
+                cancelled.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+        };
+        let _guard = registry.register("token", "1", cancel(&cancelled));
+        let result = registry.cancel("other token", "1").unwrap();
+        assert!(!result, "step 1 \nresult: {:?}\ntarget: {:?}", result, false);
+        let result = registry.cancel("token", "1").unwrap();
+        assert!(result, "step 2 \nresult: {:?}\ntarget: {:?}", result, true);
+        let result = registry.cancel("token", "1").unwrap();
+        assert!(!result, "step 3 \nresult: {:?}\ntarget: {:?}", result, false);
+        let guard = registry.register("token", "2", cancel(&cancelled));
+        drop(guard);
+        let result = registry.cancel("token", "2").unwrap();
+        assert!(!result, "step 4 \nresult: {:?}\ntarget: {:?}", result, false);
+        let result = cancelled.load(Ordering::SeqCst);
+        assert!(result == 1, "step 5 \nresult: {:?}\ntarget: {:?}", result, 1);
+        test_duration.exit();
+    }
+    ///
+    /// Testing [CancelRegistry::process] kills the process group
+    #[test]
+    fn process() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("cancel_registry_process");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let registry = CancelRegistry::default();
+        let mut child = Command::new("sh")
+            .args(["-c", "sleep 10 & sleep 10"])
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        let _guard = registry.register("token", "1", CancelRegistry::process(child.id()));
+        let time = Instant::now();
+        let result = registry.cancel("token", "1").unwrap();
+        assert!(result, "step 1 \nresult: {:?}\ntarget: {:?}", result, true);
+        let status = child.wait().unwrap();
+        assert!(!status.success(), "step 2 \nresult: {:?}\ntarget: {:?}", status, "killed");
+        assert!(time.elapsed() < Duration::from_secs(5), "step 3 \nresult: {:?}\ntarget: {:?}", time.elapsed(), "< 5 sec");
+        test_duration.exit();
+    }
+}
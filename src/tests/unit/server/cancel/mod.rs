@@ -0,0 +1 @@
+mod cancel_registry_test;
@@ -0,0 +1,158 @@
+#[cfg(test)]
+
+mod api_config {
+    use std::{fs, path::PathBuf, sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::server::config::{api_config::ApiConfig, null_defaults::NullDefaults, service_type::ServiceType};
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the folder containing `database.sqlite`, `script.py` & `bin/app` files and `folder.py` folder
+    fn root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("api-tools-{}-{}", name, std::process::id()));
+        fs::create_dir_all(root.join("bin")).unwrap();
+        fs::create_dir_all(root.join("folder.py")).unwrap();
+        fs::write(root.join("script.py"), "print('[]')").unwrap();
+        fs::write(root.join("database.sqlite"), "").unwrap();
+        fs::write(root.join("bin/app"), "#!/bin/sh").unwrap();
+        root
+    }
+    ///
+    /// Testing the valid config loaded
+    #[test]
+    fn load() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiConfig.load");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let root = root("load");
+        let yaml = r#"
+address: '127.0.0.1:8899'
+services:
+    - database-sqlite:
+        name: 'database'
+        type: sqlite
+        path: 'database.sqlite'
+        user: root
+        pass: root
+    - database-postgres:
+        name: 'db_postgres_test'
+        type: postgres
+        path: '127.0.0.1:5432'
+        replaceNullWithDefault:
+    - database-mysql:
+        name: 'db_mysql_test'
+        type: mysql
+        path: '127.0.0.1:3306'
+        replaceNullWithDefault:
+            timestamp: '2000-01-01T00:00:00'
+            timestamptz: '2000-01-01T00:00:00+03:00'
+            columns:
+                price: -1
+    - py-test-script:
+        name: 'database'            # names of the different kinds may be the same
+        type: python
+        path: 'script.py'
+    - executable-test-app:
+        name: 'executable-test'
+        type: bin
+        path: 'bin/app'
+        replaceNullWithDefault: false
+"#;
+        fs::write(root.join("config.yaml"), yaml).unwrap();
+        let config = ApiConfig::load(root.join("config.yaml")).unwrap();
+        let result = config.address.as_str();
+        let target = "127.0.0.1:8899";
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        // (step, key, name, kind, path, user, replaceNullWithDefault)
+        let test_data = [
+            (1, "database-sqlite", "database", ServiceType::Sqlite, root.join("database.sqlite").to_string_lossy().into_owned(), Some("root"), None),
+            (2, "database-postgres", "db_postgres_test", ServiceType::Postgres, "127.0.0.1:5432".to_owned(), None, Some(NullDefaults::default())),
+            (3, "database-mysql", "db_mysql_test", ServiceType::Mysql, "127.0.0.1:3306".to_owned(), None, Some(NullDefaults {
+                timestamp: serde_json::json!("2000-01-01T00:00:00"),
+                timestamptz: serde_json::json!("2000-01-01T00:00:00+03:00"),
+                columns: [("price".to_owned(), serde_json::json!(-1))].into(),
+            })),
+            (4, "py-test-script", "database", ServiceType::Python, root.join("script.py").to_string_lossy().into_owned(), None, None),
+            (5, "executable-test-app", "executable-test", ServiceType::Executable, root.join("bin/app").to_string_lossy().into_owned(), None, None),
+        ];
+        assert!(config.services.len() == test_data.len(), "\nresult: {:?}\ntarget: {:?}", config.services.len(), test_data.len());
+        for ((step, key, name, kind, path, user, replace_null), service) in test_data.into_iter().zip(&config.services) {
+            let result = (service.key.as_str(), service.name.as_str(), service.kind, service.path.clone(), service.user.as_deref(), service.replace_null_with_default.clone());
+            let target = (key, name, kind, path, user, replace_null);
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        fs::remove_dir_all(root).unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing the problems reported with the line & the field
+    #[test]
+    fn errors() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ApiConfig.errors");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let root = root("errors");
+        // (step, yaml, target [(line, field, message part)])
+        let test_data: [(i32, &str, Vec<(Option<usize>, &str, &str)>); 11] = [
+            (01, "address: '127.0.0.1:8899'\nservices:\n  - db:\n      name: db\n      type: oracle\n      path: host\n",
+                vec![(Some(5), "services[0].db.type", "unknown variant `oracle`")]),
+            (02, "address: '127.0.0.1:8899'\nservices:\n  - db:\n      name: db\n      type: postgres\n      pathh: host\n",
+                vec![(Some(6), "services[0].db.pathh", "unknown field `pathh`")]),
+            (03, "address: '127.0.0.1:8899'\nservices:\n  - db:\n      name: db\n      type: postgres\n      path: host\n  - db:\n      name: db2\n      type: postgres\n      path: host\n",
+                vec![(Some(7), "services[1].db", "Duplicate service 'db'")]),
+            (04, "address: '127.0.0.1:8899'\nservices:\n  - db1:\n      name: db\n      type: postgres\n      path: host\n  - db2:\n      type: sqlite\n      name: db\n      path: database.sqlite\n",
+                vec![(Some(9), "services[1].db2.name", "Duplicate name 'db'")]),
+            (05, "address: '127.0.0.1:8899'\nservices:\n  - db:\n      type: postgres\n  # comment\n  - py:\n      name: py\n      type: python\n      path: missing.py\n",
+                vec![(Some(3), "services[0].db.name", "is not specified"), (Some(3), "services[0].db.path", "is not specified"), (Some(9), "services[1].py.path", "Can't read")]),
+            (06, "address: ''\nservices:\n  - py:\n      name: py\n      type: python\n      path: folder.py\n  - app:\n      name: app\n      type: bin\n      path: bin/app\n",
+                vec![(Some(1), "address", "Address is not specified"), (Some(6), "services[0].py.path", "Can't read")]),
+            (07, "address: '127.0.0.1:8899'\nservices:\n  - db:\n      name: db\n      path: host\n",
+                vec![(Some(4), "services[0].db", "missing field `type`")]),
+            (08, "address: '127.0.0.1:8899'\nservices:\n  - py:\n      name: py\n      type: python\n      path: script.py\n      timeout: 0\n      outputLimit: 0\n",
+                vec![(Some(7), "services[0].py.timeout", "greater than zero"), (Some(8), "services[0].py.outputLimit", "greater than zero")]),
+            (09, "address: '127.0.0.1:8899'\nservices:\n  - app:\n      name: app\n      type: bin\n      path: bin/app\n      params: args\n      workDir: missing\n",
+                vec![(Some(8), "services[0].app.workDir", "not found")]),
+            (10, "address: '127.0.0.1:8899'\nservices:\n  - app:\n      name: app\n      type: bin\n      path: bin/app\n      limits:\n        cpu: 0\n        uid: 1000\n  - db:\n      name: db\n      type: postgres\n      path: host\n      limits: {files: 10}\n",
+                vec![(Some(8), "services[0].app.limits.cpu", "greater than zero"), (Some(14), "services[1].db.limits", "scripts & executables only")]),
+            (11, "address: '127.0.0.1:8899'\nservices:\n  - py:\n      name: py\n      type: python\n      path: script.py\n      replaceNullWithDefault: true\n",
+                vec![(Some(7), "services[0].py.replaceNullWithDefault", "databases only")]),
+        ];
+        for (step, yaml, target) in test_data {
+            let result = ApiConfig::from_yaml(yaml, &root).unwrap_err();
+            println!("step {} errors: \n\t{}", step, result.iter().map(|err| err.to_string()).collect::<Vec<_>>().join("\n\t"));
+            assert!(result.len() == target.len(), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            for (result, (line, field, message)) in result.iter().zip(target) {
+                assert!(result.line == line, "step {} \nresult: {:?}\ntarget: {:?}", step, result.line, line);
+                assert!(field.is_empty() || result.field == field, "step {} \nresult: {:?}\ntarget: {:?}", step, result.field, field);
+                assert!(result.message.contains(message), "step {} \nresult: {:?}\ntarget: {:?}", step, result.message, message);
+            }
+        }
+        let result = ApiConfig::load(root.join("missing.yaml")).is_err();
+        assert!(result, "\nresult: {:?}\ntarget: {:?}", result, true);
+        fs::remove_dir_all(root).unwrap();
+        test_duration.exit();
+    }
+}
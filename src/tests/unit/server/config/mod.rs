@@ -0,0 +1 @@
+mod api_config_test;
\ No newline at end of file
@@ -1 +1,11 @@
-mod api_query;
\ No newline at end of file
+mod api_query;
+mod api_server;
+mod auth;
+mod cancel;
+mod config;
+#[cfg(feature = "mysql")]
+mod mysql;
+mod postgres;
+mod service;
+#[cfg(feature = "sqlite")]
+mod sqlite;
\ No newline at end of file
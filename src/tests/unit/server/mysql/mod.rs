@@ -0,0 +1,2 @@
+mod mysql_service_test;
+mod mysql_value_test;
\ No newline at end of file
@@ -0,0 +1,158 @@
+#[cfg(test)]
+
+mod mysql_service {
+    use std::{sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::{
+        api::reply::api_reply::ApiReply,
+        client::{api_query::{ApiQuery, ApiQueryKind, ApiQuerySql}, api_request::ApiRequest},
+        server::{
+            api_server::{api_router::ApiRouter, api_server::ApiServer},
+            config::api_config::ApiConfig,
+            service::{service_registry::ServiceRegistry, sql_handler::SqlHandler},
+        },
+    };
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the server of the local `mysql` database
+    fn server(dbg: &Dbg) -> ApiServer {
+        let yaml = "address: '127.0.0.1:0'\nservices:\n  - my:\n      name: mysql\n      type: mysql\n      path: 'localhost:3306'\n      user: root\n      pass: root\n";
+        let registry = ServiceRegistry::from(ApiConfig::from_yaml(yaml, "").unwrap());
+        let router = SqlHandler::from_registry(&registry).unwrap().register(ApiRouter::new());
+        ApiServer::new(dbg, "127.0.0.1:0", router)
+    }
+    ///
+    /// Testing the column types converted into json, the order of the columns kept, the params bound
+    /// - Requires local mysql / mariadb with user `root` / `root`
+    #[test]
+    fn types() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test MySqlService.types");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let mut server = server(&dbg);
+        let address = server.start().unwrap();
+        // (step, sql, params, target)
+        let test_data = [
+            (
+                1,
+                r#"select
+                    cast(2 as signed) as i, 2.25e0 as f, cast(123.45 as decimal(5, 2)) as num, 'text' as t,
+                    cast('2024-01-31 12:00:00' as datetime) as ts, cast('2024-01-31' as date) as d, cast('12:30:00' as time) as tm,
+                    x'00ff' as bin, null as nothing"#,
+                json!([]),
+                vec![
+                    ("i", json!(2)), ("f", json!(2.25)), ("num", json!("123.45")), ("t", json!("text")),
+                    ("ts", json!("2024-01-31T12:00:00")), ("d", json!("2024-01-31")), ("tm", json!("12:30:00")),
+                    ("bin", json!("AP8=")), ("nothing", json!(null)),
+                ],
+            ),
+            (2, "select cast(? as signed) as a, ? + 1 as b", json!([1, 2]), vec![("a", json!(1)), ("b", json!(3))]),
+            (3, "select :a + :a as twice, :b as b", json!({"b": 1, "a": 2}), vec![("twice", json!(4)), ("b", json!(1))]),
+        ];
+        for (step, sql, params, target) in test_data {
+            let mut sql = ApiQuerySql::new("mysql", sql);
+            sql.params = serde_json::from_value(params).unwrap();
+            let query = ApiQuery::new(ApiQueryKind::Sql(sql), false);
+            let mut request = ApiRequest::new(&dbg, address, "token", query, true, false);
+            let reply = ApiReply::try_from(request.fetch(true).unwrap()).unwrap();
+            let result: Vec<(String, serde_json::Value)> = reply.data[0].clone().into_iter().collect();
+            let target: Vec<(String, serde_json::Value)> = target.into_iter().map(|(name, value)| (name.to_owned(), value)).collect();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        server.stop();
+        test_duration.exit();
+    }
+    ///
+    /// Testing the database error replied, details - only in debug
+    /// - Requires local mysql / mariadb with user `root` / `root`
+    #[test]
+    fn error() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test MySqlService.error");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let mut server = server(&dbg);
+        let address = server.start().unwrap();
+        // (step, debug, target message, target details part)
+        let test_data = [
+            (1, false, "API SQL Service - query error", None),
+            (2, true, "API SQL Service - query error", Some("1146")),
+        ];
+        for (step, debug, target_message, target_details) in test_data {
+            let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("mysql", "select * from not_existing_table")), false);
+            let mut request = ApiRequest::new(&dbg, address, "token", query, true, debug);
+            let reply = ApiReply::try_from(request.fetch(true).unwrap()).unwrap();
+            let error = serde_json::to_value(reply.error.debug(debug)).unwrap();
+            let result = error["message"].as_str().unwrap_or_default();
+            assert!(result == target_message, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_message);
+            let result = error.get("details").and_then(|details| details.as_str());
+            let matched = match target_details {
+                Some(part) => result.is_some_and(|details| details.contains(part)),
+                None => result.is_none(),
+            };
+            assert!(matched, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_details);
+        }
+        server.stop();
+        test_duration.exit();
+    }
+    ///
+    /// Testing the session state left by the query doesn't leak into the next query on the pooled connection
+    /// - Requires local mysql / mariadb with user `root` / `root`
+    #[test]
+    fn session_reset() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test MySqlService.session_reset");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let mut server = server(&dbg);
+        let address = server.start().unwrap();
+        // (step, sql, target)
+        let test_data = [
+            (1, "set @leaked = 'leaked'", None),
+            (2, "select @leaked as v", Some(json!(null))),
+            (3, "set session sql_mode = 'ANSI_QUOTES'", None),
+            (4, "select @@session.sql_mode like '%ANSI_QUOTES%' as v", Some(json!(0))),
+        ];
+        for (step, sql, target) in test_data {
+            let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("mysql", sql)), false);
+            let mut request = ApiRequest::new(&dbg, address, "token", query, true, false);
+            let reply = ApiReply::try_from(request.fetch(true).unwrap()).unwrap();
+            assert!(!reply.has_error(), "step {} \nresult: {:?}\ntarget: no error", step, reply.error);
+            if let Some(target) = target {
+                let data = serde_json::to_value(&reply.data).unwrap();
+                let result = data.pointer("/0/v");
+                assert!(result == Some(&target), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            }
+        }
+        server.stop();
+        test_duration.exit();
+    }
+}
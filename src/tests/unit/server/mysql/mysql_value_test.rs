@@ -0,0 +1,103 @@
+#[cfg(test)]
+
+mod mysql_value {
+    use std::{sync::Once, time::Duration};
+    use mysql::{consts::ColumnType, Column, Value};
+    use sal_core::dbg::Dbg;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::server::{api_query::api_query_sql_params::SqlParam, mysql::mysql_value::MySqlValue};
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the column of the `ty` in `utf8mb4`
+    fn text(ty: ColumnType) -> Column {
+        Column::new(ty).with_character_set(45)
+    }
+    ///
+    /// Returns the column of the `ty` in the `binary` character set
+    fn binary(ty: ColumnType) -> Column {
+        Column::new(ty).with_character_set(63)
+    }
+    ///
+    /// Testing the binary protocol values of the columns converted into json
+    #[test]
+    fn new() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test MySqlValue.new");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        // (step, column, value, target)
+        let test_data: [(i32, Column, Value, Option<serde_json::Value>); 20] = [
+            (01, binary(ColumnType::MYSQL_TYPE_LONG), Value::Int(-12), Some(json!(-12))),
+            (02, binary(ColumnType::MYSQL_TYPE_LONGLONG), Value::UInt(u64::MAX), Some(json!(u64::MAX))),
+            (03, binary(ColumnType::MYSQL_TYPE_TINY).with_column_length(1), Value::Int(1), Some(json!(true))),
+            (04, binary(ColumnType::MYSQL_TYPE_TINY).with_column_length(4), Value::Int(1), Some(json!(1))),
+            (05, binary(ColumnType::MYSQL_TYPE_FLOAT), Value::Float(1.5), Some(json!(1.5))),
+            (06, binary(ColumnType::MYSQL_TYPE_DOUBLE), Value::Double(f64::NAN), Some(json!(null))),
+            (07, binary(ColumnType::MYSQL_TYPE_NEWDECIMAL), Value::Bytes(b"-123.45".to_vec()), Some(json!("-123.45"))),
+            (08, binary(ColumnType::MYSQL_TYPE_NEWDECIMAL), Value::Bytes(b"1,5".to_vec()), None),
+            (09, binary(ColumnType::MYSQL_TYPE_NEWDECIMAL), Value::Bytes(b"12345678901234567890.123456789012345678".to_vec()), Some(json!("12345678901234567890.123456789012345678"))),
+            (10, binary(ColumnType::MYSQL_TYPE_NEWDECIMAL), Value::Bytes(b"1.".to_vec()), None),
+            (11, text(ColumnType::MYSQL_TYPE_VAR_STRING), Value::Bytes("текст".as_bytes().to_vec()), Some(json!("текст"))),
+            (12, text(ColumnType::MYSQL_TYPE_VAR_STRING), Value::Bytes(vec![0xff]), None),
+            (13, binary(ColumnType::MYSQL_TYPE_BLOB), Value::Bytes(vec![0, 1, 2, 255]), Some(json!("AAEC/w=="))),
+            (14, binary(ColumnType::MYSQL_TYPE_JSON), Value::Bytes(br#"{"a": [1, 2]}"#.to_vec()), Some(json!({"a": [1, 2]}))),
+            (15, binary(ColumnType::MYSQL_TYPE_BIT), Value::Bytes(vec![1, 2]), Some(json!(258))),
+            (16, binary(ColumnType::MYSQL_TYPE_DATETIME), Value::Date(2024, 1, 31, 12, 0, 0, 500000), Some(json!("2024-01-31T12:00:00.500"))),
+            (17, binary(ColumnType::MYSQL_TYPE_DATE), Value::Date(2024, 1, 31, 0, 0, 0, 0), Some(json!("2024-01-31"))),
+            (18, binary(ColumnType::MYSQL_TYPE_TIMESTAMP), Value::Date(0, 0, 0, 0, 0, 0, 0), Some(json!(null))),
+            (19, binary(ColumnType::MYSQL_TYPE_TIME), Value::Time(true, 1, 2, 3, 4, 5), Some(json!("-26:03:04.000005"))),
+            (20, binary(ColumnType::MYSQL_TYPE_LONG), Value::NULL, Some(json!(null))),
+        ];
+        for (step, column, value, target) in test_data {
+            let result = MySqlValue::new(&value, &column).ok().map(|value| value.0);
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the json parameters converted into the mysql values
+    #[test]
+    fn from_param() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test MySqlValue.from_param");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        // (step, param, target)
+        let test_data = [
+            (1, json!(null), Value::NULL),
+            (2, json!(true), Value::Int(1)),
+            (3, json!(-7), Value::Int(-7)),
+            (4, json!(u64::MAX), Value::UInt(u64::MAX)),
+            (5, json!(2.25), Value::Double(2.25)),
+            (6, json!("text"), Value::Bytes(b"text".to_vec())),
+            (7, json!({"a": [1]}), Value::Bytes(br#"{"a":[1]}"#.to_vec())),
+        ];
+        for (step, param, target) in test_data {
+            let result = Value::from(&SqlParam(param));
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+}
@@ -0,0 +1,3 @@
+mod pg_listener_test;
+mod pg_service_test;
+mod pg_value_test;
\ No newline at end of file
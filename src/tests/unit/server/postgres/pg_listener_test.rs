@@ -0,0 +1,65 @@
+#[cfg(test)]
+
+mod pg_listener {
+    use std::{sync::{mpsc, Once}, time::Duration};
+    use sal_core::dbg::Dbg;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::{
+        api::reply::api_notification::ApiNotification,
+        server::{api_query::api_query_subscribe::ApiQuerySubscribe, postgres::pg_listener::PgListener},
+        tests::unit::client::prepare_postgres::TestDatabasePostgres,
+    };
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing the notifications of the subscribed channels forwarded
+    /// - Requires local postgres with user `postgres` / `postgres`
+    #[test]
+    fn listen() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test PgListener.listen");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let client = TestDatabasePostgres::connect_db(&dbg, "postgres", "postgres", "localhost:5432", "postgres").unwrap();
+        let (send, recv) = mpsc::channel();
+        let mut listener = PgListener::listen("1", client, &ApiQuerySubscribe::new("postgres", &["event", "Alarm"]), move |notification| {
+            send.send(notification).is_ok()
+        }).unwrap();
+        let mut client = TestDatabasePostgres::connect_db(&dbg, "postgres", "postgres", "localhost:5432", "postgres").unwrap();
+        client.batch_execute(r#"
+            NOTIFY event, '1';
+            NOTIFY other, '2';
+            NOTIFY "Alarm", '{"id": 3}';
+            SELECT pg_notify('event', '4');
+        "#).unwrap();
+        let target = [("event", "1"), ("Alarm", "{\"id\": 3}"), ("event", "4")];
+        for (step, (channel, payload)) in target.into_iter().enumerate() {
+            let result: Option<ApiNotification> = recv.recv_timeout(Duration::from_secs(2)).ok();
+            let result = result.map(|notification| (notification.channel, notification.payload));
+            let target = Some((channel.to_owned(), payload.to_owned()));
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let result = recv.recv_timeout(Duration::from_millis(300)).ok();
+        assert!(result.is_none(), "not subscribed \nresult: {:?}\ntarget: None", result);
+        listener.stop();
+        let result = listener.is_active();
+        assert!(!result, "stop \nresult: {:?}\ntarget: {:?}", result, false);
+        test_duration.exit();
+    }
+}
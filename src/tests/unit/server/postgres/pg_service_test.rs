@@ -0,0 +1,297 @@
+#[cfg(test)]
+
+mod pg_service {
+    use std::{sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::{
+        api::reply::api_reply::ApiReply,
+        client::{
+            api_query::{ApiQuery, ApiQueryBatch, ApiQueryBatchStatement, ApiQueryCursor, ApiQueryCursorOpen, ApiQueryKind, ApiQuerySql, ApiQuerySqlParams},
+            api_request::ApiRequest,
+        },
+        server::{
+            api_server::{api_router::ApiRouter, api_server::ApiServer},
+            auth::{auth_operation::AuthOperation, auth_service::AuthService},
+            config::api_config::ApiConfig,
+            service::{service_registry::ServiceRegistry, sql_handler::SqlHandler},
+        },
+    };
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the server of the local `postgres` database
+    /// - `options` - additional options of the service, indented yaml lines
+    fn server(dbg: &Dbg, options: &str) -> ApiServer {
+        let router = SqlHandler::from_registry(&registry(options)).unwrap().register(ApiRouter::new());
+        ApiServer::new(dbg, "127.0.0.1:0", router)
+    }
+    ///
+    /// Returns the registry of the local `postgres` database
+    /// - `options` - additional options of the service, indented yaml lines
+    fn registry(options: &str) -> ServiceRegistry {
+        let yaml = format!("address: '127.0.0.1:0'\nservices:\n  - pg:\n      name: postgres\n      type: postgres\n      path: 'localhost:5432'\n      user: postgres\n      pass: postgres\n{}", options);
+        ServiceRegistry::from(ApiConfig::from_yaml(&yaml, "").unwrap())
+    }
+    ///
+    /// Testing the column types converted into json, the order of the columns kept
+    /// - Requires local postgres with user `postgres` / `postgres`
+    #[test]
+    fn types() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test PgService.types");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let mut server = server(&dbg, "");
+        let address = server.start().unwrap();
+        let sql = r#"select
+            2::int2 as z, 4::int4 as i4, 8::int8 as i8, 1.5::float4 as f4, 2.25::float8 as f8, 123.45::numeric as num, 12345678901234567890.123456789012345678::numeric as big, 1.50::numeric(4, 3) as scaled,
+            true as b, 'text'::text as t, 'v'::varchar as v, '{"a": 1}'::json as j, '[1, "2"]'::jsonb as jb,
+            '2024-01-31 12:00:00'::timestamp as ts, '2024-01-31 12:00:00+00'::timestamptz as tz, '2024-01-31'::date as d,
+            'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11'::uuid as u, '\x00ff'::bytea as bin,
+            array[1, null, 3]::int4[] as arr, array['a', 'b'] as tarr, null::int4 as nothing"#;
+        let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("postgres", sql)), false);
+        let mut request = ApiRequest::new(&dbg, address, "token", query, true, false);
+        let reply = ApiReply::try_from(request.fetch(true).unwrap()).unwrap();
+        let result: Vec<(String, serde_json::Value)> = reply.data[0].clone().into_iter().collect();
+        let target: Vec<(String, serde_json::Value)> = [
+            ("z", json!(2)), ("i4", json!(4)), ("i8", json!(8)), ("f4", json!(1.5)), ("f8", json!(2.25)), ("num", json!("123.45")), ("big", json!("12345678901234567890.123456789012345678")), ("scaled", json!("1.500")),
+            ("b", json!(true)), ("t", json!("text")), ("v", json!("v")), ("j", json!({"a": 1})), ("jb", json!([1, "2"])),
+            ("ts", json!("2024-01-31T12:00:00")), ("tz", json!("2024-01-31T12:00:00Z")), ("d", json!("2024-01-31")),
+            ("u", json!("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11")), ("bin", json!("AP8=")),
+            ("arr", json!([1, null, 3])), ("tarr", json!(["a", "b"])), ("nothing", json!(null)),
+        ].into_iter().map(|(name, value)| (name.to_owned(), value)).collect();
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        server.stop();
+        test_duration.exit();
+    }
+    ///
+    /// Testing the database error replied, details - only in debug
+    /// - Requires local postgres with user `postgres` / `postgres`
+    #[test]
+    fn error() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test PgService.error");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let mut server = server(&dbg, "");
+        let address = server.start().unwrap();
+        // (step, database, debug, target message, target details part)
+        let test_data = [
+            (1, "postgres", false, "API SQL Service - query error", None),
+            (2, "postgres", true, "API SQL Service - query error", Some("42P01")),
+            (3, "missing", true, "API SQL Service - database 'missing' not found", Some("not found")),
+        ];
+        for (step, database, debug, target_message, target_details) in test_data {
+            let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new(database, "select * from not_existing_table")), false);
+            let mut request = ApiRequest::new(&dbg, address, "token", query, true, debug);
+            let reply = ApiReply::try_from(request.fetch(true).unwrap()).unwrap();
+            let error = serde_json::to_value(reply.error.debug(debug)).unwrap();
+            let result = error["message"].as_str().unwrap_or_default();
+            assert!(result == target_message, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_message);
+            let result = error.get("details").and_then(|details| details.as_str());
+            let matched = match target_details {
+                Some(part) => result.is_some_and(|details| details.contains(part)),
+                None => result.is_none(),
+            };
+            assert!(matched, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_details);
+        }
+        server.stop();
+        test_duration.exit();
+    }
+    ///
+    /// Testing the failed batch statement replied with it's index, the bind & the row errors as well
+    /// - Requires local postgres with user `postgres` / `postgres`
+    #[test]
+    fn batch_error() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test PgService.batch_error");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let mut server = server(&dbg, "");
+        let address = server.start().unwrap();
+        let named = ApiQuerySqlParams::Named(serde_json::json!({"other": 1}).as_object().unwrap().to_owned());
+        // (step, second statement, target message, target details part)
+        let test_data = [
+            (1, ApiQueryBatchStatement::new("select 2 as two"), None, None),
+            (2, ApiQueryBatchStatement::new("select * from not_existing_table"), Some("API SQL Service - batch statement 1 failed, transaction rolled back"), Some("not_existing_table")),
+            (3, ApiQueryBatchStatement::new("select :missing as value").with_params(named), Some("API SQL Service - batch statement 1 failed, transaction rolled back"), Some("missing parameter")),
+            (4, ApiQueryBatchStatement::new("select '1 day'::interval as period"), Some("API SQL Service - batch statement 1 failed, transaction rolled back"), Some("unable to read column 'period'")),
+        ];
+        for (step, statement, target_message, target_details) in test_data {
+            let batch = ApiQueryBatch::new("postgres", vec![ApiQueryBatchStatement::new("select 1 as one"), statement]);
+            let query = ApiQuery::new(ApiQueryKind::Batch(batch), false);
+            let mut request = ApiRequest::new(&dbg, address, "token", query, true, true);
+            let reply = ApiReply::try_from(request.fetch(true).unwrap()).unwrap();
+            let error = serde_json::to_value(reply.error.clone().debug(true)).unwrap();
+            let result = reply.has_error().then(|| error["message"].as_str().unwrap_or_default());
+            assert!(result == target_message, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_message);
+            let result = error.get("details").and_then(|details| details.as_str());
+            let matched = match target_details {
+                Some(part) => result.is_some_and(|details| details.contains(part)),
+                None => true,
+            };
+            assert!(matched, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_details);
+        }
+        server.stop();
+        test_duration.exit();
+    }
+    ///
+    /// Testing the nulls replaced with the defaults of the column types & the columns, `replaceNullWithDefault`
+    /// - Requires local postgres with user `postgres` / `postgres`
+    #[test]
+    fn null_defaults() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test PgService.null_defaults");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let sql = r#"select
+            null::int4 as i, null::numeric as num, null::text as t, null::varchar as v, null::bool as b,
+            null::timestamp as ts, null::timestamptz as tz, null::date as d, null::jsonb as j, 5::int4 as five"#;
+        // (step, options, target)
+        let test_data = [
+            (1, "", json!({"i": null, "num": null, "t": null, "v": null, "b": null, "ts": null, "tz": null, "d": null, "j": null, "five": 5})),
+            (2, "      replaceNullWithDefault: false\n", json!({"i": null, "num": null, "t": null, "v": null, "b": null, "ts": null, "tz": null, "d": null, "j": null, "five": 5})),
+            (3, "      replaceNullWithDefault:\n", json!({
+                "i": 0, "num": "0", "t": "", "v": "", "b": false,
+                "ts": "1970-01-01T00:00:00", "tz": "1970-01-01T00:00:00Z", "d": null, "j": null, "five": 5,
+            })),
+            (4, "      replaceNullWithDefault:\n        timestamp: '2000-01-01T00:00:00'\n        timestamptz: '2000-01-01T00:00:00Z'\n        columns: {num: -1, d: '2000-01-01', j: {}, five: 0}\n", json!({
+                "i": 0, "num": -1, "t": "", "v": "", "b": false,
+                "ts": "2000-01-01T00:00:00", "tz": "2000-01-01T00:00:00Z", "d": "2000-01-01", "j": {}, "five": 5,
+            })),
+        ];
+        for (step, options, target) in test_data {
+            let mut server = server(&dbg, options);
+            let address = server.start().unwrap();
+            let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("postgres", sql)), false);
+            let mut request = ApiRequest::new(&dbg, address, "token", query, true, false);
+            let reply = ApiReply::try_from(request.fetch(true).unwrap()).unwrap();
+            assert!(!reply.has_error(), "step {} \nresult: {:?}\ntarget: no error", step, reply.error);
+            let result = serde_json::to_value(&reply.data[0]).unwrap();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            server.stop();
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the queries of the user granted reading only & of the `readOnly` service executed in the read only transaction
+    /// - Requires local postgres with user `postgres` / `postgres`
+    #[test]
+    fn read_only() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test PgService.read_only");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let sql = "select current_setting('transaction_read_only') as ro";
+        let cursor = ApiQueryKind::Cursor(ApiQueryCursor::Open(ApiQueryCursorOpen::new(ApiQuerySql::new("postgres", sql), 10)));
+        let batch = ApiQueryKind::Batch(ApiQueryBatch::new("postgres", vec![ApiQueryBatchStatement::new(sql)]));
+        let sql = ApiQueryKind::Sql(ApiQuerySql::new("postgres", sql));
+        // (step, service read only, user, query, pointer to the result, target)
+        let test_data = [
+            (1, false, "reader", &sql, "/0/ro", "on"),
+            (2, false, "writer", &sql, "/0/ro", "off"),
+            (3, false, "reader", &batch, "/0/rows/0/ro", "on"),
+            (4, false, "writer", &batch, "/0/rows/0/ro", "off"),
+            (5, false, "reader", &cursor, "/0/ro", "on"),
+            (6, false, "writer", &cursor, "/0/ro", "off"),
+            (7, true, "writer", &sql, "/0/ro", "on"),
+            (8, true, "writer", &batch, "/0/rows/0/ro", "on"),
+            (9, true, "writer", &cursor, "/0/ro", "on"),
+        ];
+        for (step, read_only, user, query, pointer, target) in test_data {
+            let auth = AuthService::new("secret", Duration::from_secs(3600))
+                .with_user("reader", "pass")
+                .with_user("writer", "pass")
+                .with_grant("reader", "postgres", &[AuthOperation::Read])
+                .with_grant("writer", "postgres", &[AuthOperation::Read, AuthOperation::Write]);
+            let registry = registry(&format!("      readOnly: {}\n", read_only));
+            let router = SqlHandler::from_registry(&registry).unwrap().register(ApiRouter::new().with_auth(auth));
+            let mut server = ApiServer::new(&dbg, "127.0.0.1:0", router);
+            let address = server.start().unwrap();
+            let query = ApiQuery::new(query.clone(), false);
+            let mut request = ApiRequest::builder(&dbg).address(address).query(query).keep_alive(true).debug(true).login(user, "pass").build().unwrap();
+            let reply = ApiReply::try_from(request.fetch(true).unwrap()).unwrap();
+            assert!(!reply.has_error(), "step {} \nresult: {:?}\ntarget: no error", step, reply.error);
+            let data = serde_json::to_value(&reply.data).unwrap();
+            let result = data.pointer(pointer).and_then(|value| value.as_str());
+            assert!(result == Some(target), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            server.stop();
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the session state left by the query doesn't leak into the next query on the pooled client
+    /// - Requires local postgres with user `postgres` / `postgres`
+    #[test]
+    fn session_reset() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test PgService.session_reset");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let mut server = server(&dbg, "");
+        let address = server.start().unwrap();
+        // (step, sql, target)
+        let test_data = [
+            (1, "select set_config('search_path', 'pg_catalog', false) as v", Some("pg_catalog")),
+            (2, "select current_setting('search_path') as v", Some("\"$user\", public")),
+            (3, "set session characteristics as transaction read only", None),
+            (4, "select current_setting('default_transaction_read_only') as v", Some("off")),
+            (5, "set role pg_read_all_data", None),
+            (6, "select current_user::text as v", Some("postgres")),
+            (7, "begin", None),
+            (8, "select set_config('search_path', 'pg_catalog', true) as v", Some("pg_catalog")),
+            (9, "select current_setting('search_path') as v", Some("\"$user\", public")),
+        ];
+        for (step, sql, target) in test_data {
+            let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("postgres", sql)), false);
+            let mut request = ApiRequest::new(&dbg, address, "token", query, true, false);
+            let reply = ApiReply::try_from(request.fetch(true).unwrap()).unwrap();
+            assert!(!reply.has_error(), "step {} \nresult: {:?}\ntarget: no error", step, reply.error);
+            if let Some(target) = target {
+                let data = serde_json::to_value(&reply.data).unwrap();
+                let result = data.pointer("/0/v").and_then(|value| value.as_str());
+                assert!(result == Some(target), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            }
+        }
+        server.stop();
+        test_duration.exit();
+    }
+}
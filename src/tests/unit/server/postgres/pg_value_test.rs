@@ -0,0 +1,110 @@
+#[cfg(test)]
+
+mod pg_value {
+    use std::{sync::Once, time::Duration};
+    use postgres::types::{FromSql, Type};
+    use sal_core::dbg::Dbg;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::server::postgres::pg_value::PgValue;
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns `numeric` in the binary format
+    fn numeric(weight: i16, sign: u16, dscale: u16, digits: &[u16]) -> Vec<u8> {
+        let mut raw = vec![];
+        raw.extend((digits.len() as u16).to_be_bytes());
+        raw.extend(weight.to_be_bytes());
+        raw.extend(sign.to_be_bytes());
+        raw.extend(dscale.to_be_bytes());
+        for digit in digits {
+            raw.extend(digit.to_be_bytes());
+        }
+        raw
+    }
+    ///
+    /// Returns one dimensional `int4[]` in the binary format, None - null element
+    fn int4_array(values: &[Option<i32>]) -> Vec<u8> {
+        let mut raw = vec![];
+        raw.extend(1i32.to_be_bytes());
+        raw.extend((values.iter().any(|value| value.is_none()) as i32).to_be_bytes());
+        raw.extend(Type::INT4.oid().to_be_bytes());
+        raw.extend((values.len() as i32).to_be_bytes());
+        raw.extend(1i32.to_be_bytes());
+        for value in values {
+            match value {
+                Some(value) => {
+                    raw.extend(4i32.to_be_bytes());
+                    raw.extend(value.to_be_bytes());
+                }
+                None => raw.extend((-1i32).to_be_bytes()),
+            }
+        }
+        raw
+    }
+    ///
+    /// Testing the binary values of the columns converted into json
+    #[test]
+    fn from_sql() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test PgValue.from_sql");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        // (step, type, raw, target)
+        let test_data: [(i32, Type, Vec<u8>, Option<serde_json::Value>); 23] = [
+            (01, Type::INT2, 7i16.to_be_bytes().to_vec(), Some(json!(7))),
+            (02, Type::INT4, (-12i32).to_be_bytes().to_vec(), Some(json!(-12))),
+            (03, Type::INT8, i64::MAX.to_be_bytes().to_vec(), Some(json!(i64::MAX))),
+            (04, Type::FLOAT4, 1.5f32.to_be_bytes().to_vec(), Some(json!(1.5))),
+            (05, Type::FLOAT8, f64::NAN.to_be_bytes().to_vec(), Some(json!(null))),
+            (06, Type::BOOL, vec![1], Some(json!(true))),
+            (07, Type::TEXT, b"text".to_vec(), Some(json!("text"))),
+            (08, Type::JSONB, [&[1u8][..], br#"{"a": [1, 2]}"#].concat(), Some(json!({"a": [1, 2]}))),
+            // 12345.678
+            (09, Type::NUMERIC, numeric(1, 0x0000, 3, &[1, 2345, 6780]), Some(json!("12345.678"))),
+            // -0.0012
+            (10, Type::NUMERIC, numeric(-1, 0x4000, 4, &[12]), Some(json!("-0.0012"))),
+            // 0.000001
+            (11, Type::NUMERIC, numeric(-2, 0x0000, 6, &[100]), Some(json!("0.000001"))),
+            // 20000, trailing zero groups are not stored
+            (12, Type::NUMERIC, numeric(1, 0x0000, 0, &[2]), Some(json!("20000"))),
+            (13, Type::NUMERIC, numeric(0, 0xC000, 0, &[]), Some(json!(null))),
+            (14, Type::NUMERIC, vec![0, 1], None),
+            // 12345678901234567890.123456789012345678, not representable by f64
+            (15, Type::NUMERIC, numeric(4, 0x0000, 18, &[1234, 5678, 9012, 3456, 7890, 1234, 5678, 9012, 3456, 7800]), Some(json!("12345678901234567890.123456789012345678"))),
+            // 2024-01-31 12:00:00.5 - microseconds since 2000-01-01
+            (16, Type::TIMESTAMP, 760017600500000i64.to_be_bytes().to_vec(), Some(json!("2024-01-31T12:00:00.500"))),
+            (17, Type::TIMESTAMPTZ, 760017600000000i64.to_be_bytes().to_vec(), Some(json!("2024-01-31T12:00:00Z"))),
+            // days since 2000-01-01
+            (18, Type::DATE, 8796i32.to_be_bytes().to_vec(), Some(json!("2024-01-31"))),
+            (19, Type::UUID, (0u8..16).collect(), Some(json!("00010203-0405-0607-0809-0a0b0c0d0e0f"))),
+            (20, Type::BYTEA, vec![0, 1, 2, 255], Some(json!("AAEC/w=="))),
+            (21, Type::INT4_ARRAY, int4_array(&[Some(1), None, Some(3)]), Some(json!([1, null, 3]))),
+            (22, Type::INT4_ARRAY, int4_array(&[]), Some(json!([]))),
+            (23, Type::INTERVAL, vec![0; 16], None),
+        ];
+        for (step, ty, raw, target) in test_data {
+            let result = PgValue::from_sql(&ty, &raw).map(|value| value.0).ok();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let result = PgValue::from_sql_null(&Type::INT4).unwrap().0;
+        assert!(result == json!(null), "\nresult: {:?}\ntarget: {:?}", result, json!(null));
+        test_duration.exit();
+    }
+}
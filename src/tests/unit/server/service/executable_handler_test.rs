@@ -0,0 +1,107 @@
+#[cfg(test)]
+
+mod executable_handler {
+    use std::{fs, os::unix::fs::PermissionsExt, path::PathBuf, sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::{
+        api::reply::api_reply::ApiReply,
+        client::{api_query::{ApiQuery, ApiQueryKind, ApiQueryExecutable}, api_request::ApiRequest},
+        server::{
+            api_query::api_query_type::ApiQueryTypeName,
+            api_server::{api_router::ApiRouter, api_server::ApiServer},
+            config::api_config::ApiConfig,
+            service::{executable_handler::ExecutableHandler, service_registry::ServiceRegistry},
+        },
+    };
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// The executables, (name, mode, source, extra config)
+    const EXECUTABLES: [(&str, u32, &str, &str); 8] = [
+        ("echo", 0o755, "IFS= read -r line || true\nprintf '%s' \"$line\"", ""),
+        ("args", 0o755, "for arg in \"$@\"; do printf '{\"arg\": \"%s\"}\\n' \"$arg\"; done", "      params: args\n"),
+        ("env", 0o755, "printf '{\"home\": \"%s\", \"lang\": \"%s\"}' \"$HOME\" \"$LANG\"", "      env:\n        LANG: C\n"),
+        ("pwd", 0o755, "printf '{\"pwd\": \"%s\"}' \"$(pwd)\"", ""),
+        ("pwd-work", 0o755, "printf '{\"pwd\": \"%s\"}' \"$(pwd)\"", "      workDir: work\n"),
+        ("fail", 0o755, "printf 'oops' >&2\nexit 2", ""),
+        ("private", 0o644, "printf '{}'", ""),
+        ("sleep", 0o755, "sleep 10", "      inheritEnv: true\n"),
+    ];
+    ///
+    /// Returns the server of the executables in the temporary folder
+    /// - Each executable is killed after 1 second
+    fn server(dbg: &Dbg, root: &PathBuf) -> ApiServer {
+        let mut yaml = "address: '127.0.0.1:0'\nservices:\n".to_owned();
+        fs::create_dir_all(root.join("work")).unwrap();
+        for (name, mode, source, extra) in EXECUTABLES.iter().chain(&[("gone", 0o755, "printf '{}'", "")]) {
+            let path = root.join(name);
+            fs::write(&path, format!("#!/bin/sh\n{}\n", source)).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(*mode)).unwrap();
+            yaml.push_str(&format!("  - {}:\n      name: {}\n      type: bin\n      path: {}\n      timeout: 1\n{}", name, name, name, extra));
+        }
+        let registry = ServiceRegistry::from(ApiConfig::from_yaml(&yaml, root).unwrap());
+        fs::remove_file(root.join("gone")).unwrap();
+        let router = ApiRouter::new().with_handler(ApiQueryTypeName::Executable, ExecutableHandler::from_registry(&registry));
+        ApiServer::new(dbg, "127.0.0.1:0", router)
+    }
+    ///
+    /// Testing the params, the environment & the working directory of the executable, the errors replied
+    #[test]
+    fn handle() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ExecutableHandler.handle");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(20));
+        test_duration.run().unwrap();
+        let root = std::env::temp_dir().join(format!("api-tools-executable-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let mut server = server(&dbg, &root);
+        let canonical = fs::canonicalize(&root).unwrap();
+        let address = server.start().unwrap();
+        // (step, executable, params, target rows, target message, target details part)
+        let test_data = [
+            (01, "echo", json!({"a": 1, "b": [2]}), json!([{"a": 1, "b": [2]}]), "", ""),
+            (02, "args", json!({"a": 1, "b": "x y"}), json!([{"arg": "--a=1"}, {"arg": "--b=x y"}]), "", ""),
+            (03, "env", json!({}), json!([{"home": "", "lang": "C"}]), "", ""),
+            (04, "pwd", json!({}), json!([{"pwd": canonical.to_string_lossy()}]), "", ""),
+            (05, "pwd-work", json!({}), json!([{"pwd": canonical.join("work").to_string_lossy()}]), "", ""),
+            (06, "fail", json!({}), json!([]), "API Executable Service - executable error", "oops"),
+            (07, "private", json!({}), json!([]), "API Executable Service - executable permission denied", "PermissionDenied"),
+            (08, "gone", json!({}), json!([]), "API Executable Service - executable not found", "NotFound"),
+            (09, "sleep", json!({}), json!([]), "API Executable Service - executable timeout", "1s"),
+            (10, "missing", json!({}), json!([]), "API Executable Service - executable 'missing' not found", "not found"),
+        ];
+        for (step, name, params, target, target_message, target_details) in test_data {
+            let query = ApiQuery::new(ApiQueryKind::Executable(ApiQueryExecutable::new(name, params.as_object().unwrap().to_owned())), false);
+            let mut request = ApiRequest::new(&dbg, address, "token", query, true, true);
+            let reply = ApiReply::try_from(request.fetch(true).unwrap()).unwrap();
+            let result = serde_json::to_value(&reply.data).unwrap();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            let error = serde_json::to_value(reply.error.clone().debug(true)).unwrap();
+            let result = error["message"].as_str().unwrap_or_default();
+            assert!(result == target_message, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_message);
+            let result = error["details"].as_str().unwrap_or_default();
+            assert!(result.contains(target_details), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_details);
+        }
+        server.stop();
+        fs::remove_dir_all(root).unwrap();
+        test_duration.exit();
+    }
+}
@@ -0,0 +1,5 @@
+mod executable_handler_test;
+mod process_runner_test;
+mod process_sandbox_test;
+mod python_handler_test;
+mod service_registry_test;
\ No newline at end of file
@@ -0,0 +1,64 @@
+#[cfg(test)]
+
+mod process_runner {
+    use std::{os::unix::process::ExitStatusExt, process::ExitStatus, sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::server::service::process_runner::ProcessOutput;
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing [ProcessOutput::rows] parsed from the stdout, the order of the fields kept
+    #[test]
+    fn rows() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ProcessOutput.rows");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        // (step, stdout, target fields, target rows)
+        let test_data = [
+            (1, "{\"b\": 1, \"a\": [2]}\n", Some(vec![vec!["b", "a"]]), json!([{"b": 1, "a": [2]}])),
+            (2, "[{\"z\": 1}, {\"y\": null, \"x\": {}}]", Some(vec![vec!["z"], vec!["y", "x"]]), json!([{"z": 1}, {"y": null, "x": {}}])),
+            (3, "null", Some(vec![]), json!([])),
+            (4, " \n", Some(vec![]), json!([])),
+            (5, "[]", Some(vec![]), json!([])),
+            (6, "[1, 2]", None, json!([])),
+            (7, "42", None, json!([])),
+            (8, "{\"b\": 1, \"a\": 2}\n\n{\"a\": 3}\n", Some(vec![vec!["b", "a"], vec!["a"]]), json!([{"b": 1, "a": 2}, {"a": 3}])),
+            (9, "{\"a\": 1}\n[2]\n", None, json!([])),
+            (10, "{\"a\": 1}\nnot json", None, json!([])),
+        ];
+        for (step, stdout, target_fields, target) in test_data {
+            let output = ProcessOutput { status: ExitStatus::from_raw(0), stdout: stdout.as_bytes().to_vec(), stderr: vec![] };
+            let result = output.rows();
+            match target_fields {
+                Some(target_fields) => {
+                    let rows = result.unwrap();
+                    let result: Vec<Vec<&str>> = rows.iter().map(|row| row.keys().map(String::as_str).collect()).collect();
+                    assert!(result == target_fields, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_fields);
+                    let result = serde_json::to_value(&rows).unwrap();
+                    assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+                }
+                None => assert!(result.is_err(), "step {} \nresult: {:?}\ntarget: Err", step, result),
+            }
+        }
+        test_duration.exit();
+    }
+}
@@ -0,0 +1,118 @@
+#[cfg(test)]
+
+mod process_sandbox {
+    use std::{fs, os::unix::fs::PermissionsExt, path::PathBuf, sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::{
+        api::reply::api_reply::ApiReply,
+        client::{api_query::{ApiQuery, ApiQueryExecutable, ApiQueryKind, ApiQueryPython}, api_request::ApiRequest},
+        error::api_error_code::ApiErrorCode,
+        server::{
+            api_query::api_query_type::ApiQueryTypeName,
+            api_server::{api_router::ApiRouter, api_server::ApiServer},
+            config::api_config::ApiConfig,
+            service::{executable_handler::ExecutableHandler, python_handler::PythonHandler, service_registry::ServiceRegistry},
+        },
+    };
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// The executables, (name, source, limits)
+    const EXECUTABLES: [(&str, &str, &str); 5] = [
+        ("cpu", "while :; do :; done", "cpu: 1"),
+        ("user", "printf '{\"uid\": %s, \"gid\": %s}' \"$(id -u)\" \"$(id -g)\"", "uid: 65534, gid: 65534"),
+        ("processes", "sleep 1 &\nsleep 1 &\nwait\nprintf '{}'", "uid: 65533, gid: 65533, processes: 2"),
+        ("privileges", "while read -r key value; do if [ \"$key\" = 'NoNewPrivs:' ]; then printf '{\"noNewPrivs\": %s}' \"$value\"; fi; done < /proc/self/status", "noNewPrivileges: true"),
+        ("read-only", "if (: > file) 2>/dev/null; then printf '{\"written\": true}'; else printf '{\"written\": false}'; fi", "readOnlyWorkDir: true"),
+    ];
+    ///
+    /// The python scripts, (name, source, limits)
+    const SCRIPTS: [(&str, &str, &str); 2] = [
+        ("memory", "b = bytearray(1024 * 1024 * 1024)\nprint('{}')", "memory: 209715200"),
+        ("files", "files = [open('/dev/null') for _ in range(100)]\nprint('{}')", "files: 16"),
+    ];
+    ///
+    /// Returns the server of the python scripts & the executables in the temporary folder, with the limits
+    fn server(dbg: &Dbg, root: &PathBuf) -> ApiServer {
+        let mut yaml = "address: '127.0.0.1:0'\nservices:\n".to_owned();
+        for (name, source, limits) in SCRIPTS {
+            fs::write(root.join(format!("{}.py", name)), source).unwrap();
+            yaml.push_str(&format!("  - {}-py:\n      name: {}\n      type: python\n      path: {}.py\n      timeout: 5\n      limits: {{{}}}\n", name, name, name, limits));
+        }
+        for (name, source, limits) in EXECUTABLES {
+            let path = root.join(name);
+            fs::write(&path, format!("#!/bin/sh\n{}\n", source)).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+            yaml.push_str(&format!(
+                "  - {}:\n      name: {}\n      type: bin\n      path: {}\n      timeout: 5\n      env: {{PATH: '/usr/bin:/bin'}}\n      limits: {{{}}}\n",
+                name, name, name, limits,
+            ));
+        }
+        let registry = ServiceRegistry::from(ApiConfig::from_yaml(&yaml, root).unwrap());
+        let router = ApiRouter::new()
+            .with_handler(ApiQueryTypeName::Python, PythonHandler::from_registry(&registry))
+            .with_handler(ApiQueryTypeName::Executable, ExecutableHandler::from_registry(&registry));
+        ApiServer::new(dbg, "127.0.0.1:0", router)
+    }
+    ///
+    /// Testing the limits applied to the process, the exceeded limit replied with [ApiErrorCode::LimitExceeded]
+    /// - Requires Linux, `python3` & the tests running as root
+    #[test]
+    fn limits() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ProcessSandbox.limits");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(30));
+        test_duration.run().unwrap();
+        let root = std::env::temp_dir().join(format!("api-tools-sandbox-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::set_permissions(&root, fs::Permissions::from_mode(0o755)).unwrap();
+        let mut server = server(&dbg, &root);
+        let address = server.start().unwrap();
+        // (step, query, target rows, target code, target details part)
+        let test_data = [
+            (1, ApiQueryKind::Python(ApiQueryPython::new("memory", Default::default())), json!([]), Some(ApiErrorCode::LimitExceeded), "address space"),
+            (2, ApiQueryKind::Executable(ApiQueryExecutable::new("cpu", Default::default())), json!([]), Some(ApiErrorCode::LimitExceeded), "CPU time"),
+            (3, ApiQueryKind::Python(ApiQueryPython::new("files", Default::default())), json!([]), Some(ApiErrorCode::LimitExceeded), "open files"),
+            (4, ApiQueryKind::Executable(ApiQueryExecutable::new("user", Default::default())), json!([{"uid": 65534, "gid": 65534}]), None, ""),
+            (5, ApiQueryKind::Executable(ApiQueryExecutable::new("processes", Default::default())), json!([]), Some(ApiErrorCode::LimitExceeded), "processes"),
+            (6, ApiQueryKind::Executable(ApiQueryExecutable::new("privileges", Default::default())), json!([{"noNewPrivs": 1}]), None, ""),
+            (7, ApiQueryKind::Executable(ApiQueryExecutable::new("read-only", Default::default())), json!([{"written": false}]), None, ""),
+        ];
+        for (step, query, target, target_code, target_details) in test_data {
+            let query = ApiQuery::new(query, false);
+            let mut request = ApiRequest::new(&dbg, address, "token", query, true, true);
+            let reply = ApiReply::try_from(request.fetch(true).unwrap()).unwrap();
+            let result = serde_json::to_value(&reply.data).unwrap();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            let result = reply.error.code();
+            assert!(result == target_code, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_code);
+            let error = serde_json::to_value(reply.error.clone().debug(true)).unwrap();
+            let result = error["details"].as_str().unwrap_or_default();
+            assert!(result.contains(target_details), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_details);
+        }
+        // The working directory is read only for the process only
+        let result = fs::write(root.join("file"), "").is_ok();
+        assert!(result, "\nresult: {:?}\ntarget: {:?}", result, true);
+        server.stop();
+        fs::remove_dir_all(root).unwrap();
+        test_duration.exit();
+    }
+}
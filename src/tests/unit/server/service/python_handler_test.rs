@@ -0,0 +1,104 @@
+#[cfg(test)]
+
+mod python_handler {
+    use std::{fs, path::PathBuf, sync::Once, time::{Duration, Instant}};
+    use sal_core::dbg::Dbg;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::{
+        api::reply::api_reply::ApiReply,
+        client::{api_query::{ApiQuery, ApiQueryKind, ApiQueryPython}, api_request::ApiRequest},
+        server::{
+            api_query::api_query_type::ApiQueryTypeName,
+            api_server::{api_router::ApiRouter, api_server::ApiServer},
+            config::api_config::ApiConfig,
+            service::{python_handler::PythonHandler, service_registry::ServiceRegistry},
+        },
+    };
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// The scripts, (name, source)
+    const SCRIPTS: [(&str, &str); 7] = [
+        ("double", "import sys, json\nparams = json.load(sys.stdin)\nprint(json.dumps({'bb': params['b'] * 2, 'aa': params['a'] * 2}))"),
+        ("rows", "import sys, json\nparams = json.load(sys.stdin)\nprint(json.dumps([{'n': n} for n in range(params['count'])]))"),
+        ("fail", "import sys\nsys.stdout.write('[]')\nsys.stderr.write('failed badly')\nsys.exit(3)"),
+        ("warn", "import sys\nsys.stderr.write('warning')\nprint('[]')"),
+        ("invalid", "print('not json')"),
+        ("sleep", "import subprocess, time\nsubprocess.Popen(['sleep', '10'])\ntime.sleep(10)"),
+        ("flood", "print('x' * 10000)"),
+    ];
+    ///
+    /// Returns the server of the python scripts in the temporary folder
+    /// - Each script is killed after 1 second or when it's output exceeds 1000 bytes
+    fn server(dbg: &Dbg, root: &PathBuf) -> ApiServer {
+        let mut yaml = "address: '127.0.0.1:0'\nservices:\n".to_owned();
+        for (name, source) in SCRIPTS {
+            fs::write(root.join(format!("{}.py", name)), source).unwrap();
+            yaml.push_str(&format!("  - {}:\n      name: {}\n      type: python\n      path: {}.py\n      timeout: 1\n      outputLimit: 1000\n", name, name, name));
+        }
+        let registry = ServiceRegistry::from(ApiConfig::from_yaml(&yaml, root).unwrap());
+        let router = ApiRouter::new().with_handler(ApiQueryTypeName::Python, PythonHandler::from_registry(&registry));
+        ApiServer::new(dbg, "127.0.0.1:0", router)
+    }
+    ///
+    /// Testing the params passed to the script, the output replied as the rows, the errors replied with the stderr
+    /// - Requires `python3`
+    #[test]
+    fn handle() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test PythonHandler.handle");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(20));
+        test_duration.run().unwrap();
+        let root = std::env::temp_dir().join(format!("api-tools-python-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let mut server = server(&dbg, &root);
+        let address = server.start().unwrap();
+        // (step, script, params, target rows, target message, target details part)
+        let test_data = [
+            (01, "double", json!({"a": 2, "b": 3}), json!([{"bb": 6, "aa": 4}]), "", ""),
+            (02, "rows", json!({"count": 3}), json!([{"n": 0}, {"n": 1}, {"n": 2}]), "", ""),
+            (03, "rows", json!({"count": 0}), json!([]), "", ""),
+            (04, "fail", json!({}), json!([]), "API Python Script Service - script error", "failed badly"),
+            (05, "warn", json!({}), json!([]), "API Python Script Service - script error", "warning"),
+            (06, "invalid", json!({}), json!([]), "API Python Script Service - invalid script output", "not json"),
+            (07, "sleep", json!({}), json!([]), "API Python Script Service - script timeout", "1s"),
+            (08, "flood", json!({}), json!([]), "API Python Script Service - script output exceeds the limit", "1000 bytes"),
+            (09, "missing", json!({}), json!([]), "API Python Script Service - script 'missing' not found", "not found"),
+            (10, "rows", json!({}), json!([]), "API Python Script Service - script error", "KeyError"),
+        ];
+        for (step, script, params, target, target_message, target_details) in test_data {
+            let query = ApiQuery::new(ApiQueryKind::Python(ApiQueryPython::new(script, params.as_object().unwrap().to_owned())), false);
+            let mut request = ApiRequest::new(&dbg, address, "token", query, true, true);
+            let time = Instant::now();
+            let reply = ApiReply::try_from(request.fetch(true).unwrap()).unwrap();
+            assert!(time.elapsed() < Duration::from_secs(5), "step {} \nresult: {:?}\ntarget: < 5s", step, time.elapsed());
+            let result = serde_json::to_value(&reply.data).unwrap();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            let error = serde_json::to_value(reply.error.clone().debug(true)).unwrap();
+            let result = error["message"].as_str().unwrap_or_default();
+            assert!(result == target_message, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_message);
+            let result = error["details"].as_str().unwrap_or_default();
+            assert!(result.contains(target_details), "step {} \nresult: {:?}\ntarget: {:?}", step, result, target_details);
+        }
+        server.stop();
+        fs::remove_dir_all(root).unwrap();
+        test_duration.exit();
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(test)]
+
+mod service_registry {
+    use std::{sync::Once, time::Duration};
+    use sal_core::dbg::Dbg;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::server::{config::api_config::ApiConfig, service::service_registry::ServiceRegistry};
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing the services looked up by the name of the query
+    #[test]
+    fn lookup() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test ServiceRegistry.lookup");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let yaml = "address: '127.0.0.1:8899'\nservices:\n  - pg:\n      name: db\n      type: postgres\n      path: host\n  - my:\n      name: local\n      type: mysql\n      path: host\n";
+        let registry = ServiceRegistry::from(ApiConfig::from_yaml(yaml, "").unwrap());
+        // (step, result key, target key)
+        let test_data = [
+            (1, registry.database("db").map(|service| service.key.as_str()), Some("pg")),
+            (2, registry.database("local").map(|service| service.key.as_str()), Some("my")),
+            (3, registry.database("missing").map(|service| service.key.as_str()), None),
+            (4, registry.python("db").map(|service| service.key.as_str()), None),
+            (5, registry.executable("local").map(|service| service.key.as_str()), None),
+            (6, registry.get("my").map(|service| service.key.as_str()), Some("my")),
+        ];
+        for (step, result, target) in test_data {
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let result = registry.services().len();
+        assert!(result == 2, "\nresult: {:?}\ntarget: {:?}", result, 2);
+        test_duration.exit();
+    }
+}
@@ -0,0 +1 @@
+mod sqlite_service_test;
\ No newline at end of file
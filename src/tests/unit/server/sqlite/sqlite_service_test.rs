@@ -0,0 +1,231 @@
+#[cfg(test)]
+
+mod sqlite_service {
+    use std::{fs, path::PathBuf, sync::Once, time::Duration};
+    use rusqlite::Connection;
+    use sal_core::dbg::Dbg;
+    use serde_json::json;
+    use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
+    use testing::stuff::max_test_duration::TestDuration;
+    use crate::{
+        api::reply::api_reply::ApiReply,
+        client::{api_query::{ApiQuery, ApiQueryKind, ApiQuerySql}, api_request::ApiRequest},
+        server::{
+            api_query::{api_query::ApiQuery as ServerQuery, api_query_type::ApiQueryTypeName},
+            api_server::{api_handler::ApiHandler, api_router::ApiRouter, api_server::ApiServer, api_session::ApiSession},
+            config::api_config::ApiConfig,
+            service::{service_registry::ServiceRegistry, sql_handler::SqlHandler},
+        },
+    };
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+                // implement your initialisation code to be called only once for current test file
+        });
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the folder containing `test.sqlite` with the table `item`
+    fn database(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("api-tools-sqlite-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let connection = Connection::open(root.join("test.sqlite")).unwrap();
+        connection.execute_batch(r#"
+            create table item (id integer primary key, name text, price real, active boolean, attrs json, photo blob, created datetime);
+            insert into item values (1, 'bolt', 1.25, 1, '{"size": "M8"}', x'00ff', '2024-01-31 12:00:00');
+            insert into item values (2, null, null, 0, null, null, null);
+        "#).unwrap();
+        root
+    }
+    ///
+    /// Returns the server of the `test.sqlite` in the `root`
+    /// - `options` - additional options of the service, indented yaml lines
+    fn server(dbg: &Dbg, root: &PathBuf, read_only: bool, options: &str) -> ApiServer {
+        let router = SqlHandler::from_registry(&registry(root, read_only, options)).unwrap().register(ApiRouter::new());
+        ApiServer::new(dbg, "127.0.0.1:0", router)
+    }
+    ///
+    /// Returns the registry of the `test.sqlite` in the `root`
+    /// - `options` - additional options of the service, indented yaml lines
+    fn registry(root: &PathBuf, read_only: bool, options: &str) -> ServiceRegistry {
+        let yaml = format!("address: '127.0.0.1:0'\nservices:\n  - lite:\n      name: test\n      type: sqlite\n      path: test.sqlite\n      readOnly: {}\n{}", read_only, options);
+        ServiceRegistry::from(ApiConfig::from_yaml(&yaml, root).unwrap())
+    }
+    ///
+    /// Testing the column values converted into json, the order of the columns kept
+    #[test]
+    fn types() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test SqliteService.types");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let root = database("types");
+        let mut server = server(&dbg, &root, true, "");
+        let address = server.start().unwrap();
+        let columns = ["id", "name", "price", "active", "attrs", "photo", "created"];
+        // (step, sql, params, target columns, target rows)
+        let test_data = [
+            (1, "select * from item order by id", json!([]), &columns[..], json!([
+                {"id": 1, "name": "bolt", "price": 1.25, "active": true, "attrs": {"size": "M8"}, "photo": "AP8=", "created": "2024-01-31 12:00:00"},
+                {"id": 2, "name": null, "price": null, "active": false, "attrs": null, "photo": null, "created": null},
+            ])),
+            (2, "select name, id from item where id = ?", json!([1]), &["name", "id"][..], json!([{"name": "bolt", "id": 1}])),
+            (3, "select id from item where name = :name and price > :price", json!({"price": 1, "name": "bolt"}), &["id"][..], json!([{"id": 1}])),
+            (4, "select id from item where active = ?", json!([false]), &["id"][..], json!([{"id": 2}])),
+        ];
+        for (step, sql, params, columns, target) in test_data {
+            let mut sql = ApiQuerySql::new("test", sql);
+            sql.params = serde_json::from_value(params).unwrap();
+            let query = ApiQuery::new(ApiQueryKind::Sql(sql), false);
+            let mut request = ApiRequest::new(&dbg, address, "token", query, true, true);
+            let reply = ApiReply::try_from(request.fetch(true).unwrap()).unwrap();
+            assert!(!reply.has_error(), "step {} \nresult: {:?}\ntarget: no error", step, reply.error);
+            let result = serde_json::to_value(&reply.data).unwrap();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            // The order of the columns
+            for row in &reply.data {
+                let result: Vec<&str> = row.keys().map(String::as_str).collect();
+                assert!(result == columns, "step {} \nresult: {:?}\ntarget: {:?}", step, result, columns);
+            }
+        }
+        server.stop();
+        fs::remove_dir_all(root).unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing the database opened for reading only or for reading & writing
+    #[test]
+    fn read_only() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test SqliteService.read_only");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let root = database("read_only");
+        // (step, read only, sql, target error details part)
+        let test_data = [
+            (1, true, "insert into item (id) values (3)", Some("readonly")),
+            (2, false, "insert into item (id) values (3)", None),
+            (3, false, "select count(*) as count from item", None),
+            (4, false, "select * from missing", Some("no such table")),
+        ];
+        for (step, read_only, sql, target) in test_data {
+            let mut server = server(&dbg, &root, read_only, "");
+            let address = server.start().unwrap();
+            let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("test", sql)), false);
+            let mut request = ApiRequest::new(&dbg, address, "token", query, true, true);
+            let reply = ApiReply::try_from(request.fetch(true).unwrap()).unwrap();
+            let error = serde_json::to_value(reply.error.clone().debug(true)).unwrap();
+            let result = error.get("details").and_then(|details| details.as_str());
+            let matched = match target {
+                Some(part) => result.is_some_and(|details| details.contains(part)),
+                None => !reply.has_error(),
+            };
+            assert!(matched, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            server.stop();
+        }
+        let result = Connection::open(root.join("test.sqlite")).unwrap().query_row("select count(*) from item", [], |row| row.get::<_, i64>(0)).unwrap();
+        assert!(result == 3, "\nresult: {:?}\ntarget: {:?}", result, 3);
+        fs::remove_dir_all(root).unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing the query of the read only session executed over the connection opened for reading only,
+    /// even if the service isn't `readOnly`
+    #[test]
+    fn read_only_session() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test SqliteService.read_only_session");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let root = database("read_only_session");
+        let handler = SqlHandler::from_registry(&registry(&root, false, "")).unwrap();
+        // The session of the user granted reading only, the query isn't checked by the grants
+        let router = ApiRouter::new().with_handler(ApiQueryTypeName::Sql, move |query: &ServerQuery, session: &mut ApiSession| {
+            session.set_read_only(true);
+            handler.handle(query, session)
+        });
+        let mut server = ApiServer::new(&dbg, "127.0.0.1:0", router);
+        let address = server.start().unwrap();
+        // (step, sql, target error details part)
+        let test_data = [
+            (1, "insert into item (id) values (3)", Some("readonly")),
+            (2, "with item_ids as (select id from item) delete from item", Some("readonly")),
+            (3, "select count(*) as count from item", None),
+        ];
+        for (step, sql, target) in test_data {
+            let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("test", sql)), false);
+            let mut request = ApiRequest::new(&dbg, address, "token", query, true, true);
+            let reply = ApiReply::try_from(request.fetch(true).unwrap()).unwrap();
+            let error = serde_json::to_value(reply.error.clone().debug(true)).unwrap();
+            let result = error.get("details").and_then(|details| details.as_str());
+            let matched = match target {
+                Some(part) => result.is_some_and(|details| details.contains(part)),
+                None => !reply.has_error(),
+            };
+            assert!(matched, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        server.stop();
+        let result = Connection::open(root.join("test.sqlite")).unwrap().query_row("select count(*) from item", [], |row| row.get::<_, i64>(0)).unwrap();
+        assert!(result == 2, "\nresult: {:?}\ntarget: {:?}", result, 2);
+        fs::remove_dir_all(root).unwrap();
+        test_duration.exit();
+    }
+    ///
+    /// Testing the nulls replaced with the defaults of the declared types & the columns, `replaceNullWithDefault`
+    #[test]
+    fn null_defaults() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        println!("");
+        let dbg = Dbg::own("test SqliteService.null_defaults");
+        println!("{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let root = database("null_defaults");
+        Connection::open(root.join("test.sqlite")).unwrap().execute("update item set active = null where id = 2", []).unwrap();
+        let sql = "select *, null as expr from item where id = 2";
+        // (step, options, target)
+        let test_data = [
+            (1, "", json!({"id": 2, "name": null, "price": null, "active": null, "attrs": null, "photo": null, "created": null, "expr": null})),
+            (2, "      replaceNullWithDefault: true\n", json!({
+                "id": 2, "name": "", "price": 0, "active": false, "attrs": null, "photo": null, "created": "1970-01-01T00:00:00", "expr": null,
+            })),
+            (3, "      replaceNullWithDefault:\n        timestamp: '2000-01-01 00:00:00'\n        columns:\n          photo: ''\n          expr: 0\n          name: 'n/a'\n", json!({
+                "id": 2, "name": "n/a", "price": 0, "active": false, "attrs": null, "photo": "", "created": "2000-01-01 00:00:00", "expr": 0,
+            })),
+        ];
+        for (step, options, target) in test_data {
+            let mut server = server(&dbg, &root, true, options);
+            let address = server.start().unwrap();
+            let query = ApiQuery::new(ApiQueryKind::Sql(ApiQuerySql::new("test", sql)), false);
+            let mut request = ApiRequest::new(&dbg, address, "token", query, true, true);
+            let reply = ApiReply::try_from(request.fetch(true).unwrap()).unwrap();
+            assert!(!reply.has_error(), "step {} \nresult: {:?}\ntarget: no error", step, reply.error);
+            let result = serde_json::to_value(&reply.data).unwrap();
+            let target = json!([target]);
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            server.stop();
+        }
+        fs::remove_dir_all(root).unwrap();
+        test_duration.exit();
+    }
+}